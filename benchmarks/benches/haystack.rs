@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::hint::black_box;
+
+use gungraun::prelude::*;
+use simplematch::{dowild, dowild_with, dowild_with_in, Chunks, Options};
+
+const DEFAULT_OPTIONS: Options<u8> = Options::new();
+
+/// Returns the `haystack` split into chunks of `len` bytes.
+fn chunks(haystack: &str, len: usize) -> Vec<&[u8]> {
+    haystack.as_bytes().chunks(len).collect()
+}
+
+// The slice path of `dowild` and `dowild_with` which the other haystacks are compared with
+// spell-checker: disable
+#[library_benchmark]
+#[benches::linearity(
+    ("a*b", &format!("{}b", "a".repeat(100))),
+    (&format!("{}b", "a*".repeat(50)), &format!("{}b", "a".repeat(100))),
+)]
+#[benches::path(
+    ("src/*/*.rs", "src/haystack/lib.rs"),
+    ("*/*/*/*.rs", &"segment/".repeat(25)),
+)]
+// spell-checker: enable
+fn bench_slice(pattern: &str, haystack: &str) -> (bool, bool) {
+    (
+        black_box(dowild(
+            black_box(pattern.as_bytes()),
+            black_box(haystack.as_bytes()),
+        )),
+        black_box(dowild_with(
+            black_box(pattern.as_bytes()),
+            black_box(haystack.as_bytes()),
+            black_box(DEFAULT_OPTIONS),
+        )),
+    )
+}
+
+// spell-checker: disable
+#[library_benchmark]
+#[benches::linearity(
+    ("a*b", &format!("{}b", "a".repeat(100))),
+    (&format!("{}b", "a*".repeat(50)), &format!("{}b", "a".repeat(100))),
+)]
+#[benches::path(
+    ("src/*/*.rs", "src/haystack/lib.rs"),
+    ("*/*/*/*.rs", &"segment/".repeat(25)),
+)]
+// spell-checker: enable
+fn bench_vec_deque(pattern: &str, haystack: &str) -> bool {
+    let haystack = haystack.bytes().collect::<VecDeque<u8>>();
+    black_box(dowild_with_in(
+        black_box(pattern.as_bytes()),
+        black_box(&haystack),
+        black_box(DEFAULT_OPTIONS),
+    ))
+}
+
+// spell-checker: disable
+#[library_benchmark]
+#[benches::linearity(
+    ("a*b", &format!("{}b", "a".repeat(100)), 8),
+    (&format!("{}b", "a*".repeat(50)), &format!("{}b", "a".repeat(100)), 8),
+)]
+#[benches::single_bytes(
+    ("a*b", &format!("{}b", "a".repeat(100)), 1),
+    (&format!("{}b", "a*".repeat(50)), &format!("{}b", "a".repeat(100)), 1),
+)]
+#[benches::path(
+    ("src/*/*.rs", "src/haystack/lib.rs", 4),
+    ("*/*/*/*.rs", &"segment/".repeat(25), 8),
+)]
+// spell-checker: enable
+fn bench_chunks(pattern: &str, haystack: &str, len: usize) -> bool {
+    let chunks = chunks(haystack, len);
+    black_box(dowild_with_in(
+        black_box(pattern.as_bytes()),
+        black_box(&Chunks::new(&chunks)),
+        black_box(DEFAULT_OPTIONS),
+    ))
+}
+
+library_benchmark_group!(
+    name = haystack;
+    benchmarks =
+        bench_chunks,
+        bench_slice,
+        bench_vec_deque,
+);
+
+main!(library_benchmark_groups = haystack);
@@ -0,0 +1,39 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simplematch::{dowild_with, Options, Pattern};
+use simplematch_fuzz::FuzzOptions;
+
+fuzz_target!(|data: (&[u8], &[u8], FuzzOptions)| {
+    let (pattern, haystack, fuzz_options) = data;
+
+    let mut options = Options::new()
+        .case_insensitive(!fuzz_options.case_sensitive)
+        .enable_classes(fuzz_options.use_ranges)
+        .enable_escape(fuzz_options.enable_escape);
+
+    if fuzz_options.use_other_negate {
+        options.class_negate = b'#';
+    }
+    if fuzz_options.use_other_wildcard_any {
+        options.wildcard_any = b'%';
+    }
+    if fuzz_options.use_other_wildcard_one {
+        options.wildcard_one = b'_';
+    }
+    if fuzz_options.use_other_wildcard_escape {
+        options.wildcard_escape = b'=';
+    }
+
+    if let Ok(compiled) = Pattern::new(pattern, options) {
+        assert_eq!(
+            compiled.is_match(haystack),
+            dowild_with(pattern, haystack, options),
+            "The compiled pattern should match like dowild_with:\noptions: '{:?}'\npattern: \
+             '{:?}'\nhaystack: '{:?}'",
+            options,
+            pattern,
+            haystack
+        );
+    }
+});
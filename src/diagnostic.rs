@@ -0,0 +1,276 @@
+//! The diagnostics of malformed patterns
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::pattern::Token;
+use crate::program::Lexeme;
+use crate::{Options, Pattern, SimpleMatchError, Wildcard};
+
+/// A malformed construct in a pattern, which is matched literally instead
+///
+/// Returned by [`validate`](crate::validate) within
+/// [`SimpleMatchError::InvalidPattern`](crate::SimpleMatchError::InvalidPattern). The
+/// `Display` implementation renders the pattern with a caret under the problem:
+///
+/// ```rust
+/// use simplematch::{validate, Options, SimpleMatchError};
+///
+/// let error = match validate(b"*.[ch", Options::default().enable_classes(true)) {
+///     Err(SimpleMatchError::InvalidPattern(error)) => error,
+///     _ => unreachable!(),
+/// };
+///
+/// assert_eq!(error.span(), 2..5);
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid pattern: The character class is not closed.\n    *.[ch\n      ^^^\nHelp: Close \
+///      the class with `]`."
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PatternError {
+    /// The start and end column of the caret in the `pattern`
+    caret: (usize, usize),
+    fix: String,
+    kind: PatternErrorKind,
+    /// The pattern rendered as text
+    pattern: String,
+    /// The start and end index of the problem in the original pattern
+    span: (usize, usize),
+}
+
+/// The kind of a [`PatternError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum PatternErrorKind {
+    /// The pattern ends with the escape character, which then matches itself
+    TrailingEscape,
+    /// A brace alternation `{` has no closing brace `}`
+    UnclosedBrace,
+    /// A character class `[` has no closing bracket `]`
+    UnclosedClass,
+    /// An extended glob like `@(` has no closing parenthesis `)`
+    UnclosedExtglob,
+    /// A closing brace `}` has no opening brace `{`
+    UnmatchedBraceClose,
+    /// A closing parenthesis `)` has no extended glob to close
+    UnmatchedExtglobClose,
+}
+
+impl PatternError {
+    /// Create a new `PatternError` of the `kind` at the `span` of the `pattern`.
+    ///
+    /// The `fix` is built from the tokens of the [`Options`].
+    fn new<T>(kind: PatternErrorKind, span: Range<usize>, pattern: &[T], options: Options<T>) -> Self
+    where
+        T: Wildcard,
+    {
+        let token = |token: T| T::to_char(token).unwrap_or(char::REPLACEMENT_CHARACTER);
+        let escape = token(options.wildcard_escape);
+        let escaped = |what: T| {
+            if options.is_escape_enabled {
+                format_fix(format_args!(" or escape it as `{escape}{}`", token(what)))
+            } else {
+                String::new()
+            }
+        };
+
+        let fix = match kind {
+            PatternErrorKind::TrailingEscape => format_fix(format_args!(
+                "Remove the `{escape}` or escape it as `{escape}{escape}` to match it literally."
+            )),
+            PatternErrorKind::UnclosedBrace => format_fix(format_args!(
+                "Close the alternation with `{}`{}.",
+                token(T::DEFAULT_BRACE_CLOSE),
+                escaped(T::DEFAULT_BRACE_OPEN)
+            )),
+            PatternErrorKind::UnclosedClass => format_fix(format_args!(
+                "Close the class with `{}`{}.",
+                token(options.class_close),
+                escaped(options.class_open)
+            )),
+            PatternErrorKind::UnclosedExtglob => format_fix(format_args!(
+                "Close the group with `{}`{}.",
+                token(T::DEFAULT_EXTGLOB_CLOSE),
+                escaped(T::DEFAULT_EXTGLOB_OPEN)
+            )),
+            PatternErrorKind::UnmatchedBraceClose => format_fix(format_args!(
+                "Open an alternation with `{}` before it{}.",
+                token(T::DEFAULT_BRACE_OPEN),
+                escaped(T::DEFAULT_BRACE_CLOSE)
+            )),
+            PatternErrorKind::UnmatchedExtglobClose => format_fix(format_args!(
+                "Open a group like `{}{}` before it{}.",
+                token(T::DEFAULT_EXTGLOB_AT),
+                token(T::DEFAULT_EXTGLOB_OPEN),
+                escaped(T::DEFAULT_EXTGLOB_CLOSE)
+            )),
+        };
+
+        // Control characters would break the rendering with the caret
+        let pattern = pattern
+            .iter()
+            .map(|c| match T::to_char(*c) {
+                Some(c) if !c.is_control() => c,
+                _ => char::REPLACEMENT_CHARACTER,
+            })
+            .collect();
+
+        Self {
+            caret: (span.start, span.end),
+            fix,
+            kind,
+            pattern,
+            span: (span.start, span.end),
+        }
+    }
+
+    /// Returns a suggestion how to fix the pattern.
+    #[must_use]
+    pub fn fix(&self) -> &str {
+        &self.fix
+    }
+
+    /// Returns the kind of this error.
+    #[must_use]
+    pub const fn kind(&self) -> PatternErrorKind {
+        self.kind
+    }
+
+    /// Returns the span of the malformed construct in the pattern.
+    ///
+    /// The span are the indices of the tokens in the pattern, so these are byte offsets for a
+    /// `&[u8]` and character indices for a `&[char]`. The span of
+    /// [`utf8::validate`](crate::utf8::validate) consists of byte offsets on character
+    /// boundaries.
+    #[must_use]
+    pub const fn span(&self) -> Range<usize> {
+        self.span.0..self.span.1
+    }
+
+    /// Convert the span from character indices to the byte offsets of the `pattern`.
+    pub(crate) fn into_byte_span(mut self, pattern: &str) -> Self {
+        let byte_offset = |index: usize| {
+            pattern
+                .char_indices()
+                .nth(index)
+                .map_or(pattern.len(), |(offset, _)| offset)
+        };
+        self.span = (byte_offset(self.span.0), byte_offset(self.span.1));
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PatternError {}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self.kind {
+            PatternErrorKind::TrailingEscape => "The pattern ends with the escape character",
+            PatternErrorKind::UnclosedBrace => "The brace alternation is not closed",
+            PatternErrorKind::UnclosedClass => "The character class is not closed",
+            PatternErrorKind::UnclosedExtglob => "The extended glob is not closed",
+            PatternErrorKind::UnmatchedBraceClose => "The closing brace has no opening brace",
+            PatternErrorKind::UnmatchedExtglobClose => {
+                "The closing parenthesis has no extended glob to close"
+            }
+        };
+        let (start, end) = self.caret;
+
+        writeln!(f, "Invalid pattern: {message}.")?;
+        writeln!(f, "    {}", self.pattern)?;
+        writeln!(
+            f,
+            "    {:start$}{:^<width$}",
+            "",
+            "",
+            width = (end - start).max(1)
+        )?;
+        write!(f, "Help: {}", self.fix)
+    }
+}
+
+/// Returns the formatted `args`
+fn format_fix(args: core::fmt::Arguments<'_>) -> String {
+    use core::fmt::Write;
+
+    let mut fix = String::new();
+    // Writing to a `String` never fails
+    let _ = fix.write_fmt(args);
+    fix
+}
+
+/// Returns the leftmost malformed construct of the `pattern`.
+///
+/// The [`Options`] are not verified.
+pub fn validate<T>(pattern: &[T], options: Options<T>) -> Result<(), PatternError>
+where
+    T: Wildcard + Ord,
+{
+    let mut starts = Vec::new();
+    let lexemes = Pattern::lex(pattern, options, |start| starts.push(start));
+    let is_matched = Lexeme::match_groups(&lexemes);
+
+    for (index, lexeme) in lexemes.iter().enumerate() {
+        let start = starts[index];
+        let end = starts.get(index + 1).copied().unwrap_or(pattern.len());
+        let kind = match lexeme {
+            Lexeme::BraceClose if !is_matched[index] => PatternErrorKind::UnmatchedBraceClose,
+            Lexeme::BraceOpen if !is_matched[index] => PatternErrorKind::UnclosedBrace,
+            Lexeme::ExtglobClose if !is_matched[index] => PatternErrorKind::UnmatchedExtglobClose,
+            Lexeme::ExtglobOpen(_) if !is_matched[index] => PatternErrorKind::UnclosedExtglob,
+            // A class which is not closed is lexed as literal opening bracket and spans the
+            // rest of the pattern
+            Lexeme::Token(Token::Literal(c))
+                if options.is_classes_enabled && *c == options.class_open =>
+            {
+                let span = start..pattern.len();
+                return Err(PatternError::new(
+                    PatternErrorKind::UnclosedClass,
+                    span,
+                    pattern,
+                    options,
+                ));
+            }
+            // The escape character can only stand for itself at the end of the pattern
+            Lexeme::Token(Token::Literal(c))
+                if options.is_escape_enabled
+                    && *c == options.wildcard_escape
+                    && end == pattern.len() =>
+            {
+                PatternErrorKind::TrailingEscape
+            }
+            _ => continue,
+        };
+
+        return Err(PatternError::new(kind, start..end, pattern, options));
+    }
+
+    Ok(())
+}
+
+/// Returns an error if the [`Options`] are strict and the `pattern` is malformed.
+///
+/// The [`Options`] are not verified.
+pub fn validate_strict<T>(pattern: &[T], options: Options<T>) -> Result<(), SimpleMatchError>
+where
+    T: Wildcard + Ord,
+{
+    if options.is_strict {
+        validate(pattern, options).map_err(SimpleMatchError::InvalidPattern)
+    } else {
+        Ok(())
+    }
+}
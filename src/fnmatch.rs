@@ -0,0 +1,523 @@
+//! A POSIX `fnmatch(3)` compatible interface
+//!
+//! The [`fnmatch`] function takes the same flags as the C function and matches the way glibc
+//! does, so C code calling `fnmatch` can be ported without changing its patterns:
+//!
+//! ```rust
+//! use simplematch::fnmatch::{fnmatch, FNM_CASEFOLD, FNM_LEADING_DIR, FNM_PATHNAME, FNM_PERIOD};
+//!
+//! let flags = FNM_PATHNAME | FNM_PERIOD | FNM_CASEFOLD | FNM_LEADING_DIR;
+//!
+//! assert_eq!(fnmatch(b"src/*.RS", b"src/lib.rs", flags), true);
+//! assert_eq!(fnmatch(b"src", b"src/lib.rs", flags), true);
+//! assert_eq!(fnmatch(b"src/*", b"src/.hidden", flags), false);
+//! ```
+//!
+//! The flags map onto [`Options`] (see the [`From`] implementation), but the pattern syntax
+//! follows glibc and not the syntax of [`dowild_with`](crate::dowild_with):
+//!
+//! * A backslash escapes any character unless [`FNM_NOESCAPE`] is given, also inside of
+//!   bracket expressions. A pattern ending in an unescaped backslash never matches.
+//! * A bracket expression is negated with `!` or `^`. A `]` directly after the `[` or the
+//!   negation is a literal member. A range with a start greater than its end is empty. A
+//!   bracket expression without closing `]` matches the `[` literally.
+//! * Bracket expressions can contain the named classes like `[:alpha:]` with the ASCII
+//!   characters of the C locale, collating symbols `[.a.]` and equivalence classes `[=a=]` of
+//!   a single character. [`FNM_CASEFOLD`] doesn't fold named classes, collating symbols and
+//!   equivalence classes.
+//! * With [`FNM_PATHNAME`], a bracket expression never matches a `/`, not even `[/]`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::{BitOr, BitOrAssign};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::pattern::Token;
+use crate::program::Lexeme;
+use crate::{Class, ClassKind, NamedClass, Options, Pattern};
+
+/// Case-insensitive matching like `FNM_CASEFOLD`
+///
+/// Only ASCII characters are folded.
+pub const FNM_CASEFOLD: Flags = Flags(1 << 4);
+/// An alias of [`FNM_PATHNAME`] like `FNM_FILE_NAME`
+pub const FNM_FILE_NAME: Flags = FNM_PATHNAME;
+/// Matching a leading directory like `FNM_LEADING_DIR`
+///
+/// The pattern also matches if it matches a part of the string which is followed by a `/`.
+/// For example, `src` matches `src/lib.rs`.
+pub const FNM_LEADING_DIR: Flags = Flags(1 << 3);
+/// Disable backslash escapes like `FNM_NOESCAPE`
+pub const FNM_NOESCAPE: Flags = Flags(1 << 1);
+/// Match a `/` only with a literal `/` like `FNM_PATHNAME`
+///
+/// Neither `*`, `?` nor bracket expressions match a `/`.
+pub const FNM_PATHNAME: Flags = Flags(1 << 0);
+/// Match a leading `.` only with a literal `.` like `FNM_PERIOD`
+///
+/// A period is leading at the start of the string and, with [`FNM_PATHNAME`], after a `/`.
+pub const FNM_PERIOD: Flags = Flags(1 << 2);
+
+/// The flags of the [`fnmatch`] function
+///
+/// The bits have the same values as the `FNM_*` constants of glibc. Flags are combined with
+/// `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Flags(i32);
+
+impl BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Flags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Flags {
+    /// Returns the raw bits of these `Flags`.
+    #[must_use]
+    pub const fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// Returns `true` if all bits of `other` are set in these `Flags`.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// `Flags` without any flag set.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Create `Flags` from the raw `bits` like the `flags` argument of the C function.
+    ///
+    /// Bits which don't belong to any flag are dropped.
+    #[must_use]
+    pub const fn from_bits_truncate(bits: i32) -> Self {
+        Self(
+            bits & (FNM_CASEFOLD.0
+                | FNM_LEADING_DIR.0
+                | FNM_NOESCAPE.0
+                | FNM_PATHNAME.0
+                | FNM_PERIOD.0),
+        )
+    }
+}
+
+impl From<Flags> for Options<u8> {
+    /// The [`Options`] equivalent to the `flags`
+    ///
+    /// Classes are always enabled and escapes unless [`FNM_NOESCAPE`] is given.
+    /// [`FNM_LEADING_DIR`] has no equivalent in the [`Options`].
+    fn from(flags: Flags) -> Self {
+        let options = Self::new()
+            .case_insensitive(flags.contains(FNM_CASEFOLD))
+            .enable_classes(true)
+            .enable_escape(!flags.contains(FNM_NOESCAPE))
+            .protect_leading_dot(flags.contains(FNM_PERIOD));
+
+        if flags.contains(FNM_PATHNAME) {
+            options.path_separator_with(b'/')
+        } else {
+            options
+        }
+    }
+}
+
+/// Returns `true` if the `pattern` matches the `string` like `fnmatch(3)` of glibc returns 0.
+///
+/// See the [module documentation](self) for the pattern syntax.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::fnmatch::{fnmatch, Flags, FNM_NOESCAPE, FNM_PATHNAME};
+///
+/// assert_eq!(fnmatch(b"*.[ch]", b"main.c", Flags::empty()), true);
+/// assert_eq!(fnmatch(b"[^.]*", b".bashrc", Flags::empty()), false);
+/// assert_eq!(fnmatch(b"\\*", b"*", Flags::empty()), true);
+/// assert_eq!(fnmatch(b"\\*", b"\\x", FNM_NOESCAPE), true);
+/// assert_eq!(fnmatch(b"*", b"a/b", FNM_PATHNAME), false);
+/// ```
+#[must_use]
+pub fn fnmatch(pattern: &[u8], string: &[u8], flags: Flags) -> bool {
+    let lexemes = match lex(pattern, flags) {
+        Some(lexemes) => lexemes,
+        None => return false,
+    };
+    if flags.contains(FNM_PERIOD) && has_star_at_leading_period(&lexemes, string, flags) {
+        return false;
+    }
+    // The lexemes are folded already, since glibc doesn't fold everything in bracket expressions
+    let pattern = Pattern::from_lexemes(lexemes, Options::from(flags).case_insensitive(false));
+
+    pattern.is_match(string)
+        || (flags.contains(FNM_LEADING_DIR)
+            && string
+                .iter()
+                .enumerate()
+                .any(|(idx, c)| *c == b'/' && pattern.is_match(&string[..idx])))
+}
+
+/// Returns `true` if a `*` of the pattern is matched at a leading period of the `string`
+///
+/// Unlike the leading dot protection of the [`Options`], glibc fails with `FNM_PERIOD` as soon
+/// as a `*` is at a leading period, even if the `*` could match the empty string. Since
+/// nothing but a literal `/` matches a `/` with [`FNM_PATHNAME`], the segments of the pattern
+/// match the segments of the `string` with the same index. Without it, only the start of the
+/// `string` has a leading period.
+fn has_star_at_leading_period(lexemes: &[Lexeme<u8>], string: &[u8], flags: Flags) -> bool {
+    let is_separator = |lexeme: &Lexeme<u8>| matches!(lexeme, Lexeme::Token(Token::Literal(b'/')));
+
+    if flags.contains(FNM_PATHNAME) {
+        lexemes
+            .split(is_separator)
+            .zip(string.split(|c| *c == b'/'))
+            .any(|(segment, s)| {
+                matches!(segment.first(), Some(Lexeme::Token(Token::Any)))
+                    && s.first() == Some(&b'.')
+            })
+    } else {
+        matches!(lexemes.first(), Some(Lexeme::Token(Token::Any))) && string.first() == Some(&b'.')
+    }
+}
+
+/// The result of matching a single character with a bracket expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bracket {
+    /// The bracket expression is malformed, so the pattern can't match at this position
+    Invalid,
+    /// The character matches and the closing `]` is at the index
+    Match(usize),
+    /// The character doesn't match and the closing `]` is at the index
+    NoMatch(usize),
+    /// There is no closing `]`, so the `[` is matched literally
+    Unterminated,
+}
+
+/// Collect the members of a set into ranges of consecutive bytes
+fn into_class(members: &[bool; 256]) -> Class<u8> {
+    let mut class = Class::new_positive();
+
+    let mut start = None;
+    for (c, is_member) in (0..=u8::MAX).zip(members.iter().copied()) {
+        match (start, is_member) {
+            (None, true) => start = Some(c),
+            (Some(low), false) => {
+                let high = c - 1;
+                class.push(if low == high {
+                    ClassKind::One(low)
+                } else {
+                    ClassKind::Range(low, high)
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(low) = start {
+        class.push(ClassKind::Range(low, u8::MAX));
+    }
+
+    class
+}
+
+/// Split the glibc `pattern` into the lexemes of a [`Pattern`]
+///
+/// Returns `None` if the pattern can never match.
+fn lex(pattern: &[u8], flags: Flags) -> Option<Vec<Lexeme<u8>>> {
+    let is_escape_enabled = !flags.contains(FNM_NOESCAPE);
+    let is_case_folded = flags.contains(FNM_CASEFOLD);
+    let is_pathname = flags.contains(FNM_PATHNAME);
+
+    let mut lexemes = Vec::with_capacity(pattern.len());
+    let mut p_idx = 0;
+
+    while p_idx < pattern.len() {
+        let token = match pattern[p_idx] {
+            // glibc lets a `*` with `FNM_PATHNAME` only try the positions before the next `/`,
+            // so a following escaped `/` can never match
+            b'*' if is_pathname
+                && is_escape_enabled
+                && pattern[p_idx..]
+                    .iter()
+                    .position(|c| !matches!(c, b'*' | b'?'))
+                    .map_or(false, |idx| pattern[p_idx + idx..].starts_with(b"\\/")) =>
+            {
+                return None
+            }
+            b'*' => Token::Any,
+            b'?' => Token::One,
+            // A trailing backslash never matches
+            b'\\' if is_escape_enabled => {
+                p_idx += 1;
+                Token::Literal(*pattern.get(p_idx)?)
+            }
+            b'[' => match parse_bracket(pattern, p_idx + 1, flags).ok()? {
+                Some((class, end)) => {
+                    p_idx = end;
+                    Token::Class(class)
+                }
+                None => Token::Literal(b'['),
+            },
+            c => Token::Literal(c),
+        };
+
+        let token = match token {
+            Token::Literal(c) if is_case_folded && c.is_ascii_alphabetic() => {
+                let mut class = Class::new_positive();
+                class.push(ClassKind::One(c.to_ascii_lowercase()));
+                class.push(ClassKind::One(c.to_ascii_uppercase()));
+                Token::Class(class)
+            }
+            token => token,
+        };
+        lexemes.push(Lexeme::Token(token));
+        p_idx += 1;
+    }
+
+    Some(lexemes)
+}
+
+/// Parse the bracket expression starting after the `[` at `start`
+///
+/// Returns the class and the index of the closing `]`, `Ok(None)` if the bracket expression is
+/// not terminated and `Err(())` if the bracket expression can never match.
+///
+/// Whether glibc considers a bracket expression malformed depends on the matched character,
+/// so the bracket expression is evaluated for every byte like glibc does.
+fn parse_bracket(
+    pattern: &[u8],
+    start: usize,
+    flags: Flags,
+) -> Result<Option<(Class<u8>, usize)>, ()> {
+    let mut members = [false; 256];
+    let mut end = None;
+    let mut is_unterminated = false;
+
+    for (c, is_member) in (0..=u8::MAX).zip(members.iter_mut()) {
+        match match_bracket(pattern, start, c, flags) {
+            Bracket::Match(idx) => {
+                *is_member = true;
+                end = end.or(Some(idx));
+            }
+            Bracket::NoMatch(idx) => end = end.or(Some(idx)),
+            Bracket::Unterminated if c == b'[' => is_unterminated = true,
+            Bracket::Invalid | Bracket::Unterminated => {}
+        }
+    }
+
+    if is_unterminated {
+        return Ok(None);
+    }
+    // With `FNM_PATHNAME`, a `/` is never matched by a bracket expression
+    if flags.contains(FNM_PATHNAME) {
+        members[usize::from(b'/')] = false;
+    }
+
+    end.map(|end| Some((into_class(&members), end))).ok_or(())
+}
+
+/// Match the character `c` with the bracket expression starting after the `[` at `start`
+///
+/// This follows the implementation of glibc in the C locale closely. Past the end of the
+/// `pattern`, the index yields a `0` like the terminating null character of a C string.
+#[allow(clippy::too_many_lines)]
+fn match_bracket(pattern: &[u8], start: usize, c: u8, flags: Flags) -> Bracket {
+    let is_escape_enabled = !flags.contains(FNM_NOESCAPE);
+    let fold = |c: u8| {
+        if flags.contains(FNM_CASEFOLD) {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        }
+    };
+    let at = |idx: usize| pattern.get(idx).copied().unwrap_or(0);
+    // Returns the symbol of the collating symbol `[.c.]` starting with the `.` at `idx` and
+    // the index after it. Only single characters are supported like in the C locale.
+    let collating_symbol = |idx: usize| {
+        let mut q = idx + 1;
+        while !(at(q) == b'.' && at(q + 1) == b']') {
+            if at(q) == 0 {
+                return None;
+            }
+            q += 1;
+        }
+        (q == idx + 2).then(|| (at(idx + 1), q + 2))
+    };
+    // Returns the name of the class `[:name:]` starting with the `:` at `idx` and the index
+    // after it. glibc only considers the characters from `a` to `y` for class names.
+    let class_name = |idx: usize| {
+        let len = pattern[idx + 1..]
+            .iter()
+            .position(|c| !(b'a'..=b'y').contains(c))
+            .unwrap_or(pattern.len() - idx - 1);
+        let end = idx + 1 + len;
+        (at(end) == b':' && at(end + 1) == b']').then(|| (&pattern[idx + 1..end], end + 2))
+    };
+
+    let folded = fold(c);
+    let mut p_idx = start;
+    let is_negated = matches!(at(p_idx), b'!' | b'^');
+    if is_negated {
+        p_idx += 1;
+    }
+
+    let mut b = at(p_idx);
+    p_idx += 1;
+    let is_match = loop {
+        // The single character or the start of a range and whether it is a collating symbol
+        let (low, is_collating) = if is_escape_enabled && b == b'\\' {
+            if at(p_idx) == 0 {
+                return Bracket::Invalid;
+            }
+            p_idx += 1;
+            (fold(at(p_idx - 1)), false)
+        } else if b == b'[' && at(p_idx) == b':' {
+            if let Some((name, after)) = class_name(p_idx) {
+                match NamedClass::from_name(name) {
+                    Some(class) if class.is_ascii_member(c) => {
+                        p_idx = after;
+                        break true;
+                    }
+                    Some(_) => {
+                        b = at(after);
+                        p_idx = after + 1;
+                        if b == b']' {
+                            break false;
+                        }
+                        continue;
+                    }
+                    None => return Bracket::Invalid,
+                }
+            }
+            (b'[', false)
+        } else if b == b'[' && at(p_idx) == b'=' {
+            if at(p_idx + 1) != 0 && at(p_idx + 2) == b'=' && at(p_idx + 3) == b']' {
+                if c == at(p_idx + 1) {
+                    p_idx += 4;
+                    break true;
+                }
+                b = at(p_idx + 4);
+                p_idx += 5;
+                if b == b']' {
+                    break false;
+                }
+                continue;
+            }
+            (b'[', false)
+        } else if b == 0 {
+            return Bracket::Unterminated;
+        } else if b == b'[' && at(p_idx) == b'.' {
+            match collating_symbol(p_idx) {
+                Some((symbol, after)) => {
+                    p_idx = after;
+                    (symbol, true)
+                }
+                None => return Bracket::Invalid,
+            }
+        } else {
+            (fold(b), false)
+        };
+
+        let is_range = at(p_idx) == b'-' && at(p_idx + 1) != 0 && at(p_idx + 1) != b']';
+        // glibc compares collating symbols with the character which isn't folded
+        if !is_range && ((is_collating && c == low) || (!is_collating && folded == low)) {
+            break true;
+        }
+
+        b = at(p_idx);
+        p_idx += 1;
+        if b == b'-' && at(p_idx) != b']' {
+            let mut high = at(p_idx);
+            p_idx += 1;
+            if is_escape_enabled && high == b'\\' {
+                high = at(p_idx);
+                p_idx += 1;
+            }
+            if high == b'[' && at(p_idx) == b'.' {
+                match collating_symbol(p_idx) {
+                    Some((symbol, after)) => {
+                        high = symbol;
+                        p_idx = after;
+                    }
+                    None => return Bracket::Invalid,
+                }
+            }
+            if high == 0 {
+                return Bracket::Invalid;
+            }
+            if low <= folded && folded <= fold(high) {
+                break true;
+            }
+            b = at(p_idx);
+            p_idx += 1;
+        }
+
+        if b == b']' {
+            break false;
+        }
+    };
+
+    if !is_match {
+        return if is_negated {
+            Bracket::Match(p_idx - 1)
+        } else {
+            Bracket::NoMatch(p_idx - 1)
+        };
+    }
+
+    // Skip the rest of the bracket expression which is validated less strictly
+    loop {
+        let b = at(p_idx);
+        p_idx += 1;
+        match b {
+            0 => return Bracket::Unterminated,
+            b'\\' if is_escape_enabled => {
+                if at(p_idx) == 0 {
+                    return Bracket::Invalid;
+                }
+                p_idx += 1;
+            }
+            b'[' if at(p_idx) == b':' => {
+                if let Some((_, after)) = class_name(p_idx) {
+                    p_idx = after;
+                }
+            }
+            b'[' if at(p_idx) == b'=' => {
+                if at(p_idx + 1) == 0 || at(p_idx + 2) != b'=' || at(p_idx + 3) != b']' {
+                    return Bracket::Invalid;
+                }
+                p_idx += 4;
+            }
+            b'[' if at(p_idx) == b'.' => {
+                let mut q = p_idx + 1;
+                while !(at(q) == b'.' && at(q + 1) == b']') {
+                    if at(q) == 0 {
+                        return Bracket::Invalid;
+                    }
+                    q += 1;
+                }
+                p_idx = q + 2;
+            }
+            b']' => break,
+            _ => {}
+        }
+    }
+
+    if is_negated {
+        Bracket::NoMatch(p_idx - 1)
+    } else {
+        Bracket::Match(p_idx - 1)
+    }
+}
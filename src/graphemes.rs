@@ -0,0 +1,293 @@
+//! Matching on the grapheme clusters of a `str` with the `graphemes` feature
+//!
+//! With the functions of the [`utf8`](crate::utf8) module, a `?` matches a single character.
+//! However, a user-perceived character can consist of multiple characters like an `é` written
+//! as `e` and a combining accent, a flag or a family emoji. The functions of this module match
+//! the extended grapheme clusters of the haystack instead, so `??` matches two visible
+//! characters:
+//!
+//! ```rust
+//! use simplematch::{graphemes, utf8};
+//!
+//! let haystack = "e\u{301}🇩🇪";
+//!
+//! assert_eq!(utf8::dowild("??", haystack), false);
+//! assert_eq!(graphemes::dowild("??", haystack), true);
+//! ```
+//!
+//! A `?`, a `*` and a negated class consume whole grapheme clusters. A class like `[äöü]`
+//! matches a grapheme cluster only if it consists of a single character of the class.
+//! Literal characters of the pattern have to match all characters of a grapheme cluster, so
+//! `e` doesn't match the `e` of the decomposed `é`. All reported spans are byte offsets on
+//! grapheme cluster boundaries of the haystack.
+//!
+//! The grapheme clusters are found with the [`unicode-segmentation`] crate. Like the [`utf8`]
+//! module, the pattern is collected into characters on every call. If the same pattern is
+//! used multiple times, it is more efficient to create a [`Pattern`] of characters once and
+//! use [`Pattern::is_match_graphemes`] and its siblings.
+//!
+//! [`unicode-segmentation`]: https://docs.rs/unicode-segmentation
+//! [`utf8`]: crate::utf8
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use unicode_segmentation::GraphemeCursor;
+
+use crate::haystack::Haystack;
+use crate::{Options, Pattern};
+
+/// A `str` which is matched grapheme cluster by grapheme cluster
+struct Graphemes<'h>(&'h str);
+
+impl Haystack<char> for Graphemes<'_> {
+    const HAS_CLUSTERS: bool = true;
+    const IS_INDEXED: bool = false;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        self.0.next(pos)
+    }
+
+    #[inline]
+    fn next_cluster(&self, pos: usize, tokens: &mut Vec<char>) -> Option<(Option<char>, usize)> {
+        let end = self.cluster_end(pos)?;
+        tokens.extend(self.0[pos..end].chars());
+        let single = match tokens.as_slice() {
+            [c] => Some(*c),
+            _ => None,
+        };
+        Some((single, end))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(char, usize)> {
+        self.0.prev(pos)
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        self.cluster_end(pos).unwrap_or(pos + 1)
+    }
+}
+
+impl Graphemes<'_> {
+    /// Returns the end of the grapheme cluster starting at `pos` or `None` at the end
+    #[inline]
+    fn cluster_end(&self, pos: usize) -> Option<usize> {
+        let bytes = self.0.as_bytes();
+        let first = *bytes.get(pos)?;
+
+        // Two ASCII characters are always separate grapheme clusters except for `\r\n`
+        match bytes.get(pos + 1) {
+            None => return Some(pos + 1),
+            Some(second) if first.is_ascii() && second.is_ascii() && first != b'\r' => {
+                return Some(pos + 1);
+            }
+            _ => {}
+        }
+
+        GraphemeCursor::new(pos, self.0.len(), true)
+            .next_boundary(self.0, 0)
+            .ok()
+            .flatten()
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Pattern<char> {
+    /// Returns the spans of the `haystack` matched by each `*`, `?` and character class like
+    /// [`Pattern::captures`] but on the grapheme clusters of the `haystack`.
+    ///
+    /// The spans are byte offsets on grapheme cluster boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['*', '?'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     pattern.captures_graphemes("cafe\u{301}"),
+    ///     Some(vec![0..3, 3..6])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn captures_graphemes(&self, haystack: &str) -> Option<Vec<Range<usize>>> {
+        self.captures_in(&Graphemes(haystack))
+    }
+
+    /// Returns the leftmost and shortest span of the `haystack` matched by this `Pattern` like
+    /// [`Pattern::find`] but on the grapheme clusters of the `haystack`.
+    ///
+    /// The span is a byte range on grapheme cluster boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['?', '!'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.find_graphemes("🇩🇪!"), Some(0..9));
+    /// ```
+    #[must_use]
+    pub fn find_graphemes(&self, haystack: &str) -> Option<Range<usize>> {
+        self.find_leftmost(&Graphemes(haystack), false)
+    }
+
+    /// Returns the leftmost and longest span of the `haystack` matched by this `Pattern` like
+    /// [`Pattern::find_longest`] but on the grapheme clusters of the `haystack`.
+    ///
+    /// The span is a byte range on grapheme cluster boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['e', '*'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.find_longest_graphemes("e\u{301}e!"), Some(3..5));
+    /// ```
+    #[must_use]
+    pub fn find_longest_graphemes(&self, haystack: &str) -> Option<Range<usize>> {
+        self.find_leftmost(&Graphemes(haystack), true)
+    }
+
+    /// Returns `true` if this `Pattern` matches the grapheme clusters of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['?', '?'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.is_match_graphemes("e\u{301}👍🏽"), true);
+    /// assert_eq!(pattern.is_match_graphemes("👨‍👩‍👧"), false);
+    /// ```
+    #[must_use]
+    pub fn is_match_graphemes(&self, haystack: &str) -> bool {
+        self.is_match_in(&Graphemes(haystack))
+    }
+}
+
+/// Returns the spans of the `haystack` matched by each `*`, `?` and character class of the
+/// `pattern` or `None` if the `pattern` doesn't match.
+///
+/// Like [`utf8::captures`](crate::utf8::captures) but on the grapheme clusters of the
+/// `haystack`. The spans are byte offsets on grapheme cluster boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{graphemes, Options};
+///
+/// assert_eq!(
+///     graphemes::captures("?*", "🇩🇪🇫🇷", Options::default()),
+///     Some(vec![0..8, 8..16])
+/// );
+/// ```
+#[must_use]
+pub fn captures(pattern: &str, haystack: &str, options: Options<char>) -> Option<Vec<Range<usize>>> {
+    parse(pattern, options).captures_graphemes(haystack)
+}
+
+/// Returns `true` if the wildcard `pattern` matches the grapheme clusters of the `haystack`.
+///
+/// Like [`utf8::dowild`](crate::utf8::dowild) but a `?` matches a single grapheme cluster
+/// instead of a single character.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::graphemes;
+///
+/// assert_eq!(graphemes::dowild("caf?", "cafe\u{301}"), true);
+/// assert_eq!(graphemes::dowild("cafe", "cafe\u{301}"), false);
+/// ```
+#[must_use]
+pub fn dowild(pattern: &str, haystack: &str) -> bool {
+    dowild_with(pattern, haystack, Options::default())
+}
+
+/// Returns `true` if the wildcard `pattern` matches the grapheme clusters of the `haystack`.
+/// This function can be customized with [`Options`].
+///
+/// Like [`utf8::dowild_with`](crate::utf8::dowild_with) but a `?` and a negated class match a
+/// single grapheme cluster instead of a single character.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{graphemes, Options};
+///
+/// let options = Options::default().enable_classes(true);
+///
+/// assert_eq!(
+///     graphemes::dowild_with("[!a-z]?", "e\u{301}x", options),
+///     true
+/// );
+/// ```
+#[must_use]
+pub fn dowild_with(pattern: &str, haystack: &str, options: Options<char>) -> bool {
+    crate::dowild_with_in(
+        &pattern.chars().collect::<Vec<_>>(),
+        &Graphemes(haystack),
+        options,
+    )
+}
+
+/// Returns the leftmost and shortest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`utf8::find`](crate::utf8::find) but on the grapheme clusters of the `haystack`. The
+/// span is a byte range on grapheme cluster boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{graphemes, Options};
+///
+/// assert_eq!(
+///     graphemes::find("?b", "e\u{301}b", Options::default()),
+///     Some(0..4)
+/// );
+/// ```
+#[must_use]
+pub fn find(pattern: &str, haystack: &str, options: Options<char>) -> Option<Range<usize>> {
+    parse(pattern, options).find_graphemes(haystack)
+}
+
+/// Returns the leftmost and longest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`utf8::find_longest`](crate::utf8::find_longest) but on the grapheme clusters of the
+/// `haystack`. The span is a byte range on grapheme cluster boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{graphemes, Options};
+///
+/// assert_eq!(
+///     graphemes::find_longest("x*", "e\u{301}xe\u{301}", Options::default()),
+///     Some(3..7)
+/// );
+/// ```
+#[must_use]
+pub fn find_longest(pattern: &str, haystack: &str, options: Options<char>) -> Option<Range<usize>> {
+    parse(pattern, options).find_longest_graphemes(haystack)
+}
+
+fn parse(pattern: &str, options: Options<char>) -> Pattern<char> {
+    Pattern::parse(&pattern.chars().collect::<Vec<_>>(), options)
+}
@@ -0,0 +1,310 @@
+//! The [`Haystack`] abstraction over the input of the matchers
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// A haystack which the matchers walk through token by token
+///
+/// The haystack doesn't need to be contiguous in memory. Besides slices, it is implemented
+/// for a `VecDeque`, for [`Chunks`] like the segments of a path, which are matched as if they
+/// were concatenated, and with the `ropey` feature for the bytes and characters of a
+/// `ropey::Rope`. Use [`dowild_with_in`](crate::dowild_with_in) and
+/// [`Pattern::is_match_in`](crate::Pattern::is_match_in) to match any of them without copying
+/// them into a `Vec` first.
+///
+/// A position in the haystack is not necessarily the index of a token. The positions of a
+/// `str` are the byte offsets of its characters, so the matchers decode the characters on the
+/// fly and every reported position lies on a character boundary. The positions of a haystack
+/// have to grow from the first token to the [`Haystack::end`].
+///
+/// A haystack can group its tokens into clusters like the grapheme clusters of a text. A `?`
+/// or a class consumes a whole cluster then and a match never ends inside of a cluster. The
+/// tokens of a cluster which are matched by the literals of the pattern can differ from the
+/// tokens at its positions, for example if the haystack is normalized.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::VecDeque;
+///
+/// use simplematch::{dowild_with_in, Chunks, Options};
+///
+/// let mut buffer = VecDeque::from(b"log.txt".to_vec());
+/// buffer.push_front(b'.');
+///
+/// assert_eq!(dowild_with_in(b".*.txt", &buffer, Options::default()), true);
+/// assert_eq!(
+///     dowild_with_in(
+///         b"src/*.rs",
+///         &Chunks::new(&[&b"src/"[..], b"lib.rs"]),
+///         Options::default()
+///     ),
+///     true
+/// );
+/// ```
+pub trait Haystack<T>
+where
+    T: Copy,
+{
+    /// `true` if a cluster can consist of multiple tokens
+    ///
+    /// The general matcher handles such clusters, so the faster matchers hand these haystacks
+    /// over to it.
+    const HAS_CLUSTERS: bool = false;
+    /// `true` if every token takes exactly one position like in a slice
+    ///
+    /// The number of tokens between two positions is their difference then, which rules out
+    /// some haystacks early.
+    const IS_INDEXED: bool = false;
+
+    /// Returns the position after the last token.
+    fn end(&self) -> usize;
+
+    /// Returns the token at the position `pos` and the position of the next token or `None`
+    /// if `pos` is the end of the haystack.
+    fn next(&self, pos: usize) -> Option<(T, usize)>;
+
+    /// Pushes the tokens of the cluster starting at the position `pos` to the `tokens` and
+    /// returns the cluster as a single token if there is one and the position after the
+    /// cluster or `None` if `pos` is the end of the haystack.
+    ///
+    /// The single token is matched by `?` and classes.
+    #[inline]
+    fn next_cluster(&self, pos: usize, tokens: &mut Vec<T>) -> Option<(Option<T>, usize)> {
+        self.next(pos).map(|(token, next)| {
+            tokens.push(token);
+            (Some(token), next)
+        })
+    }
+
+    /// Returns the token in front of the position `pos` and its position or `None` if `pos` is
+    /// the start of the haystack.
+    fn prev(&self, pos: usize) -> Option<(T, usize)>;
+
+    /// Returns the position of the token following the one at `pos`.
+    ///
+    /// At the end of the haystack, this is the position after the end.
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        self.next(pos).map_or(pos + 1, |(_, next)| next)
+    }
+}
+
+impl<T> Haystack<T> for [T]
+where
+    T: Copy,
+{
+    const IS_INDEXED: bool = true;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(T, usize)> {
+        self.get(pos).map(|c| (*c, pos + 1))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(T, usize)> {
+        let prev = pos.checked_sub(1)?;
+        self.get(prev).map(|c| (*c, prev))
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        pos + 1
+    }
+}
+
+impl<T> Haystack<T> for VecDeque<T>
+where
+    T: Copy,
+{
+    const IS_INDEXED: bool = true;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(T, usize)> {
+        self.get(pos).map(|c| (*c, pos + 1))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(T, usize)> {
+        let prev = pos.checked_sub(1)?;
+        self.get(prev).map(|c| (*c, prev))
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        pos + 1
+    }
+}
+
+/// A haystack of chunks which are matched as if they were concatenated
+///
+/// The positions are the indices in the concatenation. The start of every chunk is computed
+/// once when the `Chunks` are created, so the token at a position is found with a binary
+/// search over the chunks.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{dowild_with_in, Chunks, Options};
+///
+/// let segments = [&b"src"[..], b"/", b"lib.rs"];
+/// let options = Options::default().path_separator_with(b'/');
+///
+/// assert_eq!(
+///     dowild_with_in(b"src/*.rs", &Chunks::new(&segments), options),
+///     true
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Chunks<'c, T> {
+    chunks: &'c [&'c [T]],
+    /// The position of the first token of every chunk followed by the end of the haystack
+    starts: Vec<usize>,
+}
+
+impl<'c, T> Chunks<'c, T> {
+    /// Create a new haystack of the `chunks`.
+    #[must_use]
+    pub fn new(chunks: &'c [&'c [T]]) -> Self {
+        let mut starts = Vec::with_capacity(chunks.len() + 1);
+        starts.push(0);
+        let mut end = 0;
+        for chunk in chunks {
+            end += chunk.len();
+            starts.push(end);
+        }
+
+        Self { chunks, starts }
+    }
+}
+
+impl<T> Haystack<T> for Chunks<'_, T>
+where
+    T: Copy,
+{
+    const IS_INDEXED: bool = true;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(T, usize)> {
+        if pos >= self.end() {
+            return None;
+        }
+
+        // The last chunk starting at `pos` or before is not empty, since it contains `pos`
+        let index = self.starts.partition_point(|start| *start <= pos) - 1;
+        let c = self.chunks[index][pos - self.starts[index]];
+        Some((c, pos + 1))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(T, usize)> {
+        let prev = pos.checked_sub(1)?;
+        self.next(prev).map(|(c, _)| (c, prev))
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        pos + 1
+    }
+}
+
+impl Haystack<char> for str {
+    const IS_INDEXED: bool = false;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        let first = *self.as_bytes().get(pos)?;
+        if first.is_ascii() {
+            return Some((first as char, pos + 1));
+        }
+        self[pos..].chars().next().map(|c| (c, pos + c.len_utf8()))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(char, usize)> {
+        self[..pos]
+            .chars()
+            .next_back()
+            .map(|c| (c, pos - c.len_utf8()))
+    }
+}
+
+/// The positions are the byte indices of the `Rope`.
+#[cfg(feature = "ropey")]
+impl Haystack<u8> for ropey::Rope {
+    const IS_INDEXED: bool = true;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.len_bytes()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(u8, usize)> {
+        self.get_byte(pos).map(|c| (c, pos + 1))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(u8, usize)> {
+        let prev = pos.checked_sub(1)?;
+        self.get_byte(prev).map(|c| (c, prev))
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        pos + 1
+    }
+}
+
+/// The positions are the character indices of the `Rope`.
+#[cfg(feature = "ropey")]
+impl Haystack<char> for ropey::Rope {
+    const IS_INDEXED: bool = true;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.len_chars()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        self.get_char(pos).map(|c| (c, pos + 1))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(char, usize)> {
+        let prev = pos.checked_sub(1)?;
+        self.get_char(prev).map(|c| (c, prev))
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        pos + 1
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! The `simplematch` library provides a fast and efficient way to match wildcard patterns on
 //! strings and bytes. It includes two primary functions, `dowild` and `dowild_with`, along
-//! with an `Options` struct to customize the behavior of the `dowild_with` function.
+//! with an `Options` struct to customize the behavior of the `dowild_with` function and a
+//! precompiled `Pattern` to match the same pattern many times.
 //!
 //! ## Usage
 //!
@@ -29,7 +30,7 @@
 //! The `*` character matches any sequence of characters, while the `?` character matches
 //! a single character.
 //!
-//! `Wildcard` is natively implemented for `u8` and `char`.
+//! `Wildcard` is natively implemented for `u8`, `u16` and `char`.
 //!
 //! **Parameters:**
 //! - `pattern`: A bytes or char slice representing the wildcard pattern to match against.
@@ -79,7 +80,7 @@
 //!
 //! Matches the given `haystack` against the specified `pattern` with customizable [`Options`].
 //! This function allows for matching case insensitive, custom wildcard characters, escaping
-//! special characters and character classes including ranges.
+//! special characters, character classes including ranges and brace alternations.
 //!
 //! **Parameters:**
 //! - `pattern`: A bytes or char slice representing the wildcard pattern to match against.
@@ -117,6 +118,86 @@
 //! );
 //! ```
 //!
+//! ### `Pattern`
+//!
+//! If the same pattern is matched against many haystacks, a [`Pattern`] parses the pattern
+//! and the [`Options`] only once. The matching semantics are the same as with [`dowild`] and
+//! [`dowild_with`].
+//!
+//! ```rust
+//! use simplematch::{Options, Pattern};
+//!
+//! let pattern = Pattern::new(b"*.[ch]", Options::default().enable_classes(true)).unwrap();
+//!
+//! assert_eq!(pattern.is_match(b"main.c"), true);
+//! assert_eq!(pattern.is_match(b"main.rs"), false);
+//! ```
+//!
+//! ### `captures`
+//!
+//! Like [`dowild_with`] but returns the spans of the haystack matched by each `*`, `?` and
+//! character class of the pattern.
+//!
+//! ```rust
+//! use simplematch::{captures, Options};
+//!
+//! assert_eq!(
+//!     captures(b"*.tar.?z", b"backup.tar.gz", Options::default()),
+//!     Some(vec![0..6, 11..12])
+//! );
+//! ```
+//!
+//! ### `find` and `find_longest`
+//!
+//! Search for the leftmost span of the haystack matched by the pattern instead of matching the
+//! whole haystack. [`find`] returns the shortest and [`find_longest`] the longest of the
+//! leftmost matches.
+//!
+//! ```rust
+//! use simplematch::{find, find_longest, Options};
+//!
+//! let haystack = b"ERROR: timeout, ERROR: timeout";
+//!
+//! assert_eq!(
+//!     find(b"ERR?R*timeout", haystack, Options::default()),
+//!     Some(0..14)
+//! );
+//! assert_eq!(
+//!     find_longest(b"ERR?R*timeout", haystack, Options::default()),
+//!     Some(0..30)
+//! );
+//! ```
+//!
+//! All non-overlapping matches can be iterated over with [`find_iter`] or just counted with
+//! [`count`].
+//!
+//! ```rust
+//! use simplematch::{count, find_iter, Options};
+//!
+//! let haystack = b"ERROR: timeout, ERROR: timeout";
+//!
+//! assert_eq!(
+//!     find_iter(b"ERR?R*timeout", haystack, Options::default()).collect::<Vec<_>>(),
+//!     [0..14, 16..30]
+//! );
+//! assert_eq!(count(b"ERR?R*timeout", haystack, Options::default()), 2);
+//! ```
+//!
+//! ### `WildSet`
+//!
+//! To test a haystack against many patterns, a [`WildSet`] reports which patterns match. The
+//! patterns are grouped by their literal parts, so most of them are skipped without running
+//! the matcher.
+//!
+//! ```rust
+//! use simplematch::{Options, WildSet};
+//!
+//! let set = WildSet::new(["*.rs", "src/*", "*.toml"], Options::default()).unwrap();
+//!
+//! assert_eq!(set.matches("src/lib.rs"), vec![0, 1]);
+//! assert_eq!(set.is_match("README.md"), false);
+//! ```
+//!
 //! ## Character classes
 //!
 //! An expression `[...]` matches a single character if the first character following the
@@ -133,6 +214,26 @@
 //! within the brackets. For example, `[]-]` matches the two characters `]` and `-`. As opposed
 //! to regex, it is possible to revert a range `[F-A]` which has the same meaning as `[A-F]`.
 //!
+//! ## Named classes
+//!
+//! Within brackets, the POSIX character classes `[:alnum:]`, `[:alpha:]`, `[:blank:]`,
+//! `[:cntrl:]`, `[:digit:]`, `[:graph:]`, `[:lower:]`, `[:print:]`, `[:punct:]`, `[:space:]`,
+//! `[:upper:]` and `[:xdigit:]` match any character of the respective [`NamedClass`]. For
+//! example, `[[:alpha:]_]` matches any letter and `_`. For `u8`, the classes contain the ASCII
+//! characters of the C locale and for `char` the Unicode characters. Like with `fnmatch` and
+//! `FNM_CASEFOLD` of glibc and with `nocasematch` of bash, the named classes are not folded
+//! if matched case-insensitive, so `[[:upper:]]` doesn't match `a`. A class with an unknown
+//! name like `[:word:]` is interpreted literally.
+//!
+//! ```rust
+//! use simplematch::{dowild_with, Options};
+//!
+//! let options = Options::default().enable_classes(true);
+//!
+//! assert_eq!(dowild_with(b"[[:upper:]]*", b"Cargo.toml", options), true);
+//! assert_eq!(dowild_with(b"[![:alnum:]_]", b"-", options), true);
+//! ```
+//!
 //! ## Complementation
 //!
 //! An expression `[!...]` matches any single character that is not included in the expression
@@ -144,6 +245,280 @@
 //! represent themselves. For instance, `[[?*\\]` matches the four characters `[`, `?`, `*`,
 //! and `\`.
 //!
+//! ## Brace alternations
+//!
+//! If enabled with [`Options::enable_braces`], an expression `{...,...}` matches any one of
+//! its comma separated alternatives. For example, `*.{rs,toml}` matches `main.rs` and
+//! `Cargo.toml`. Alternations can be nested like in `{src/{lib,main},build}.rs` and
+//! alternatives can be empty, so `a{,b}` matches `a` and `ab`. The alternatives are matched
+//! side by side and are never expanded into all possible patterns.
+//!
+//! A `{` without a matching `}` is interpreted literally as well as a `}` without a matching
+//! `{` and a `,` outside of braces. To remove the special meanings of `{`, `}` and `,`, you
+//! can precede them with the escape character.
+//!
+//! ## Extended globbing
+//!
+//! If enabled with [`Options::enable_extglob`], the operators of the bash `extglob` option
+//! match a `|` separated list of patterns:
+//!
+//! * `?(...)` matches zero or one occurrence of the patterns
+//! * `*(...)` matches zero or more occurrences of the patterns
+//! * `+(...)` matches one or more occurrences of the patterns
+//! * `@(...)` matches exactly one of the patterns
+//! * `!(...)` matches anything except one of the patterns
+//!
+//! For example, `+([0-9]).log` matches `2024.log` and `!(*.bak|*.tmp)` matches every file
+//! name which doesn't end with `.bak` or `.tmp`. The operators can be nested and the
+//! repetitions are matched without backtracking, so nested repetitions like `*(*(a))b` don't
+//! blow up exponentially. An operator without a matching `)` is interpreted literally as well
+//! as a `)` or a `|` outside of an operator.
+//!
+//! ## Paths
+//!
+//! If a [`path_separator`](Options::path_separator) is set, `*`, `?` and negated character
+//! classes don't match the separator. For example with the separator `/`, `src/*.rs` matches
+//! `src/lib.rs` but not `src/a/lib.rs`. With [`Options::enable_globstar`], a `**` as a whole
+//! segment matches any number of segments, so `src/**/*.rs` matches both. A second wildcard
+//! which doesn't match the separator while `*` does, like the `%` of IMAP, can be set with
+//! [`Options::wildcard_any_segment_with`]. Hidden files can be protected with
+//! [`Options::protect_leading_dot`], so a leading dot is only matched by a literal dot.
+//!
+//! ```rust
+//! use simplematch::{dowild_with, Options};
+//!
+//! let options = Options::default()
+//!     .path_separator_with(b'/')
+//!     .enable_globstar(true);
+//!
+//! assert_eq!(dowild_with(b"src/*.rs", b"src/a/lib.rs", options), false);
+//! assert_eq!(dowild_with(b"src/**/*.rs", b"src/a/lib.rs", options), true);
+//! ```
+//!
+//! ## UTF-8
+//!
+//! The [`DoWild`] implementation of `str` matches the bytes of the strings, so `?` consumes a
+//! single byte of a multibyte character. The functions of the [`utf8`] module and the
+//! `Pattern<char>` methods like [`Pattern::is_match_str`] match the characters of a `str`
+//! instead. The haystack is decoded on the fly, and all reported spans are byte offsets on
+//! character boundaries of the haystack.
+//!
+//! ```rust
+//! use simplematch::{utf8, DoWild, Options};
+//!
+//! assert_eq!("caf?".dowild("café"), false);
+//! assert_eq!(utf8::dowild("caf?", "café"), true);
+//! assert_eq!(utf8::find("?ü", "grün", Options::default()), Some(1..4));
+//! ```
+//!
+//! ## UTF-16
+//!
+//! Like bytes, the code units of UTF-16 can be matched directly, for example the strings of
+//! Windows and JavaScript engines. A `?` consumes a single code unit then and only ASCII
+//! characters are folded. The functions of the [`utf16`] module and the `Pattern<char>`
+//! methods like [`Pattern::is_match_utf16`] decode the surrogate pairs instead, so a `?` and
+//! a class consume a whole code point.
+//!
+//! ```rust
+//! use simplematch::{utf16, DoWild};
+//!
+//! let pattern = "*.?".encode_utf16().collect::<Vec<u16>>();
+//! let haystack = "crab.🦀".encode_utf16().collect::<Vec<u16>>();
+//!
+//! assert_eq!(pattern.dowild(haystack.clone()), false);
+//! assert_eq!(utf16::dowild(&pattern, &haystack), true);
+//! ```
+//!
+//! ## Haystacks
+//!
+//! Besides slices, the `*_in` functions like [`dowild_with_in`] and [`Pattern::is_match_in`]
+//! match any [`Haystack`] without copying it into a `Vec` first. The [`Haystack`] trait is
+//! implemented for a `VecDeque`, for [`Chunks`] like the segments of a path, which are matched
+//! as if they were concatenated, and with the `ropey` feature for the bytes and the
+//! characters of a `ropey::Rope`.
+//!
+//! ```rust
+//! use simplematch::{dowild_with_in, Chunks, Options};
+//!
+//! let segments = [&b"src"[..], b"/", b"lib.rs"];
+//!
+//! assert_eq!(
+//!     dowild_with_in(b"src/*.rs", &Chunks::new(&segments), Options::default()),
+//!     true
+//! );
+//! ```
+//!
+//! ## Grapheme clusters
+//!
+//! With the `graphemes` feature, the functions of the [`graphemes`] module and the
+//! `Pattern<char>` methods like [`Pattern::is_match_graphemes`] match the extended grapheme
+//! clusters of a `str`. A `?` consumes one user-perceived character then, also if it consists
+//! of multiple characters like an `é` with a combining accent or a flag.
+//!
+//! ```rust
+//! # #[cfg(feature = "graphemes")]
+//! # {
+//! use simplematch::graphemes;
+//!
+//! assert_eq!(graphemes::dowild("??", "e\u{301}🇩🇪"), true);
+//! # }
+//! ```
+//!
+//! ## Unicode normalization
+//!
+//! The same visible text can be spelled with different characters. An `é` is either a single
+//! precomposed character (NFC) or an `e` followed by a combining accent (NFD) like in file
+//! names from macOS. With the `normalization` feature, [`Options::normalize_with`] matches
+//! such canonically equivalent sequences. [`Normalization::Compatible`] also matches
+//! compatibility equivalent characters like the full-width `Ａ` and `A` (NFKC). The pattern
+//! is decomposed once and the haystack lazily during the matching, so there is no
+//! normalized copy of the haystack. A `?` and a class match a base character together with
+//! its combining marks.
+//!
+//! ```rust
+//! # #[cfg(feature = "normalization")]
+//! # {
+//! use simplematch::{utf8, Normalization, Options};
+//!
+//! let options = Options::default().normalize_with(Normalization::Canonical);
+//!
+//! assert_eq!(utf8::dowild_with("café*", "cafe\u{301}.txt", options), true);
+//! assert_eq!(utf8::dowild_with("caf?", "cafe\u{301}", options), true);
+//! # }
+//! ```
+//!
+//! ## Unicode case folding
+//!
+//! By default, case-insensitive matching folds only ASCII characters. With the `unicode`
+//! feature, patterns of `char`s are matched with the case folding of the Unicode standard,
+//! so `σ`, `ς` and `Σ` match each other, also within ranges. On top of that, characters
+//! which fold to multiple characters match their expansion in literal text, for example
+//! `straße*` matches `STRASSE` and `ﬁ` matches `fi`. A `?` or a character class still
+//! matches exactly one character. The case folding tables are embedded in the crate, so
+//! this works in `no_std` environments, too.
+//!
+//! ```rust
+//! # #[cfg(feature = "unicode")]
+//! # {
+//! use simplematch::{dowild_with, Options};
+//!
+//! let pattern = "straße*".chars().collect::<Vec<char>>();
+//! let haystack = "STRASSENBAHN".chars().collect::<Vec<char>>();
+//!
+//! assert_eq!(
+//!     dowild_with(
+//!         &pattern,
+//!         &haystack,
+//!         Options::default().case_insensitive(true)
+//!     ),
+//!     true
+//! );
+//! # }
+//! ```
+//!
+//! ## `fnmatch`
+//!
+//! The [`fnmatch`](fnmatch::fnmatch) function of the [`fnmatch`] module takes the flags of
+//! `fnmatch(3)` and matches like glibc, so C code can be ported without changing its patterns.
+//!
+//! ```rust
+//! use simplematch::fnmatch::{fnmatch, FNM_PATHNAME, FNM_PERIOD};
+//!
+//! assert_eq!(
+//!     fnmatch(b"src/*.rs", b"src/lib.rs", FNM_PATHNAME | FNM_PERIOD),
+//!     true
+//! );
+//! assert_eq!(
+//!     fnmatch(b"src/*", b"src/.env", FNM_PATHNAME | FNM_PERIOD),
+//!     false
+//! );
+//! ```
+//!
+//! ## SQL `LIKE`
+//!
+//! The [`like`] module evaluates the `LIKE` and `ILIKE` predicates of SQL with an optional
+//! escape character. Like in the SQL standard and unlike the escape character of the
+//! [`Options`], an escape character which doesn't precede `%`, `_` or itself is an error.
+//!
+//! ```rust
+//! use simplematch::like::{ilike, like, LikeError};
+//!
+//! assert_eq!(like("100#%", "100%", Some('#')), Ok(true));
+//! assert_eq!(ilike("%.TXT", "notes.txt", None), Ok(true));
+//! assert_eq!(
+//!     like("100#", "100#", Some('#')),
+//!     Err(LikeError::TrailingEscape)
+//! );
+//! ```
+//!
+//! ## SQL `SIMILAR TO`
+//!
+//! The [`similar`] module evaluates the `SIMILAR TO` predicate of SQL which adds alternations
+//! `|`, groups `(...)`, the quantifiers `*`, `+`, `?` and `{m,n}` and bracket expressions to
+//! the `%` and `_` of `LIKE`. It doesn't need a regex engine and works in `no_std`, too.
+//!
+//! ```rust
+//! use simplematch::similar::similar_to;
+//!
+//! assert_eq!(similar_to("(ab|cd)+", "abcdab", None), Ok(true));
+//! assert_eq!(similar_to("[0-9]{4}-%", "2024-10-17", None), Ok(true));
+//! ```
+//!
+//! ## Regular expressions
+//!
+//! [`to_regex_string`] translates a pattern with its [`Options`] to a regex which matches the
+//! same haystacks, so the pattern can be handed to tools which only accept regular
+//! expressions. The syntax is the one of the Rust `regex` crate, which ripgrep uses, too.
+//!
+//! ```rust
+//! use simplematch::{utf8, Options};
+//!
+//! let options = Options::default().enable_braces(true);
+//!
+//! assert_eq!(
+//!     utf8::to_regex_string("*.{jpg,png}", options).unwrap(),
+//!     "(?s)^.*\\.(?:jpg|png)$"
+//! );
+//! ```
+//!
+//! ## Validation
+//!
+//! Malformed constructs like a class without a closing `]` are matched literally instead of
+//! failing. [`validate`] and [`utf8::validate`] report them with their position and a
+//! suggested fix, for example to show them to the author of the pattern:
+//!
+//! ```rust
+//! use simplematch::{utf8, Options};
+//!
+//! let options = Options::default().enable_classes(true);
+//!
+//! assert_eq!(utf8::validate("*.[ch]", options), Ok(()));
+//! assert_eq!(
+//!     utf8::validate("*.[ch", options).unwrap_err().to_string(),
+//!     "Invalid pattern: The character class is not closed.\n    *.[ch\n      ^^^\nHelp: Close \
+//!      the class with `]`."
+//! );
+//! ```
+//!
+//! With [`Options::strict`], malformed patterns are refused instead of matched literally.
+//! [`Pattern::new`] fails and [`try_dowild_with`] returns the error, while [`dowild_with`]
+//! doesn't match anything.
+//!
+//! ## Streaming
+//!
+//! A [`StreamMatcher`] matches a haystack which arrives in chunks, like a file or a network
+//! stream, without collecting it first. It reports the verdict as soon as it is certain:
+//!
+//! ```rust
+//! use simplematch::{Options, StreamMatcher};
+//!
+//! let mut matcher = StreamMatcher::new(b"HTTP/1.1 200 *", Options::default()).unwrap();
+//!
+//! assert_eq!(matcher.feed(b"HTTP/1.1 2"), None);
+//! assert_eq!(matcher.feed(b"00 OK\r\n"), Some(true));
+//! assert_eq!(matcher.finish(), true);
+//! ```
+//!
 //! ## Credits
 //!
 //! This linear-time wildcard matching algorithm is derived from the one presented in Russ
@@ -272,6 +647,25 @@ macro_rules! impl_dowild {
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+mod diagnostic;
+pub mod fnmatch;
+#[cfg(feature = "graphemes")]
+pub mod graphemes;
+mod haystack;
+pub mod like;
+#[cfg(feature = "normalization")]
+mod normalization;
+mod pattern;
+mod program;
+mod regex;
+mod set;
+pub mod similar;
+mod stream;
+#[cfg(feature = "unicode")]
+mod unicode;
+pub mod utf16;
+pub mod utf8;
+
 #[cfg(not(feature = "std"))]
 use alloc::collections::VecDeque;
 #[cfg(not(feature = "std"))]
@@ -280,7 +674,7 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::fmt::Display;
-use core::ops::Deref;
+use core::ops::{Deref, Range};
 #[cfg(feature = "std")]
 use std::collections::VecDeque;
 #[cfg(feature = "std")]
@@ -290,6 +684,12 @@ use std::string::String;
 #[cfg(feature = "std")]
 use std::vec::Vec;
 
+pub use diagnostic::{PatternError, PatternErrorKind};
+pub use haystack::{Chunks, Haystack};
+pub use pattern::{FindIter, Pattern};
+pub use set::WildSet;
+pub use stream::StreamMatcher;
+
 /// A convenience trait to use [`dowild`] and [`dowild_with`] directly for this type
 ///
 /// This trait is natively implemented for
@@ -298,6 +698,8 @@ use std::vec::Vec;
 /// * `String`
 /// * `&[u8]`
 /// * `Vec<u8>`
+/// * `&[u16]`
+/// * `Vec<u16>`
 /// * `&[char]`
 /// * `Vec<char>`
 ///
@@ -347,9 +749,15 @@ where
 }
 
 /// The trait for types which should be able to be matched for a wildcard pattern
-pub trait Wildcard: Eq + Copy + Clone {
+pub trait Wildcard: Eq + Copy + Clone + 'static {
     /// The default token to match any number of characters, usually `*`.
     const DEFAULT_ANY: Self;
+    /// The default token to close a brace alternation, usually `}`.
+    const DEFAULT_BRACE_CLOSE: Self;
+    /// The default token to open a brace alternation, usually `{`.
+    const DEFAULT_BRACE_OPEN: Self;
+    /// The default token to separate the alternatives of a brace alternation, usually `,`.
+    const DEFAULT_BRACE_SEPARATOR: Self;
     /// The default token to close a character class pattern, usually `]`.
     const DEFAULT_CLASS_CLOSE: Self;
     /// The default token to specify a range, usually `-`.
@@ -358,10 +766,80 @@ pub trait Wildcard: Eq + Copy + Clone {
     const DEFAULT_CLASS_NEGATE: Self;
     /// The default token to open a character class pattern, usually `[`.
     const DEFAULT_CLASS_OPEN: Self;
+    /// The default token of a dot which starts the name of a hidden file, usually `.`.
+    const DEFAULT_DOT: Self;
     /// The default token to escape special characters, usually `\`.
     const DEFAULT_ESCAPE: Self;
+    /// The default token which starts an extglob `@(...)` matching exactly one of its
+    /// sub-patterns, usually `@`.
+    const DEFAULT_EXTGLOB_AT: Self;
+    /// The default token to close an extglob, usually `)`.
+    const DEFAULT_EXTGLOB_CLOSE: Self;
+    /// The default token which starts an extglob `!(...)` matching anything except its
+    /// sub-patterns, usually `!`.
+    const DEFAULT_EXTGLOB_NOT: Self;
+    /// The default token to open an extglob after its operator, usually `(`.
+    const DEFAULT_EXTGLOB_OPEN: Self;
+    /// The default token which starts an extglob `+(...)` matching one or more of its
+    /// sub-patterns, usually `+`.
+    const DEFAULT_EXTGLOB_PLUS: Self;
+    /// The default token to separate the sub-patterns of an extglob, usually `|`.
+    const DEFAULT_EXTGLOB_SEPARATOR: Self;
     /// The default token match exactly one character, usually `?`.
     const DEFAULT_ONE: Self;
+    /// The full case folding of all tokens which fold to more than one token sorted by the
+    /// token, for example `ß` to `ss`.
+    ///
+    /// Case-insensitive matching uses this table in addition to [`Wildcard::fold_case`]. The
+    /// default is empty.
+    const FULL_CASE_FOLDING: &'static [(Self, &'static [Self])] = &[];
+
+    /// `true` if the tokens are Unicode scalar values and `false` if they are bytes
+    ///
+    /// [`to_regex_string`] translates a pattern of bytes to a regex which matches bytes.
+    const IS_UNICODE: bool;
+
+    /// Returns the canonical combining class of the `token`
+    ///
+    /// Tokens with a canonical combining class of `0` are starters, all others are combining
+    /// marks which are reordered by their class during the normalization. The default is `0`
+    /// for all tokens.
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn canonical_combining_class(_token: Self) -> u8 {
+        0
+    }
+
+    /// Returns the canonical composition of the two tokens or `None` if they don't compose
+    ///
+    /// The default composes nothing.
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn compose(_first: Self, _second: Self) -> Option<Self> {
+        None
+    }
+
+    /// Calls `emit` with each token of the full decomposition of the `token`
+    ///
+    /// The `normalization` selects the canonical or the compatibility decomposition. The
+    /// default emits the `token` itself.
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn decompose<F>(token: Self, _normalization: Normalization, mut emit: F)
+    where
+        F: FnMut(Self),
+    {
+        emit(token);
+    }
+
+    /// Returns the token which represents all tokens matching this `token` case-insensitive
+    ///
+    /// Two tokens have to match with [`Wildcard::match_one_case_insensitive`] if and only if
+    /// their folded tokens are equal. Usually, this is the lowercase character.
+    fn fold_case(token: Self) -> Self;
+
+    /// Returns `true` if the `token` is a member of the POSIX character class `class`
+    fn is_in_named_class(token: Self, class: NamedClass) -> bool;
 
     /// Returns `true` if two character match case-insensitive
     fn match_one_case_insensitive(first: Self, second: Self) -> bool;
@@ -372,6 +850,19 @@ pub trait Wildcard: Eq + Copy + Clone {
     fn match_range_case_insensitive(token: Self, low: Self, high: Self) -> bool;
     /// Returns `true` if the `token` matches the range from `low` to `high` case-sensitive
     fn match_range_case_sensitive(token: Self, low: Self, high: Self) -> bool;
+
+    /// Append the `token` to the `regex` as a literal in the syntax of [`to_regex_string`]
+    ///
+    /// The regex metacharacters must be escaped with a backslash, so the literal can be used
+    /// inside and outside of a character class.
+    fn push_regex_literal(token: Self, regex: &mut String);
+
+    /// Returns the ASCII character of the `token` or `None` if it is not an ASCII character
+    fn to_ascii(token: Self) -> Option<u8>;
+
+    /// Returns the `char` of the `token` or `None` if the `token` is not a character on its
+    /// own, like a non-ASCII byte
+    fn to_char(token: Self) -> Option<char>;
 }
 
 /// A simple type to hold the borrowed or owned value `T`
@@ -394,17 +885,73 @@ enum Class<T> {
 enum ClassKind<T> {
     /// A range like `a-z`
     Range(T, T),
+    /// A character escaped with the escape character like `\]`
+    Escaped(T),
     /// A single character
     One(T),
     /// A range which has the same start and end character like `z-z`
     RangeOne(T),
+    /// A POSIX character class like `[:alpha:]`
+    Named(NamedClass),
+}
+
+/// The POSIX character classes which can be used inside of a character class like
+/// `[[:alpha:]]`
+///
+/// For `u8` the classes contain the ASCII characters of the C locale. For `char` the classes
+/// contain all Unicode characters with the respective property where the standard library
+/// provides one, so `[:alpha:]` matches `é` but `[:digit:]` only matches `0` to `9`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NamedClass {
+    /// `[:alnum:]`, the union of `[:alpha:]` and `[:digit:]`
+    Alnum,
+    /// `[:alpha:]`, the alphabetic characters
+    Alpha,
+    /// `[:blank:]`, the space and the tab character
+    Blank,
+    /// `[:cntrl:]`, the control characters
+    Cntrl,
+    /// `[:digit:]`, the digits `0` to `9`
+    Digit,
+    /// `[:graph:]`, the visible characters
+    Graph,
+    /// `[:lower:]`, the lowercase characters
+    Lower,
+    /// `[:print:]`, the union of `[:graph:]` and the space character
+    Print,
+    /// `[:punct:]`, the visible characters which are not in `[:alnum:]`
+    Punct,
+    /// `[:space:]`, the whitespace characters
+    Space,
+    /// `[:upper:]`, the uppercase characters
+    Upper,
+    /// `[:xdigit:]`, the hexadecimal digits `0` to `9`, `a` to `f` and `A` to `F`
+    Xdigit,
+}
+
+/// The Unicode equivalence under which [`Options::normalization`] matches characters
+#[cfg(feature = "normalization")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Normalization {
+    /// Canonically equivalent sequences match, like the precomposed `é` (NFC) and an `e`
+    /// followed by a combining acute accent (NFD).
+    Canonical,
+    /// Compatibility equivalent sequences match, too, like the full-width `Ａ` and `A` (NFKC)
+    /// or the ligature `ﬁ` and `fi`.
+    Compatible,
 }
 
 /// The `Error` of the simplematch crate
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SimpleMatchError {
     /// A character in [`Options`] was assigned multiple times
     DuplicateCharacterAssignment,
+    /// The pattern contains a malformed construct, see [`validate`]
+    InvalidPattern(PatternError),
+    /// The pattern can't be expressed as a regex, see [`to_regex_string`]
+    NoRegexEquivalent,
+    /// The pattern can't be matched in chunks, see [`StreamMatcher`]
+    NotStreamable,
 }
 
 // Represents a character class
@@ -424,21 +971,65 @@ struct CharacterClasses<T>(VecDeque<CharacterClass<T>>);
 /// Customize the matching behavior of the [`dowild_with`] function
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Options<T>
 where
     T: Wildcard,
 {
     /// If `true` the patterns are matched case-sensitive
     ///
-    /// The default is to match case-sensitive. Currently, only ascii characters are
-    /// considered.
+    /// The default is to match case-sensitive. Only ASCII characters are folded, unless the
+    /// `unicode` feature is enabled and the patterns consist of `char`s.
     pub case_sensitive: bool,
 
+    /// The token to close a character class.
+    ///
+    /// The default is `]`
+    pub class_close: T,
+
+    /// The token between the first and the last character of a range in a character class.
+    ///
+    /// The default is `-`
+    pub class_hyphen: T,
+
     /// The token to negate a character class.
     ///
     /// The default is `!`
     pub class_negate: T,
 
+    /// A second token to negate a character class besides the [`class_negate`].
+    ///
+    /// The default is `None`. Most shells, glibc and Redis accept both `[!...]` and `[^...]`.
+    ///
+    /// [`class_negate`]: Options::class_negate
+    pub class_negate_alternative: Option<T>,
+
+    /// The token to open a character class.
+    ///
+    /// The default is `[`
+    pub class_open: T,
+
+    /// Set to `true` to enable brace alternations `{...,...}`.
+    ///
+    /// The default is `false`.
+    ///
+    /// A brace alternation matches any one of its comma separated alternatives. Alternations
+    /// can be nested and alternatives can be empty, like in `{a,b{c,d},}`. A `{` without
+    /// a matching `}` is matched literally like `}` and `,` outside of an alternation. If
+    /// escaping is enabled, `{`, `}` and `,` can be escaped.
+    pub is_braces_enabled: bool,
+
+    /// Set to `true` to enable escaping inside of character classes.
+    ///
+    /// The default is `false`. This option has only an effect if escaping is enabled with
+    /// [`is_escape_enabled`]. Then an escaped character is a member of the class on its own
+    /// like in Redis, glibc and most shells, so `[\]a]` contains `]` and `a` and `[a\-z]`
+    /// contains `a`, `-` and `z`. Otherwise, the escape character is a member of the class
+    /// like any other character.
+    ///
+    /// [`is_escape_enabled`]: Options::is_escape_enabled
+    pub is_class_escape_enabled: bool,
+
     /// Set to `true` to enable character classes `[...]`.
     ///
     /// The default is `false`.
@@ -449,17 +1040,104 @@ where
     /// The default is `false`.
     ///
     /// The default wildcard characters that can be escaped per default are `*`, `?`. These
-    /// characters are adjustable. If character classes are enabled, `[` can be escaped, too.
+    /// characters are adjustable. If character classes are enabled, the [`class_open`] token
+    /// `[` can be escaped, too.
     ///
     /// If the escape character is not escaping a special character it is matched literally.
     /// For example `"\\a"` matches the escape character and `a` literally.
+    ///
+    /// [`class_open`]: Options::class_open
     pub is_escape_enabled: bool,
 
+    /// Set to `true` to enable the extended globbing operators of bash's `extglob`.
+    ///
+    /// The default is `false`.
+    ///
+    /// Each operator takes a `|` separated list of sub-patterns which can be nested:
+    ///
+    /// * `?(a|b)` matches zero or one occurrence of the sub-patterns
+    /// * `*(a|b)` matches zero or more occurrences of the sub-patterns
+    /// * `+(a|b)` matches one or more occurrences of the sub-patterns
+    /// * `@(a|b)` matches exactly one of the sub-patterns
+    /// * `!(a|b)` matches anything except one of the sub-patterns
+    ///
+    /// The `?` and `*` of the operators are the [`wildcard_one`] and [`wildcard_any`]. An
+    /// operator without a matching `)` is matched like without this option and `|` and `)`
+    /// outside of an extglob are matched literally. If escaping is enabled, the operators,
+    /// `(`, `|` and `)` can be escaped. The patterns are matched side by side in a single pass
+    /// over the haystack, so nested repetitions like `*(*(a))` don't blow up
+    /// exponentially.
+    ///
+    /// [`wildcard_any`]: Options::wildcard_any
+    /// [`wildcard_one`]: Options::wildcard_one
+    pub is_extglob_enabled: bool,
+
+    /// Set to `true` to match `**` as a whole path segment across segments.
+    ///
+    /// The default is `false`. This option has only an effect if a [`path_separator`] is set.
+    ///
+    /// A `**/` at the start of a segment matches zero or more whole segments, so `src/**/*.rs`
+    /// matches `src/lib.rs` and `src/a/b/lib.rs`. A `**` at the end of the pattern or
+    /// alternative matches the rest of the haystack including separators. Otherwise, `**` is
+    /// just two `*`.
+    ///
+    /// [`path_separator`]: Options::path_separator
+    pub is_globstar_enabled: bool,
+
+    /// Set to `true` to match a leading dot only with a literal dot in the pattern.
+    ///
+    /// The default is `false`. This is the `FNM_PERIOD` flag of `fnmatch`, which protects
+    /// hidden files. A dot is leading if it is the first character of the haystack or, if a
+    /// [`path_separator`] is set, the first character after a separator. Such a dot is not
+    /// matched by `*`, `?` or character classes, so `*` doesn't match `.git` but `.*` does.
+    ///
+    /// [`path_separator`]: Options::path_separator
+    pub is_leading_dot_protected: bool,
+
+    /// Set to `true` to refuse to match with a malformed pattern.
+    ///
+    /// The default is `false`, which matches the malformed constructs reported by [`validate`]
+    /// literally, like a class without a closing `]`. This is rarely intended and can be
+    /// dangerous, for example if a typo turns the deny-list `[!a-z]*` into `[!a-z*` which
+    /// matches almost nothing. If `true`, [`Pattern::new`], [`WildSet::new`] and
+    /// [`to_regex_string`] fail, [`try_dowild_with`] returns an error and [`dowild_with`]
+    /// doesn't match anything if the pattern is malformed.
+    pub is_strict: bool,
+
+    /// Set to match Unicode equivalent sequences of characters.
+    ///
+    /// The default is `None`. Requires the `normalization` feature. If set, the pattern and
+    /// the haystack are compared in their decomposed form, so a pattern in NFC matches a
+    /// haystack in NFD and vice versa. The haystack is normalized lazily during the matching
+    /// without allocating a normalized copy. A base character and its combining marks form a
+    /// single character which is matched as a whole by `?` and classes. See also the crate
+    /// level documentation about [normalization](crate#unicode-normalization).
+    #[cfg(feature = "normalization")]
+    pub normalization: Option<Normalization>,
+
+    /// The path separator which can only be matched literally.
+    ///
+    /// The default is `None`. If set, `*`, `?` and negated character classes don't match the
+    /// separator unless a [`wildcard_any_segment`] is set. Then `*` matches the separator
+    /// and only the [`wildcard_any_segment`] doesn't.
+    ///
+    /// [`wildcard_any_segment`]: Options::wildcard_any_segment
+    pub path_separator: Option<T>,
+
     /// The token in the pattern to match zero or more occurrences of any character.
     ///
     /// The default token is `*`.
     pub wildcard_any: T,
 
+    /// The token in the pattern to match zero or more occurrences of any character except the
+    /// [`path_separator`].
+    ///
+    /// The default is `None`. This is the `%` of IMAP mailbox patterns. If set, the
+    /// `wildcard_any` matches the path separator.
+    ///
+    /// [`path_separator`]: Options::path_separator
+    pub wildcard_any_segment: Option<T>,
+
     /// The token in the pattern to escape special characters as defined by the other fields.
     ///
     /// The default is the backslash character `\`.
@@ -514,8 +1192,9 @@ where
     /// Parse a `CharacterClass`  with the opening bracket at the `start` index
     ///
     /// Beware, the starting condition is not verified in any way. A [`CharacterClass`] is
-    /// considered invalid, if there is no closing bracket found.
-    fn parse(start: usize, pattern: &[T], class_negate: T) -> Self {
+    /// considered invalid, if there is no closing bracket found. The class tokens and the case
+    /// sensitivity are the ones of the [`Options`].
+    fn parse(start: usize, pattern: &[T], options: &Options<T>) -> Self {
         // The first character of a range is always the opening bracket
         let mut p_idx = start + 1;
         if p_idx + 2 > pattern.len() {
@@ -523,7 +1202,9 @@ where
             return Self::new_invalid(start, p_idx + 1);
         }
 
-        let mut class = if pattern[p_idx] == class_negate {
+        let mut class = if pattern[p_idx] == options.class_negate
+            || Some(pattern[p_idx]) == options.class_negate_alternative
+        {
             p_idx += 1;
             Class::new_negative()
         } else {
@@ -531,15 +1212,15 @@ where
         };
 
         // The `]` directly after the opening `[` (and possibly `!`) is special and matched literally
-        if pattern[p_idx] == T::DEFAULT_CLASS_CLOSE {
-            let kind = ClassKind::parse_first(p_idx, pattern);
+        if pattern[p_idx] == options.class_close {
+            let kind = ClassKind::parse_first(p_idx, pattern, options);
             p_idx += kind.len();
             class.push(kind);
         }
 
         if p_idx < pattern.len() {
             // Parse until we reach either the end of the string or find a `]`
-            while let Some(kind) = ClassKind::parse(p_idx, pattern) {
+            while let Some(kind) = ClassKind::parse(p_idx, pattern, options) {
                 p_idx += kind.len();
                 if p_idx >= pattern.len() {
                     // The end of the string without a `]`
@@ -588,8 +1269,8 @@ where
     }
 
     #[inline]
-    fn parse(start: usize, pattern: &[T], class_negate: T) -> CharacterClass<T> {
-        CharacterClass::parse(start, pattern, class_negate)
+    fn parse(start: usize, pattern: &[T], options: &Options<T>) -> CharacterClass<T> {
+        CharacterClass::parse(start, pattern, options)
     }
 
     /// Parse a new class at this `index` or if already present return a reference to it.
@@ -597,7 +1278,12 @@ where
     /// The character at the `index` has to be the opening bracket character. This implies that
     /// `start < pattern.len()`. Note a [`CharacterClass`] can be invalid if there was no
     /// closing bracket.
-    fn get_or_add(&mut self, start: usize, pattern: &[T], class_negate: T) -> &CharacterClass<T> {
+    fn get_or_add(
+        &mut self,
+        start: usize,
+        pattern: &[T],
+        options: &Options<T>,
+    ) -> &CharacterClass<T> {
         if let Some(last) = self.0.back() {
             #[allow(clippy::else_if_without_else)]
             if last.start == start {
@@ -614,7 +1300,7 @@ where
             }
         }
 
-        let class = Self::parse(start, pattern, class_negate);
+        let class = Self::parse(start, pattern, options);
 
         // Stick to the default allocation strategy, doubling the buffer starting with a capacity of
         // `1`. In case of an invalid class as first class, the maximum amount of classes is `1`, so
@@ -698,26 +1384,35 @@ where
     {
         match self {
             Self::Range(low, high) => match_range(*token, *low, *high),
-            Self::One(c) | Self::RangeOne(c) => match_one(*c, *token),
+            Self::Escaped(c) | Self::One(c) | Self::RangeOne(c) => match_one(*c, *token),
+            Self::Named(class) => T::is_in_named_class(*token, *class),
         }
     }
 
     /// Does no out of bounds check for the first character
     #[inline]
-    fn parse(index: usize, pattern: &[T]) -> Option<Self> {
-        if pattern[index] == T::DEFAULT_CLASS_CLOSE {
+    fn parse(index: usize, pattern: &[T], options: &Options<T>) -> Option<Self> {
+        if pattern[index] == options.class_close {
             None
+        } else if options.is_class_escape_enabled
+            && options.is_escape_enabled
+            && pattern[index] == options.wildcard_escape
+            && index + 1 < pattern.len()
+        {
+            Some(Self::Escaped(pattern[index + 1]))
+        } else if let Some(class) = NamedClass::parse(index, pattern, options) {
+            Some(Self::Named(class))
         } else {
-            Some(Self::parse_first(index, pattern))
+            Some(Self::parse_first(index, pattern, options))
         }
     }
 
     /// Does no out of bounds and `]` check for the first character
-    fn parse_first(index: usize, pattern: &[T]) -> Self {
+    fn parse_first(index: usize, pattern: &[T], options: &Options<T>) -> Self {
         let first = pattern[index];
-        if index + 2 < pattern.len() && pattern[index + 1] == T::DEFAULT_CLASS_HYPHEN {
+        if index + 2 < pattern.len() && pattern[index + 1] == options.class_hyphen {
             let second = pattern[index + 2];
-            if second == T::DEFAULT_CLASS_CLOSE {
+            if second == options.class_close {
                 Self::One(first)
             } else {
                 match first.cmp(&second) {
@@ -735,23 +1430,190 @@ where
     const fn len(&self) -> usize {
         match self {
             Self::Range(_, _) | Self::RangeOne(_) => 3,
+            Self::Escaped(_) => 2,
             Self::One(_) => 1,
+            Self::Named(class) => class.name().len() + 4,
         }
     }
 }
 
-impl<T> Default for Options<T>
-where
-    T: Wildcard,
-{
-    fn default() -> Self {
-        Self::new()
+impl NamedClass {
+    /// All `NamedClass`es
+    const ALL: [Self; 12] = [
+        Self::Alnum,
+        Self::Alpha,
+        Self::Blank,
+        Self::Cntrl,
+        Self::Digit,
+        Self::Graph,
+        Self::Lower,
+        Self::Print,
+        Self::Punct,
+        Self::Space,
+        Self::Upper,
+        Self::Xdigit,
+    ];
+
+    /// Returns the `NamedClass` with the `name` or `None` if there is no such class.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::NamedClass;
+    ///
+    /// assert_eq!(NamedClass::from_name(b"alpha"), Some(NamedClass::Alpha));
+    /// assert_eq!(NamedClass::from_name(b"ALPHA"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|class| class.name().as_bytes() == name)
     }
-}
 
-impl<T> Options<T>
-where
-    T: Wildcard,
+    /// Returns `true` if the ASCII character `c` is a member of this `NamedClass` in the C
+    /// locale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::NamedClass;
+    ///
+    /// assert_eq!(NamedClass::Space.is_ascii_member(b'\t'), true);
+    /// assert_eq!(NamedClass::Punct.is_ascii_member(b'a'), false);
+    /// ```
+    #[must_use]
+    pub const fn is_ascii_member(self, c: u8) -> bool {
+        match self {
+            Self::Alnum => c.is_ascii_alphanumeric(),
+            Self::Alpha => c.is_ascii_alphabetic(),
+            Self::Blank => matches!(c, b' ' | b'\t'),
+            Self::Cntrl => c.is_ascii_control(),
+            Self::Digit => c.is_ascii_digit(),
+            Self::Graph => c.is_ascii_graphic(),
+            Self::Lower => c.is_ascii_lowercase(),
+            Self::Print => c.is_ascii_graphic() || c == b' ',
+            Self::Punct => c.is_ascii_punctuation(),
+            // Unlike `u8::is_ascii_whitespace`, the C locale includes the vertical tab
+            Self::Space => c.is_ascii_whitespace() || c == b'\x0b',
+            Self::Upper => c.is_ascii_uppercase(),
+            Self::Xdigit => c.is_ascii_hexdigit(),
+        }
+    }
+
+    /// Returns `true` if the character `c` is a member of this `NamedClass`.
+    ///
+    /// ASCII characters are members like with [`NamedClass::is_ascii_member`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::NamedClass;
+    ///
+    /// assert_eq!(NamedClass::Alpha.is_member('é'), true);
+    /// assert_eq!(NamedClass::Digit.is_member('٣'), false);
+    /// ```
+    #[must_use]
+    pub fn is_member(self, c: char) -> bool {
+        if c.is_ascii() {
+            return self.is_ascii_member(c as u8);
+        }
+
+        match self {
+            Self::Alnum => c.is_alphanumeric(),
+            Self::Alpha => c.is_alphabetic(),
+            Self::Blank => c.is_whitespace() && !matches!(c, '\u{85}' | '\u{2028}' | '\u{2029}'),
+            Self::Cntrl => c.is_control(),
+            Self::Digit | Self::Xdigit => false,
+            Self::Graph => !c.is_whitespace() && !c.is_control(),
+            Self::Lower => c.is_lowercase(),
+            Self::Print => !c.is_control(),
+            Self::Punct => !c.is_whitespace() && !c.is_control() && !c.is_alphanumeric(),
+            Self::Space => c.is_whitespace(),
+            Self::Upper => c.is_uppercase(),
+        }
+    }
+
+    /// Returns the name of this `NamedClass` as written between `[:` and `:]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::NamedClass;
+    ///
+    /// assert_eq!(NamedClass::Xdigit.name(), "xdigit");
+    /// ```
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Alnum => "alnum",
+            Self::Alpha => "alpha",
+            Self::Blank => "blank",
+            Self::Cntrl => "cntrl",
+            Self::Digit => "digit",
+            Self::Graph => "graph",
+            Self::Lower => "lower",
+            Self::Print => "print",
+            Self::Punct => "punct",
+            Self::Space => "space",
+            Self::Upper => "upper",
+            Self::Xdigit => "xdigit",
+        }
+    }
+
+    /// Parse a `NamedClass` like `[:alpha:]` starting with the `[` at the `index`
+    ///
+    /// Returns `None` if there is no valid `NamedClass` at the `index`. Does no out of bounds
+    /// check for the first character.
+    fn parse<T>(index: usize, pattern: &[T], options: &Options<T>) -> Option<Self>
+    where
+        T: Wildcard,
+    {
+        const MAX_NAME_LEN: usize = 6;
+
+        if pattern[index] != options.class_open
+            || pattern.get(index + 1).copied().and_then(T::to_ascii) != Some(b':')
+        {
+            return None;
+        }
+
+        let mut name = [0; MAX_NAME_LEN];
+        for (idx, c) in pattern
+            .iter()
+            .skip(index + 2)
+            .take(MAX_NAME_LEN + 1)
+            .enumerate()
+        {
+            match T::to_ascii(*c)? {
+                b':' => {
+                    return if pattern.get(index + idx + 3) == Some(&options.class_close) {
+                        Self::from_name(&name[..idx])
+                    } else {
+                        None
+                    };
+                }
+                c if idx < MAX_NAME_LEN => name[idx] = c,
+                _ => return None,
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Default for Options<T>
+where
+    T: Wildcard,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Options<T>
+where
+    T: Wildcard,
 {
     /// Create new `Options` for the [`dowild_with`] function.
     #[must_use]
@@ -759,17 +1621,33 @@ where
         Self {
             case_sensitive: true,
             wildcard_escape: T::DEFAULT_ESCAPE,
+            is_braces_enabled: false,
+            is_class_escape_enabled: false,
             is_classes_enabled: false,
+            class_close: T::DEFAULT_CLASS_CLOSE,
+            class_hyphen: T::DEFAULT_CLASS_HYPHEN,
             class_negate: T::DEFAULT_CLASS_NEGATE,
+            class_negate_alternative: None,
+            class_open: T::DEFAULT_CLASS_OPEN,
             wildcard_any: T::DEFAULT_ANY,
             wildcard_one: T::DEFAULT_ONE,
             is_escape_enabled: false,
+            is_extglob_enabled: false,
+            is_globstar_enabled: false,
+            is_leading_dot_protected: false,
+            is_strict: false,
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            path_separator: None,
+            wildcard_any_segment: None,
         }
     }
 
     /// If `true` match the pattern case-insensitive.
     ///
-    /// The default is to match case-sensitive.
+    /// The default is to match case-sensitive. Only ASCII characters are folded, unless the
+    /// `unicode` feature is enabled and the pattern consists of `char`s. See also the crate
+    /// level documentation about [Unicode case folding](crate#unicode-case-folding).
     ///
     /// # Examples
     ///
@@ -819,6 +1697,49 @@ where
         self
     }
 
+    /// If `true`, enable brace alternations `{...,...}`.
+    ///
+    /// The default is `false`. See also [`Options::is_braces_enabled`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default().enable_braces(true);
+    ///
+    /// assert_eq!(dowild_with(b"*.{rs,toml}", b"Cargo.toml", options), true);
+    /// assert_eq!(dowild_with(b"*.{rs,toml}", b"Cargo.lock", options), false);
+    /// ```
+    #[must_use]
+    pub const fn enable_braces(mut self, yes: bool) -> Self {
+        self.is_braces_enabled = yes;
+        self
+    }
+
+    /// If `true`, enable the extglob operators `?(...)`, `*(...)`, `+(...)`, `@(...)` and
+    /// `!(...)`.
+    ///
+    /// The default is `false`. See also [`Options::is_extglob_enabled`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default().enable_extglob(true).enable_classes(true);
+    ///
+    /// assert_eq!(dowild_with(b"+([0-9]).log", b"2024.log", options), true);
+    /// assert_eq!(dowild_with(b"+([0-9]).log", b".log", options), false);
+    /// assert_eq!(dowild_with(b"!(*.bak|*.tmp)", b"notes.txt", options), true);
+    /// assert_eq!(dowild_with(b"!(*.bak|*.tmp)", b"notes.bak", options), false);
+    /// ```
+    #[must_use]
+    pub const fn enable_extglob(mut self, yes: bool) -> Self {
+        self.is_extglob_enabled = yes;
+        self
+    }
+
     /// If `true`, enable character classes `[...]`.
     ///
     /// The default is `false`.
@@ -856,6 +1777,247 @@ where
         self
     }
 
+    /// Set the token to open a character class.
+    ///
+    /// The default is `[`. Character classes have to be enabled with
+    /// [`enable_classes`](Options::enable_classes). A named class is written with the same
+    /// tokens, like `<:alpha:>` for the opening `<` and the closing `>`.
+    ///
+    /// # Examples
+    ///
+    /// Write classes like `<a-z>` if `[` and `]` are reserved.
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default()
+    ///     .enable_classes(true)
+    ///     .class_open_with(b'<')
+    ///     .class_close_with(b'>');
+    ///
+    /// assert_eq!(dowild_with(b"<a-z>[0]", b"x[0]", options), true);
+    /// assert_eq!(dowild_with(b"<!a-z>[0]", b"x[0]", options), false);
+    /// ```
+    #[must_use]
+    pub const fn class_open_with(mut self, token: T) -> Self {
+        self.class_open = token;
+        self
+    }
+
+    /// Set the token to close a character class.
+    ///
+    /// The default is `]`. See also [`class_open_with`](Options::class_open_with).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::Options;
+    ///
+    /// let options = Options::default()
+    ///     .enable_classes(true)
+    ///     .class_open_with(b'<')
+    ///     .class_close_with(b'>');
+    /// ```
+    #[must_use]
+    pub const fn class_close_with(mut self, token: T) -> Self {
+        self.class_close = token;
+        self
+    }
+
+    /// Set the token between the first and the last character of a range.
+    ///
+    /// The default is `-`. Character classes have to be enabled with
+    /// [`enable_classes`](Options::enable_classes).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default()
+    ///     .enable_classes(true)
+    ///     .class_hyphen_with(b'~');
+    ///
+    /// assert_eq!(dowild_with(b"[a~z]", b"x", options), true);
+    /// assert_eq!(dowild_with(b"[a-z]", b"-", options), true);
+    /// ```
+    #[must_use]
+    pub const fn class_hyphen_with(mut self, token: T) -> Self {
+        self.class_hyphen = token;
+        self
+    }
+
+    /// Use this `token` to negate a character class besides the [`Options::class_negate`].
+    ///
+    /// The default is no such token. Character classes have to be enabled with
+    /// [`enable_classes`](Options::enable_classes).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default()
+    ///     .enable_classes(true)
+    ///     .class_negate_alternative_with(b'^');
+    ///
+    /// assert_eq!(dowild_with(b"[!a-z]", b"1", options), true);
+    /// assert_eq!(dowild_with(b"[^a-z]", b"1", options), true);
+    /// assert_eq!(dowild_with(b"[^a-z]", b"x", options), false);
+    /// ```
+    #[must_use]
+    pub const fn class_negate_alternative_with(mut self, token: T) -> Self {
+        self.class_negate_alternative = Some(token);
+        self
+    }
+
+    /// If `true`, enable escaping inside of character classes.
+    ///
+    /// The default is `false`. Escaping has to be enabled with
+    /// [`enable_escape`](Options::enable_escape). See also
+    /// [`Options::is_class_escape_enabled`].
+    ///
+    /// # Examples
+    ///
+    /// Match like the `KEYS` and `SCAN` commands of Redis:
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default()
+    ///     .enable_classes_with(b'^')
+    ///     .enable_escape(true)
+    ///     .enable_class_escape(true);
+    ///
+    /// assert_eq!(dowild_with(b"[\\]a]", b"]", options), true);
+    /// assert_eq!(dowild_with(b"[a\\-z]", b"-", options), true);
+    /// assert_eq!(dowild_with(b"[a\\-z]", b"x", options), false);
+    /// ```
+    #[must_use]
+    pub const fn enable_class_escape(mut self, yes: bool) -> Self {
+        self.is_class_escape_enabled = yes;
+        self
+    }
+
+    /// If `true`, match `**` as a whole path segment across segments.
+    ///
+    /// The default is `false`. See also [`Options::is_globstar_enabled`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default()
+    ///     .path_separator_with(b'/')
+    ///     .enable_globstar(true);
+    ///
+    /// assert_eq!(dowild_with(b"src/**/*.rs", b"src/lib.rs", options), true);
+    /// assert_eq!(
+    ///     dowild_with(b"src/**/*.rs", b"src/a/b/lib.rs", options),
+    ///     true
+    /// );
+    /// assert_eq!(dowild_with(b"src/**/*.rs", b"tests/lib.rs", options), false);
+    /// ```
+    #[must_use]
+    pub const fn enable_globstar(mut self, yes: bool) -> Self {
+        self.is_globstar_enabled = yes;
+        self
+    }
+
+    /// If `true`, a leading dot of the haystack is only matched by a literal dot.
+    ///
+    /// The default is `false`. See also [`Options::is_leading_dot_protected`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default().protect_leading_dot(true);
+    ///
+    /// assert_eq!(dowild_with(b"*", b".git", options), false);
+    /// assert_eq!(dowild_with(b".*", b".git", options), true);
+    /// assert_eq!(dowild_with(b"*", b"main.rs", options), true);
+    /// ```
+    #[must_use]
+    pub const fn protect_leading_dot(mut self, yes: bool) -> Self {
+        self.is_leading_dot_protected = yes;
+        self
+    }
+
+    /// If `true`, refuse to match with a malformed pattern.
+    ///
+    /// The default is `false`. See also [`Options::is_strict`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, try_dowild_with, Options, Pattern};
+    ///
+    /// let options = Options::default().enable_classes(true).strict(true);
+    ///
+    /// assert_eq!(dowild_with(b"[!a-z]*", b"1.txt", options), true);
+    /// assert_eq!(dowild_with(b"[!a-z*", b"1.txt", options), false);
+    /// assert_eq!(dowild_with(b"[!a-z*", b"[!a-z*", options), false);
+    /// assert!(try_dowild_with(b"[!a-z*", b"1.txt", options).is_err());
+    /// assert!(Pattern::new(b"[!a-z*", options).is_err());
+    /// ```
+    #[must_use]
+    pub const fn strict(mut self, yes: bool) -> Self {
+        self.is_strict = yes;
+        self
+    }
+
+    /// Match Unicode equivalent sequences of characters under this `normalization`.
+    ///
+    /// The default is no normalization. Requires the `normalization` feature. See also
+    /// [`Options::normalization`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{utf8, Normalization, Options};
+    ///
+    /// let options = Options::default().normalize_with(Normalization::Canonical);
+    ///
+    /// assert_eq!(
+    ///     utf8::dowild_with("café*", "cafe\u{301} au lait", options),
+    ///     true
+    /// );
+    /// assert_eq!(utf8::dowild_with("ABC", "ＡＢＣ", options), false);
+    ///
+    /// let options = Options::default().normalize_with(Normalization::Compatible);
+    ///
+    /// assert_eq!(utf8::dowild_with("ABC", "ＡＢＣ", options), true);
+    /// ```
+    #[cfg(feature = "normalization")]
+    #[must_use]
+    pub const fn normalize_with(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Use this `token` as path separator which `*`, `?` and negated classes don't match.
+    ///
+    /// The default is no path separator. See also [`Options::path_separator`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default().path_separator_with(b'/');
+    ///
+    /// assert_eq!(dowild_with(b"src/*.rs", b"src/lib.rs", options), true);
+    /// assert_eq!(dowild_with(b"src/*.rs", b"src/a/b.rs", options), false);
+    /// ```
+    #[must_use]
+    pub const fn path_separator_with(mut self, token: T) -> Self {
+        self.path_separator = Some(token);
+        self
+    }
+
     /// Use this `token` instead of the default `*` to match any occurrences of a characters.
     ///
     /// # Examples
@@ -885,10 +2047,36 @@ where
         self
     }
 
+    /// Use this `token` to match any occurrences of characters except the path separator.
+    ///
+    /// The default is no such token. See also [`Options::wildcard_any_segment`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{dowild_with, Options};
+    ///
+    /// let options = Options::default()
+    ///     .path_separator_with(b'/')
+    ///     .wildcard_any_segment_with(b'%');
+    ///
+    /// assert_eq!(dowild_with(b"INBOX/%", b"INBOX/Sent", options), true);
+    /// assert_eq!(dowild_with(b"INBOX/%", b"INBOX/Sent/2024", options), false);
+    /// assert_eq!(dowild_with(b"INBOX/*", b"INBOX/Sent/2024", options), true);
+    /// ```
+    #[must_use]
+    pub const fn wildcard_any_segment_with(mut self, token: T) -> Self {
+        self.wildcard_any_segment = Some(token);
+        self
+    }
+
     /// Check `Options` for configuration errors
     ///
     /// An invalid configuration consists of duplicate character assignments. For example you
-    /// can't use `*` for the escape character and `wildcard_any` character simultaneously.
+    /// can't use `*` for the escape character and `wildcard_any` character simultaneously. The
+    /// `wildcard_any`, `wildcard_one`, `wildcard_escape` and the four class tokens
+    /// `class_open`, `class_close`, `class_negate` and `class_hyphen` have to be distinct. The
+    /// `class_negate_alternative` may only be the same as the `class_negate`.
     ///
     /// # Errors
     ///
@@ -907,12 +2095,62 @@ where
     /// );
     /// ```
     pub fn verify(&self) -> Result<(), SimpleMatchError> {
-        if self.wildcard_any == self.wildcard_one
-            || self.wildcard_any == self.wildcard_escape
-            || self.wildcard_any == self.class_negate
-            || self.wildcard_one == self.wildcard_escape
-            || self.wildcard_one == self.class_negate
-            || self.wildcard_escape == self.class_negate
+        let specials = [
+            self.wildcard_any,
+            self.wildcard_one,
+            self.wildcard_escape,
+            self.class_open,
+            self.class_close,
+            self.class_negate,
+            self.class_hyphen,
+        ];
+        if specials
+            .iter()
+            .enumerate()
+            .any(|(index, token)| specials[index + 1..].contains(token))
+        {
+            return Err(SimpleMatchError::DuplicateCharacterAssignment);
+        }
+
+        if let Some(token) = self.class_negate_alternative {
+            if token != self.class_negate && specials.contains(&token) {
+                return Err(SimpleMatchError::DuplicateCharacterAssignment);
+            }
+        }
+        if let Some(token) = self.wildcard_any_segment {
+            if specials.contains(&token) || Some(token) == self.path_separator {
+                return Err(SimpleMatchError::DuplicateCharacterAssignment);
+            }
+        }
+        if let Some(separator) = self.path_separator {
+            if specials.contains(&separator) {
+                return Err(SimpleMatchError::DuplicateCharacterAssignment);
+            }
+        }
+
+        if self.is_braces_enabled
+            && specials[..5]
+                .iter()
+                .chain(&self.wildcard_any_segment)
+                .any(|token| {
+                    *token == T::DEFAULT_BRACE_OPEN
+                        || *token == T::DEFAULT_BRACE_CLOSE
+                        || *token == T::DEFAULT_BRACE_SEPARATOR
+                })
+        {
+            return Err(SimpleMatchError::DuplicateCharacterAssignment);
+        }
+
+        if self.is_extglob_enabled
+            && specials[..5]
+                .iter()
+                .chain(&self.wildcard_any_segment)
+                .chain(&self.path_separator)
+                .any(|token| {
+                    *token == T::DEFAULT_EXTGLOB_OPEN
+                        || *token == T::DEFAULT_EXTGLOB_CLOSE
+                        || *token == T::DEFAULT_EXTGLOB_SEPARATOR
+                })
         {
             return Err(SimpleMatchError::DuplicateCharacterAssignment);
         }
@@ -973,6 +2211,19 @@ impl Display for SimpleMatchError {
                      assignment."
                 )
             }
+            Self::InvalidPattern(error) => error.fmt(f),
+            Self::NoRegexEquivalent => {
+                write!(
+                    f,
+                    "Translating the pattern failed: The pattern has no equivalent regex."
+                )
+            }
+            Self::NotStreamable => {
+                write!(
+                    f,
+                    "Streaming the pattern failed: The pattern can't be matched in chunks."
+                )
+            }
         }
     }
 }
@@ -985,6 +2236,8 @@ impl_dowild!(u8: &[u8]);
 impl_dowild!(u8: &str => .as_bytes());
 impl_dowild!(u8: String => .as_bytes());
 impl_dowild!(u8: Vec<u8> => .as_slice());
+impl_dowild!(u16: &[u16]);
+impl_dowild!(u16: Vec<u16> => .as_slice());
 impl_dowild!(char: &[char]);
 impl_dowild!(char: Vec<char> => .as_slice());
 
@@ -996,6 +2249,27 @@ impl Wildcard for u8 {
     const DEFAULT_CLASS_HYPHEN: Self = b'-';
     const DEFAULT_CLASS_NEGATE: Self = b'!';
     const DEFAULT_CLASS_OPEN: Self = b'[';
+    const DEFAULT_BRACE_CLOSE: Self = b'}';
+    const DEFAULT_BRACE_OPEN: Self = b'{';
+    const DEFAULT_BRACE_SEPARATOR: Self = b',';
+    const DEFAULT_DOT: Self = b'.';
+    const DEFAULT_EXTGLOB_AT: Self = b'@';
+    const DEFAULT_EXTGLOB_CLOSE: Self = b')';
+    const DEFAULT_EXTGLOB_NOT: Self = b'!';
+    const DEFAULT_EXTGLOB_OPEN: Self = b'(';
+    const DEFAULT_EXTGLOB_PLUS: Self = b'+';
+    const DEFAULT_EXTGLOB_SEPARATOR: Self = b'|';
+    const IS_UNICODE: bool = false;
+
+    #[inline]
+    fn fold_case(token: Self) -> Self {
+        token.to_ascii_lowercase()
+    }
+
+    #[inline]
+    fn is_in_named_class(token: Self, class: NamedClass) -> bool {
+        class.is_ascii_member(token)
+    }
 
     #[inline]
     fn match_one_case_sensitive(first: Self, second: Self) -> bool {
@@ -1022,6 +2296,123 @@ impl Wildcard for u8 {
             is_in_ascii_range_case_insensitive(token, low, high)
         }
     }
+
+    fn push_regex_literal(token: Self, regex: &mut String) {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+        if token.is_ascii() {
+            char::push_regex_literal(char::from(token), regex);
+        } else {
+            regex.push_str("\\x");
+            regex.push(char::from(HEX_DIGITS[usize::from(token >> 4)]));
+            regex.push(char::from(HEX_DIGITS[usize::from(token & 0xf)]));
+        }
+    }
+
+    #[inline]
+    fn to_ascii(token: Self) -> Option<u8> {
+        token.is_ascii().then_some(token)
+    }
+
+    #[inline]
+    fn to_char(token: Self) -> Option<char> {
+        token.is_ascii().then_some(char::from(token))
+    }
+}
+
+/// The code units of UTF-16 like the strings of Windows and JavaScript
+///
+/// Like with bytes, a `?` and a class match a single code unit, so a character outside of the
+/// Basic Multilingual Plane consists of two surrogates. The functions of the [`utf16`] module
+/// match whole code points instead. Only ASCII characters are folded and the named classes
+/// contain only ASCII characters like for `u8`. [`to_regex_string`] translates the code units
+/// like bytes, so there is only a regex equivalent for ASCII patterns.
+impl Wildcard for u16 {
+    const DEFAULT_ANY: Self = b'*' as Self;
+    const DEFAULT_ESCAPE: Self = b'\\' as Self;
+    const DEFAULT_ONE: Self = b'?' as Self;
+    const DEFAULT_CLASS_CLOSE: Self = b']' as Self;
+    const DEFAULT_CLASS_HYPHEN: Self = b'-' as Self;
+    const DEFAULT_CLASS_NEGATE: Self = b'!' as Self;
+    const DEFAULT_CLASS_OPEN: Self = b'[' as Self;
+    const DEFAULT_BRACE_CLOSE: Self = b'}' as Self;
+    const DEFAULT_BRACE_OPEN: Self = b'{' as Self;
+    const DEFAULT_BRACE_SEPARATOR: Self = b',' as Self;
+    const DEFAULT_DOT: Self = b'.' as Self;
+    const DEFAULT_EXTGLOB_AT: Self = b'@' as Self;
+    const DEFAULT_EXTGLOB_CLOSE: Self = b')' as Self;
+    const DEFAULT_EXTGLOB_NOT: Self = b'!' as Self;
+    const DEFAULT_EXTGLOB_OPEN: Self = b'(' as Self;
+    const DEFAULT_EXTGLOB_PLUS: Self = b'+' as Self;
+    const DEFAULT_EXTGLOB_SEPARATOR: Self = b'|' as Self;
+    const IS_UNICODE: bool = false;
+
+    #[inline]
+    fn fold_case(token: Self) -> Self {
+        Self::to_ascii(token).map_or(token, |ascii| Self::from(ascii.to_ascii_lowercase()))
+    }
+
+    #[inline]
+    fn is_in_named_class(token: Self, class: NamedClass) -> bool {
+        Self::to_ascii(token).map_or(false, |ascii| class.is_ascii_member(ascii))
+    }
+
+    #[inline]
+    fn match_one_case_sensitive(first: Self, second: Self) -> bool {
+        first == second
+    }
+
+    #[inline]
+    fn match_one_case_insensitive(first: Self, second: Self) -> bool {
+        first == second
+            || matches!((Self::to_ascii(first), Self::to_ascii(second)),
+                (Some(first), Some(second)) if first.eq_ignore_ascii_case(&second))
+    }
+
+    #[inline]
+    fn match_range_case_sensitive(token: Self, low: Self, high: Self) -> bool {
+        low <= token && token <= high
+    }
+
+    #[inline]
+    fn match_range_case_insensitive(token: Self, low: Self, high: Self) -> bool {
+        const ASCII_CASE_MASK: u8 = 0b0010_0000;
+
+        if low <= token && token <= high {
+            return true;
+        }
+        match Self::to_ascii(token) {
+            Some(ascii) if ascii.is_ascii_alphabetic() => {
+                let other_case = Self::from(ascii ^ ASCII_CASE_MASK);
+                low <= other_case && other_case <= high
+            }
+            _ => false,
+        }
+    }
+
+    fn push_regex_literal(token: Self, regex: &mut String) {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+        if let Some(ascii) = Self::to_ascii(token) {
+            char::push_regex_literal(char::from(ascii), regex);
+        } else {
+            regex.push_str("\\x{");
+            for shift in [12, 8, 4, 0] {
+                regex.push(char::from(HEX_DIGITS[usize::from((token >> shift) & 0xf)]));
+            }
+            regex.push('}');
+        }
+    }
+
+    #[inline]
+    fn to_ascii(token: Self) -> Option<u8> {
+        u8::try_from(token).ok().filter(u8::is_ascii)
+    }
+
+    #[inline]
+    fn to_char(token: Self) -> Option<char> {
+        char::from_u32(u32::from(token))
+    }
 }
 
 impl Wildcard for char {
@@ -1032,12 +2423,84 @@ impl Wildcard for char {
     const DEFAULT_CLASS_HYPHEN: Self = '-';
     const DEFAULT_CLASS_NEGATE: Self = '!';
     const DEFAULT_CLASS_OPEN: Self = '[';
+    const DEFAULT_BRACE_CLOSE: Self = '}';
+    const DEFAULT_BRACE_OPEN: Self = '{';
+    const DEFAULT_BRACE_SEPARATOR: Self = ',';
+    const DEFAULT_DOT: Self = '.';
+    const DEFAULT_EXTGLOB_AT: Self = '@';
+    const DEFAULT_EXTGLOB_CLOSE: Self = ')';
+    const DEFAULT_EXTGLOB_NOT: Self = '!';
+    const DEFAULT_EXTGLOB_OPEN: Self = '(';
+    const DEFAULT_EXTGLOB_PLUS: Self = '+';
+    const DEFAULT_EXTGLOB_SEPARATOR: Self = '|';
+    #[cfg(feature = "unicode")]
+    const FULL_CASE_FOLDING: &'static [(Self, &'static [Self])] = unicode::FULL_CASE_FOLDING;
+    const IS_UNICODE: bool = true;
+
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn canonical_combining_class(token: Self) -> u8 {
+        if token.is_ascii() {
+            0
+        } else {
+            unicode_normalization::char::canonical_combining_class(token)
+        }
+    }
+
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn compose(first: Self, second: Self) -> Option<Self> {
+        if first.is_ascii() && second.is_ascii() {
+            None
+        } else {
+            unicode_normalization::char::compose(first, second)
+        }
+    }
+
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn decompose<F>(token: Self, normalization: Normalization, mut emit: F)
+    where
+        F: FnMut(Self),
+    {
+        if token.is_ascii() {
+            emit(token);
+        } else if normalization == Normalization::Canonical {
+            unicode_normalization::char::decompose_canonical(token, emit);
+        } else {
+            unicode_normalization::char::decompose_compatible(token, emit);
+        }
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[inline]
+    fn fold_case(token: Self) -> Self {
+        token.to_ascii_lowercase()
+    }
+
+    #[cfg(feature = "unicode")]
+    #[inline]
+    fn fold_case(token: Self) -> Self {
+        unicode::fold_simple(token)
+    }
 
+    #[inline]
+    fn is_in_named_class(token: Self, class: NamedClass) -> bool {
+        class.is_member(token)
+    }
+
+    #[cfg(not(feature = "unicode"))]
     #[inline]
     fn match_one_case_insensitive(first: Self, second: Self) -> bool {
         first.eq_ignore_ascii_case(&second)
     }
 
+    #[cfg(feature = "unicode")]
+    #[inline]
+    fn match_one_case_insensitive(first: Self, second: Self) -> bool {
+        first == second || unicode::fold_simple(first) == unicode::fold_simple(second)
+    }
+
     #[inline]
     fn match_one_case_sensitive(first: Self, second: Self) -> bool {
         first == second
@@ -1048,6 +2511,7 @@ impl Wildcard for char {
         low <= token && token <= high
     }
 
+    #[cfg(not(feature = "unicode"))]
     #[inline]
     fn match_range_case_insensitive(token: Self, low: Self, high: Self) -> bool {
         if low <= token && token <= high {
@@ -1058,12 +2522,91 @@ impl Wildcard for char {
             is_in_ascii_range_case_insensitive(token as u8, low as u8, high as u8)
         }
     }
+
+    #[cfg(feature = "unicode")]
+    #[inline]
+    fn match_range_case_insensitive(token: Self, low: Self, high: Self) -> bool {
+        // Like with ASCII characters, the `token` matches if any character with the same case
+        // folding is in the range. For example, `k` matches `[\u{2100}-\u{2200}]` because of
+        // the Kelvin sign `\u{212a}`.
+        (low <= token && token <= high) || unicode::case_orbit(token).any(|c| low <= c && c <= high)
+    }
+
+    #[inline]
+    fn push_regex_literal(token: Self, regex: &mut String) {
+        if is_regex_meta_character(token) {
+            regex.push('\\');
+        }
+        regex.push(token);
+    }
+
+    #[inline]
+    fn to_ascii(token: Self) -> Option<u8> {
+        token.is_ascii().then_some(token as u8)
+    }
+
+    #[inline]
+    fn to_char(token: Self) -> Option<char> {
+        Some(token)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 // The main dowild functions
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Returns the spans of the `haystack` matched by each `*`, `?` and character class of the
+/// `pattern` or `None` if the `pattern` doesn't match.
+///
+/// The spans are returned in the order of the wildcards in the `pattern`. Every `*` is lazy
+/// and captures as few characters as possible, with a `*` further to the left taking
+/// precedence. See [`Pattern::captures`] for the details. If the same pattern is used multiple
+/// times, it is more efficient to create a [`Pattern`] once.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{captures, Options};
+///
+/// assert_eq!(
+///     captures(b"*.tar.gz", b"backup.tar.gz", Options::default()),
+///     Some(vec![0..6])
+/// );
+/// ```
+#[must_use]
+pub fn captures<T>(pattern: &[T], haystack: &[T], options: Options<T>) -> Option<Vec<Range<usize>>>
+where
+    T: Wildcard + Ord,
+{
+    Pattern::parse(pattern, options).captures(haystack)
+}
+
+/// Returns the number of non-overlapping matches of the `pattern` in the `haystack`.
+///
+/// This is the same as counting the matches of [`find_iter`].
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{count, Options};
+///
+/// assert_eq!(
+///     count(
+///         b"ERR*timeout",
+///         b"ERROR timeout, ERROR: timeout, WARN",
+///         Options::default()
+///     ),
+///     2
+/// );
+/// ```
+#[must_use]
+pub fn count<T>(pattern: &[T], haystack: &[T], options: Options<T>) -> usize
+where
+    T: Wildcard + Ord,
+{
+    Pattern::parse(pattern, options).count(haystack)
+}
+
 /// Returns `true` if the wildcard pattern matches the `haystack`.
 ///
 /// Allowed wildcard characters are `*` to match any amount of characters and `?` to match
@@ -1257,6 +2800,147 @@ pub fn dowild_with<T>(pattern: &[T], haystack: &[T], options: Options<T>) -> boo
 where
     T: Wildcard + Ord,
 {
+    dowild_with_in(pattern, haystack, options)
+}
+
+/// Like [`dowild_with`] but returns an error instead of matching with invalid [`Options`] or
+/// a malformed pattern.
+///
+/// A pattern is only rejected as malformed if [`Options::is_strict`] is set. See [`validate`]
+/// for the malformed constructs.
+///
+/// # Errors
+///
+/// Returns [`SimpleMatchError::DuplicateCharacterAssignment`] if the [`Options`] are invalid
+/// and [`SimpleMatchError::InvalidPattern`] if the `pattern` is malformed in strict mode.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{try_dowild_with, Options};
+///
+/// let options = Options::default().enable_classes(true);
+///
+/// assert_eq!(try_dowild_with(b"[!a-z", b"[!a-z", options), Ok(true));
+/// assert!(try_dowild_with(b"[!a-z", b"[!a-z", options.strict(true)).is_err());
+/// ```
+pub fn try_dowild_with<T>(
+    pattern: &[T],
+    haystack: &[T],
+    options: Options<T>,
+) -> Result<bool, SimpleMatchError>
+where
+    T: Wildcard + Ord,
+{
+    try_dowild_with_in(pattern, haystack, options)
+}
+
+/// Returns `true` if the wildcard pattern matches any [`Haystack`] like [`dowild_with`].
+///
+/// The `haystack` doesn't need to be contiguous like a `&[T]`, so a `VecDeque`, the segments
+/// of a path or, with the `ropey` feature, a `ropey::Rope` can be matched without copying
+/// them first. Matching a slice with [`dowild_with`] is faster, though.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::VecDeque;
+///
+/// use simplematch::{dowild_with_in, Chunks, Options};
+///
+/// let mut ring = VecDeque::from(b"timeout".to_vec());
+/// for c in b"ERROR: ".iter().rev() {
+///     ring.push_front(*c);
+/// }
+///
+/// assert_eq!(dowild_with_in(b"ERROR:*", &ring, Options::default()), true);
+///
+/// let segments = [&b"src"[..], b"/", b"lib.rs"];
+/// let options = Options::default().path_separator_with(b'/');
+///
+/// assert_eq!(
+///     dowild_with_in(b"src/*.rs", &Chunks::new(&segments), options),
+///     true
+/// );
+/// ```
+#[must_use]
+#[inline]
+pub fn dowild_with_in<H, T>(pattern: &[T], haystack: &H, options: Options<T>) -> bool
+where
+    T: Wildcard + Ord,
+    H: Haystack<T> + ?Sized,
+{
+    if options.is_strict && diagnostic::validate(pattern, options).is_err() {
+        return false;
+    }
+
+    dowild_with_lenient_in(pattern, haystack, options)
+}
+
+/// Like [`try_dowild_with`] but for any [`Haystack`] like [`dowild_with_in`]
+///
+/// # Errors
+///
+/// Returns [`SimpleMatchError::DuplicateCharacterAssignment`] if the [`Options`] are invalid
+/// and [`SimpleMatchError::InvalidPattern`] if the `pattern` is malformed in strict mode.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::VecDeque;
+///
+/// use simplematch::{try_dowild_with_in, Options};
+///
+/// let haystack = VecDeque::from(vec![b'a', b'b']);
+///
+/// assert_eq!(
+///     try_dowild_with_in(b"a?", &haystack, Options::default()),
+///     Ok(true)
+/// );
+/// assert!(
+///     try_dowild_with_in(b"a?", &haystack, Options::default().wildcard_one_with(b'*')).is_err()
+/// );
+/// ```
+#[inline]
+pub fn try_dowild_with_in<H, T>(
+    pattern: &[T],
+    haystack: &H,
+    options: Options<T>,
+) -> Result<bool, SimpleMatchError>
+where
+    T: Wildcard + Ord,
+    H: Haystack<T> + ?Sized,
+{
+    options.verify()?;
+    diagnostic::validate_strict(pattern, options)?;
+
+    Ok(dowild_with_lenient_in(pattern, haystack, options))
+}
+
+/// Returns `true` if the wildcard pattern matches the [`Haystack`] matching malformed
+/// constructs literally
+#[inline]
+fn dowild_with_lenient_in<H, T>(pattern: &[T], haystack: &H, options: Options<T>) -> bool
+where
+    T: Wildcard + Ord,
+    H: Haystack<T> + ?Sized,
+{
+    // Alternations and paths need the compiled pattern. Expanding alternations into all
+    // combinations would grow exponentially with the number of alternations. The same goes
+    // for characters like `ß` which fold to multiple characters and for clusters of multiple
+    // characters in the haystack.
+    if H::HAS_CLUSTERS
+        || options.is_braces_enabled
+        || options.is_extglob_enabled
+        || options.is_leading_dot_protected
+        || options.path_separator.is_some()
+        || options.wildcard_any_segment.is_some()
+        || (!options.case_sensitive && !T::FULL_CASE_FOLDING.is_empty())
+        || is_normalized(&options)
+    {
+        return Pattern::parse(pattern, options).is_match_in(haystack);
+    }
+
     if options.case_sensitive {
         dowild_with_worker(
             pattern,
@@ -1282,9 +2966,9 @@ where
 /// [`dowild`].
 #[inline]
 #[allow(clippy::too_many_lines)]
-fn dowild_with_worker<F, G, T>(
+fn dowild_with_worker<F, G, H, T>(
     pattern: &[T],
-    haystack: &[T],
+    haystack: &H,
     options: Options<T>,
     match_one: F,
     match_range: G,
@@ -1293,9 +2977,10 @@ where
     T: Wildcard + Ord,
     F: Fn(T, T) -> bool + Copy,
     G: Fn(T, T, T) -> bool + Copy,
+    H: Haystack<T> + ?Sized,
 {
     let Options {
-        class_negate,
+        class_open,
         is_classes_enabled,
         is_escape_enabled,
         wildcard_any,
@@ -1307,20 +2992,31 @@ where
     let is_wildcard_any = |token: T| token == wildcard_any;
     let is_wildcard_one = |token: T| token == wildcard_one;
     let is_escape = |token: T| is_escape_enabled && token == wildcard_escape;
-    let is_class_open = |token: T| is_classes_enabled && token == T::DEFAULT_CLASS_OPEN;
+    let is_class_open = |token: T| is_classes_enabled && token == class_open;
 
     let is_special = |token: T| {
         token == wildcard_any
             || token == wildcard_one
             || token == wildcard_escape
-            || (is_classes_enabled && token == T::DEFAULT_CLASS_OPEN)
+            || (is_classes_enabled && token == class_open)
     };
 
     let is_valid_class_or_escape = |token: T, p_idx: usize, invalid_class_idx: usize| {
-        (is_classes_enabled && token == T::DEFAULT_CLASS_OPEN && p_idx < invalid_class_idx)
+        (is_classes_enabled && token == class_open && p_idx < invalid_class_idx)
             || (is_escape_enabled && token == wildcard_escape)
     };
 
+    // Advance the haystack to the first character which matches the `token`
+    let skip_to = |mut h_idx: usize, token: T| {
+        while let Some((h, next)) = haystack.next(h_idx) {
+            if match_one(h, token) {
+                break;
+            }
+            h_idx = next;
+        }
+        h_idx
+    };
+
     let mut p_idx = 0;
     let mut h_idx = 0;
 
@@ -1333,7 +3029,7 @@ where
     let mut has_seen_wildcard_any = false;
     let mut invalid_class_idx = usize::MAX;
 
-    while p_idx < pattern.len() || h_idx < haystack.len() {
+    while p_idx < pattern.len() || h_idx < haystack.end() {
         if p_idx < pattern.len() {
             match pattern[p_idx] {
                 c if is_wildcard_any(c) => {
@@ -1350,9 +3046,9 @@ where
                     let next_c = pattern[p_idx];
                     #[allow(clippy::else_if_without_else)]
                     if is_wildcard_one(next_c) {
-                        while h_idx < haystack.len() {
+                        while let Some((_, next)) = haystack.next(h_idx) {
                             p_idx += 1;
-                            h_idx += 1;
+                            h_idx = next;
                             if !(p_idx < pattern.len() && is_wildcard_one(pattern[p_idx])) {
                                 break;
                             }
@@ -1361,10 +3057,8 @@ where
                             return true;
                         }
                     } else if !is_valid_class_or_escape(next_c, p_idx, invalid_class_idx) {
-                        while h_idx < haystack.len() && !match_one(haystack[h_idx], next_c) {
-                            h_idx += 1;
-                        }
-                        if h_idx >= haystack.len() {
+                        h_idx = skip_to(h_idx, next_c);
+                        if h_idx >= haystack.end() {
                             return false;
                         }
                     }
@@ -1374,27 +3068,26 @@ where
                     continue;
                 }
                 c if is_wildcard_one(c) => {
-                    if h_idx < haystack.len() {
+                    if let Some((_, next)) = haystack.next(h_idx) {
                         p_idx += 1;
-                        h_idx += 1;
+                        h_idx = next;
                         continue;
                     }
                 }
                 // Handling of the escape character. If it is the last character in the pattern, it
                 // can only stand for itself.
                 c if is_escape(c) && p_idx + 1 < pattern.len() => {
-                    if h_idx < haystack.len() {
+                    if let Some((h, next)) = haystack.next(h_idx) {
                         let next_c = pattern[p_idx + 1];
-                        let h = haystack[h_idx];
 
                         #[allow(clippy::else_if_without_else)]
                         if is_special(next_c) && h == next_c {
                             p_idx += 2;
-                            h_idx += 1;
+                            h_idx = next;
                             continue;
                         } else if !is_special(next_c) && h == wildcard_escape {
                             p_idx += 1;
-                            h_idx += 1;
+                            h_idx = next;
                             continue;
                         }
                     }
@@ -1404,23 +3097,15 @@ where
                 // classes that are outside of the possible index don't need to be considered anymore
                 // and are pruned.
                 c if is_class_open(c) && p_idx < invalid_class_idx && p_idx + 1 < pattern.len() => {
-                    if h_idx < haystack.len() {
+                    if let Some((h, next)) = haystack.next(h_idx) {
                         let class = if has_seen_wildcard_any {
                             // Try to get rid of classes outside of the possible index
                             classes.prune(next_p_idx);
-                            BorrowedOrOwned::Borrowed(classes.get_or_add(
-                                p_idx,
-                                pattern,
-                                class_negate,
-                            ))
+                            BorrowedOrOwned::Borrowed(classes.get_or_add(p_idx, pattern, &options))
                         } else {
                             // There's no need to store character classes as long as we don't require
                             // to reset.
-                            BorrowedOrOwned::Owned(CharacterClasses::parse(
-                                p_idx,
-                                pattern,
-                                class_negate,
-                            ))
+                            BorrowedOrOwned::Owned(CharacterClasses::parse(p_idx, pattern, &options))
                         };
 
                         // Try to match this class. If it is an invalid class, we can interpret the
@@ -1428,46 +3113,41 @@ where
                         // there is no class. If the class is valid and matched, we can advance as
                         // usual, otherwise we need to reset.
                         #[allow(clippy::else_if_without_else)]
-                        if let Some(is_match) =
-                            class.try_match(haystack[h_idx], match_one, match_range)
-                        {
+                        if let Some(is_match) = class.try_match(h, match_one, match_range) {
                             p_idx += class.len();
                             if is_match {
-                                h_idx += 1;
+                                h_idx = next;
                                 continue;
                             }
                         } else {
                             invalid_class_idx = class.as_ref().start;
                             // A small shortcut to avoid the big loop and enter the generic
                             // character case.
-                            if match_one(haystack[h_idx], T::DEFAULT_CLASS_OPEN) {
+                            if match_one(h, class_open) {
                                 p_idx += 1;
-                                h_idx += 1;
+                                h_idx = next;
                                 continue;
                             }
                         }
                     }
                 }
                 c => {
-                    if h_idx < haystack.len() && match_one(haystack[h_idx], c) {
+                    if let Some((_, next)) = haystack.next(h_idx).filter(|(h, _)| match_one(*h, c)) {
                         p_idx += 1;
-                        h_idx += 1;
+                        h_idx = next;
                         continue;
                     }
                 }
             }
         }
-        if has_seen_wildcard_any && next_h_idx < haystack.len() {
+        if has_seen_wildcard_any && next_h_idx < haystack.end() {
             p_idx = next_p_idx;
-            next_h_idx += 1;
+            next_h_idx = haystack.step(next_h_idx);
 
             if p_idx < pattern.len()
                 && !is_valid_class_or_escape(pattern[p_idx], p_idx, invalid_class_idx)
             {
-                while next_h_idx < haystack.len() && !match_one(haystack[next_h_idx], pattern[p_idx])
-                {
-                    next_h_idx += 1;
-                }
+                next_h_idx = skip_to(next_h_idx, pattern[p_idx]);
             }
 
             h_idx = next_h_idx;
@@ -1479,6 +3159,236 @@ where
     true
 }
 
+/// Returns the leftmost and shortest span of the `haystack` matched by the `pattern`.
+///
+/// In contrast to [`dowild_with`], the `pattern` doesn't need to match the whole `haystack`.
+/// Of all matches, the one which starts first is returned and of these the shortest. Returns
+/// `None` if the `pattern` doesn't match anywhere in the `haystack`. The [`Options`] are
+/// applied the same way as in [`dowild_with`].
+///
+/// If the same pattern is used multiple times, it is more efficient to create a [`Pattern`]
+/// once and use [`Pattern::find`].
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{find, Options};
+///
+/// let haystack = b"[12:00] ERROR: timeout, ERROR: timeout";
+///
+/// assert_eq!(
+///     find(b"ERR?R*timeout", haystack, Options::default()),
+///     Some(8..22)
+/// );
+/// assert_eq!(find(b"WARN*", haystack, Options::default()), None);
+/// ```
+#[must_use]
+pub fn find<T>(pattern: &[T], haystack: &[T], options: Options<T>) -> Option<Range<usize>>
+where
+    T: Wildcard + Ord,
+{
+    Pattern::parse(pattern, options).find(haystack)
+}
+
+/// Returns an iterator over all non-overlapping matches of the `pattern` in the `haystack`.
+///
+/// Every match is the leftmost and shortest match like with [`find`], searched from the end of
+/// the previous match. See [`FindIter`] for the handling of empty matches. If the same pattern
+/// is used multiple times, it is more efficient to create a [`Pattern`] once and use
+/// [`Pattern::find_iter`].
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{find_iter, Options};
+///
+/// let haystack = b"ERROR timeout, ERROR: timeout, WARN";
+/// let matches = find_iter(b"ERR*timeout", haystack, Options::default()).collect::<Vec<_>>();
+///
+/// assert_eq!(matches, [0..13, 15..29]);
+/// ```
+pub fn find_iter<'h, T>(
+    pattern: &[T],
+    haystack: &'h [T],
+    options: Options<T>,
+) -> FindIter<'static, 'h, T>
+where
+    T: Wildcard + Ord,
+{
+    FindIter::new(
+        BorrowedOrOwned::Owned(Pattern::parse(pattern, options).into_program()),
+        haystack,
+    )
+}
+
+/// Returns the leftmost and longest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`find`] but of all matches starting at the leftmost index the longest is returned.
+/// See also [`Pattern::find_longest`].
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{find_longest, Options};
+///
+/// let haystack = b"[12:00] ERROR: timeout, ERROR: timeout";
+///
+/// assert_eq!(
+///     find_longest(b"ERR?R*timeout", haystack, Options::default()),
+///     Some(8..38)
+/// );
+/// ```
+#[must_use]
+pub fn find_longest<T>(pattern: &[T], haystack: &[T], options: Options<T>) -> Option<Range<usize>>
+where
+    T: Wildcard + Ord,
+{
+    Pattern::parse(pattern, options).find_longest(haystack)
+}
+
+/// Translate the `pattern` to a regular expression which matches the same haystacks.
+///
+/// The regex matches exactly the haystacks which [`dowild_with`] matches with the same
+/// [`Options`]. It is anchored at both ends with `^` and `$`, so it has to match the whole
+/// haystack like the `pattern`.
+///
+/// The target flavor is the syntax of the Rust [`regex`](https://docs.rs/regex) crate, which is
+/// also used by ripgrep. The regex starts with the flags `(?s)`, so `.` matches a newline,
+/// adds `i` if the `pattern` is case-insensitive and unsets `u` for a `u8` pattern, which
+/// makes it a regex for [`regex::bytes`](https://docs.rs/regex/latest/regex/bytes/index.html).
+/// Literals are escaped with a backslash or written as `\xHH` if they are non-ASCII bytes.
+/// Character classes may use Unicode properties like `\p{Alphabetic}` and the intersection
+/// `&&`, so they might have to be adjusted for other engines, like the one of PostgreSQL.
+///
+/// A case-insensitive `char` pattern without the `unicode` feature folds only ASCII letters,
+/// which the regex spells out as `[aA]` instead of using the flag `i`.
+///
+/// # Errors
+///
+/// Returns [`SimpleMatchError::DuplicateCharacterAssignment`] if the [`Options`] are invalid,
+/// [`SimpleMatchError::InvalidPattern`] if the `pattern` is malformed and
+/// [`Options::is_strict`] is set and [`SimpleMatchError::NoRegexEquivalent`] if the `pattern`
+/// can't be expressed as regex:
+///
+/// * A negated extended glob `!(...)` has no regex equivalent without lookarounds.
+/// * The Unicode normalization of [`Options::normalization`] is applied to the haystack.
+/// * A case-insensitive pattern with the `unicode` feature contains a character like `ß` or a
+///   sequence of characters like `ss` which take part in the full case folding.
+/// * With [`Options::protect_leading_dot`], a `?`, `*` or character class follows a token
+///   which may or may not end at the start of a path segment, like in `*?`. Whether the
+///   leading dot rule applies then depends on the haystack.
+/// * A case-insensitive negated class of `char`s like `[![:upper:]a]` mixes a named class,
+///   which is not folded, with other members.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{to_regex_string, Options, SimpleMatchError};
+///
+/// assert_eq!(
+///     to_regex_string(b"*.tar.gz", Options::default()).unwrap(),
+///     "(?s-u)^.*\\.tar\\.gz$"
+/// );
+/// assert_eq!(
+///     to_regex_string(
+///         "src/*.[ch]".chars().collect::<Vec<_>>().as_slice(),
+///         Options::default()
+///             .enable_classes(true)
+///             .path_separator_with('/')
+///     )
+///     .unwrap(),
+///     "(?s)^src/[^/]*\\.[ch]$"
+/// );
+/// assert_eq!(
+///     to_regex_string(b"!(*.bak)", Options::default().enable_extglob(true)),
+///     Err(SimpleMatchError::NoRegexEquivalent)
+/// );
+/// ```
+pub fn to_regex_string<T>(pattern: &[T], options: Options<T>) -> Result<String, SimpleMatchError>
+where
+    T: Wildcard + Ord,
+{
+    regex::translate(pattern, options)
+}
+
+/// Returns an error if the `pattern` contains a malformed construct.
+///
+/// Malformed constructs don't make a pattern unusable. They are matched literally instead,
+/// which is rarely what the author of the pattern intended. This function reports the leftmost
+/// of them as [`SimpleMatchError::InvalidPattern`] with the [`PatternErrorKind`], the span in
+/// the `pattern` and a suggestion how to fix it:
+///
+/// * A pattern ending with the escape character, if [`Options::is_escape_enabled`]
+/// * A character class `[` without a closing `]`, if [`Options::is_classes_enabled`]
+/// * A `{` without a closing `}` and a `}` without an opening `{`, if
+///   [`Options::is_braces_enabled`]
+/// * An extended glob like `@(` without a closing `)` and a `)` without an extended glob, if
+///   [`Options::is_extglob_enabled`]
+///
+/// An escape character which doesn't escape a special character and a separator like `,`
+/// outside of a group are not reported, since matching them literally is intended.
+///
+/// # Errors
+///
+/// Returns [`SimpleMatchError::DuplicateCharacterAssignment`] if the [`Options`] are invalid
+/// and [`SimpleMatchError::InvalidPattern`] if the `pattern` is malformed.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{validate, Options, PatternErrorKind, SimpleMatchError};
+///
+/// let options = Options::default().enable_braces(true).enable_escape(true);
+///
+/// assert_eq!(validate(b"*.{rs,toml}", options), Ok(()));
+///
+/// let error = match validate(b"*.{rs,toml", options) {
+///     Err(SimpleMatchError::InvalidPattern(error)) => error,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(error.kind(), PatternErrorKind::UnclosedBrace);
+/// assert_eq!(error.span(), 2..3);
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid pattern: The brace alternation is not closed.\n    *.{rs,toml\n      ^\nHelp: \
+///      Close the alternation with `}` or escape it as `\\{`."
+/// );
+/// ```
+pub fn validate<T>(pattern: &[T], options: Options<T>) -> Result<(), SimpleMatchError>
+where
+    T: Wildcard + Ord,
+{
+    options.verify()?;
+    diagnostic::validate(pattern, options).map_err(SimpleMatchError::InvalidPattern)
+}
+
+/// Returns the full case folding of the `token` if it folds to more than one token
+#[inline]
+fn fold_case_full<T>(token: T) -> Option<&'static [T]>
+where
+    T: Wildcard + Ord,
+{
+    T::FULL_CASE_FOLDING
+        .binary_search_by(|(c, _)| c.cmp(&token))
+        .ok()
+        .map(|index| T::FULL_CASE_FOLDING[index].1)
+}
+
+/// Returns `true` if the [`Options`] match Unicode equivalent sequences
+#[inline]
+const fn is_normalized<T>(options: &Options<T>) -> bool
+where
+    T: Wildcard,
+{
+    #[cfg(feature = "normalization")]
+    return options.normalization.is_some();
+    #[cfg(not(feature = "normalization"))]
+    {
+        let _ = options;
+        false
+    }
+}
+
 /// Returns true if the `token` is in the case insensitive inclusive range from `low` to `high`
 ///
 /// `token` has to be ascii alphabetic character.
@@ -1500,6 +3410,32 @@ const fn is_in_ascii_range_case_insensitive(token: u8, low: u8, high: u8) -> boo
     }
 }
 
+/// Returns `true` if the character `c` has a special meaning in the syntax of
+/// [`to_regex_string`]
+#[inline]
+const fn is_regex_meta_character(c: char) -> bool {
+    matches!(
+        c,
+        '\\' | '.'
+            | '+'
+            | '*'
+            | '?'
+            | '('
+            | ')'
+            | '|'
+            | '['
+            | ']'
+            | '{'
+            | '}'
+            | '^'
+            | '$'
+            | '#'
+            | '&'
+            | '-'
+            | '~'
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -0,0 +1,232 @@
+//! The SQL `LIKE` and `ILIKE` predicates
+//!
+//! A `%` matches any sequence of characters including the empty one and a `_` matches exactly
+//! one character. All other characters match themselves. The [`like`] and [`ilike`] functions
+//! evaluate a predicate once and a [`Like`] is parsed once to be matched against many strings:
+//!
+//! ```rust
+//! use simplematch::like::{ilike, like, Like, LikeError, DEFAULT_ESCAPE};
+//!
+//! assert_eq!(like("a%c", "abbc", Some(DEFAULT_ESCAPE)), Ok(true));
+//! assert_eq!(like("100\\%", "100%", Some(DEFAULT_ESCAPE)), Ok(true));
+//! assert_eq!(ilike("ABC_", "abcd", None), Ok(true));
+//! assert_eq!(
+//!     like("100\\", "100", Some(DEFAULT_ESCAPE)),
+//!     Err(LikeError::TrailingEscape)
+//! );
+//!
+//! let predicate = Like::new("%.rs", None).unwrap();
+//! assert_eq!(predicate.is_match("lib.rs"), true);
+//! ```
+//!
+//! The escape character follows the rule of the SQL standard, which is stricter than
+//! PostgreSQL and SQLite:
+//!
+//! * The escape character must precede a `%`, a `_` or the escape character itself, which then
+//!   match literally. Any other character after the escape character and a trailing escape
+//!   character are an error. PostgreSQL and SQLite accept the escape character before any
+//!   character and match that character literally.
+//! * PostgreSQL uses the backslash as escape character if there is no `ESCAPE` clause. Pass
+//!   [`DEFAULT_ESCAPE`] for this default and `None` for SQLite, which has no escape character
+//!   without an `ESCAPE` clause. [`parse_escape`] turns the string of an `ESCAPE` clause into
+//!   the escape character.
+//! * `LIKE` is case-sensitive like in PostgreSQL. SQLite's `LIKE` folds the ASCII characters,
+//!   which corresponds to [`ilike`] without the `unicode` feature. With the `unicode` feature,
+//!   [`ilike`] folds all characters like PostgreSQL's `ILIKE`.
+//! * The strings are matched character by character, so `_` matches a single character even if
+//!   it is encoded with multiple bytes.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::pattern::Token;
+use crate::program::Lexeme;
+use crate::{Options, Pattern};
+
+/// The escape character of PostgreSQL if a `LIKE` has no `ESCAPE` clause
+pub const DEFAULT_ESCAPE: char = '\\';
+
+/// A precompiled `LIKE` or `ILIKE` pattern
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::like::Like;
+///
+/// let predicate = Like::new("user\\_%", Some('\\')).unwrap();
+///
+/// assert_eq!(predicate.is_match("user_42"), true);
+/// assert_eq!(predicate.is_match("username"), false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Like(Pattern<char>);
+
+/// The error of a malformed `LIKE` pattern or `ESCAPE` clause
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LikeError {
+    /// The escape character is followed by something else than `%`, `_` or itself
+    ///
+    /// The index is the byte offset of the escape character in the pattern.
+    InvalidEscapeSequence(usize),
+    /// The string of the `ESCAPE` clause is neither empty nor a single character
+    InvalidEscapeString,
+    /// The pattern ends with the escape character
+    TrailingEscape,
+}
+
+impl Like {
+    /// Parse the `pattern` of a case-insensitive `ILIKE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `escape` character is used wrongly in the `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::like::Like;
+    ///
+    /// let predicate = Like::ilike("%.JPG", None).unwrap();
+    ///
+    /// assert_eq!(predicate.is_match("holiday.jpg"), true);
+    /// ```
+    pub fn ilike(pattern: &str, escape: Option<char>) -> Result<Self, LikeError> {
+        Self::parse(pattern, escape, false)
+    }
+
+    /// Returns `true` if the `string` matches this pattern.
+    #[must_use]
+    pub fn is_match(&self, string: &str) -> bool {
+        self.0.is_match_str(string)
+    }
+
+    /// Parse the `pattern` of a case-sensitive `LIKE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `escape` character is used wrongly in the `pattern`.
+    pub fn new(pattern: &str, escape: Option<char>) -> Result<Self, LikeError> {
+        Self::parse(pattern, escape, true)
+    }
+
+    fn parse(pattern: &str, escape: Option<char>, case_sensitive: bool) -> Result<Self, LikeError> {
+        let mut lexemes = Vec::with_capacity(pattern.len());
+        let mut chars = pattern.char_indices();
+
+        while let Some((index, c)) = chars.next() {
+            let token = if Some(c) == escape {
+                match chars.next() {
+                    Some((_, next)) if next == c || next == '%' || next == '_' => {
+                        Token::Literal(next)
+                    }
+                    Some(_) => return Err(LikeError::InvalidEscapeSequence(index)),
+                    None => return Err(LikeError::TrailingEscape),
+                }
+            } else if c == '%' {
+                Token::Any
+            } else if c == '_' {
+                Token::One
+            } else {
+                Token::Literal(c)
+            };
+            lexemes.push(Lexeme::Token(token));
+        }
+
+        Ok(Self(Pattern::from_lexemes(
+            lexemes,
+            Options::new().case_insensitive(!case_sensitive),
+        )))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for LikeError {}
+
+impl Display for LikeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidEscapeSequence(index) => write!(
+                f,
+                "Invalid LIKE pattern: The escape character at byte {index} must be followed by \
+                 '%', '_' or the escape character."
+            ),
+            Self::InvalidEscapeString => write!(
+                f,
+                "Invalid escape string: The escape string must be empty or one character."
+            ),
+            Self::TrailingEscape => write!(
+                f,
+                "Invalid LIKE pattern: The pattern must not end with the escape character."
+            ),
+        }
+    }
+}
+
+/// Returns `true` if the `string` matches the `pattern` of a case-insensitive `ILIKE`.
+///
+/// # Errors
+///
+/// Returns an error if the `escape` character is used wrongly in the `pattern`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::like::ilike;
+///
+/// assert_eq!(ilike("hello%", "HELLO, WORLD", None), Ok(true));
+/// ```
+pub fn ilike(pattern: &str, string: &str, escape: Option<char>) -> Result<bool, LikeError> {
+    Like::ilike(pattern, escape).map(|like| like.is_match(string))
+}
+
+/// Returns `true` if the `string` matches the `pattern` of a case-sensitive `LIKE`.
+///
+/// # Errors
+///
+/// Returns an error if the `escape` character is used wrongly in the `pattern`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::like::{like, LikeError};
+///
+/// assert_eq!(like("a_c", "abc", None), Ok(true));
+/// assert_eq!(like("a!%", "a%", Some('!')), Ok(true));
+/// assert_eq!(
+///     like("a!b", "ab", Some('!')),
+///     Err(LikeError::InvalidEscapeSequence(1))
+/// );
+/// ```
+pub fn like(pattern: &str, string: &str, escape: Option<char>) -> Result<bool, LikeError> {
+    Like::new(pattern, escape).map(|like| like.is_match(string))
+}
+
+/// Returns the escape character of the string of an `ESCAPE` clause.
+///
+/// Like in PostgreSQL, an empty string disables escaping and returns `None`.
+///
+/// # Errors
+///
+/// Returns [`LikeError::InvalidEscapeString`] if the `escape` has more than one character.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::like::{parse_escape, LikeError};
+///
+/// assert_eq!(parse_escape("#"), Ok(Some('#')));
+/// assert_eq!(parse_escape(""), Ok(None));
+/// assert_eq!(parse_escape("##"), Err(LikeError::InvalidEscapeString));
+/// ```
+pub fn parse_escape(escape: &str) -> Result<Option<char>, LikeError> {
+    let mut chars = escape.chars();
+    match (chars.next(), chars.next()) {
+        (c, None) => Ok(c),
+        _ => Err(LikeError::InvalidEscapeString),
+    }
+}
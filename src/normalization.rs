@@ -0,0 +1,213 @@
+//! The normalization-insensitive matching of the `normalization` feature
+//!
+//! Both sides are compared in their decomposed form. The literals of the pattern are
+//! decomposed once when the pattern is parsed. The haystack is wrapped in [`Normalized`]
+//! which decomposes it cluster by cluster while the [`Program`](crate::program::Program)
+//! walks through it, so there is never a normalized copy of the whole haystack.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::haystack::Haystack;
+use crate::pattern::Token;
+use crate::program::Lexeme;
+use crate::{Class, ClassKind, Normalization, Wildcard};
+
+/// A haystack which is matched in its decomposed form
+///
+/// Every cluster is a starter of the inner haystack followed by all of its combining marks
+/// in canonical order. The positions are the positions of the inner haystack, so all spans
+/// refer to the original haystack.
+pub struct Normalized<'h, H>
+where
+    H: ?Sized,
+{
+    haystack: &'h H,
+    normalization: Normalization,
+}
+
+impl<T, H> Haystack<T> for Normalized<'_, H>
+where
+    T: Wildcard,
+    H: Haystack<T> + ?Sized,
+{
+    const HAS_CLUSTERS: bool = true;
+    const IS_INDEXED: bool = false;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.haystack.end()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(T, usize)> {
+        self.haystack.next(pos)
+    }
+
+    fn next_cluster(&self, pos: usize, tokens: &mut Vec<T>) -> Option<(Option<T>, usize)> {
+        let start = tokens.len();
+        let (_, mut end) = self.haystack.next_cluster(pos, tokens)?;
+        decompose_from(tokens, start, self.normalization);
+
+        // Combining marks and Hangul jamo can follow as clusters of their own, for example in
+        // a `str` which has a cluster for every character
+        while let Some((c, _)) = self.haystack.next(end) {
+            let mut first = None;
+            T::decompose(c, self.normalization, |d| {
+                first = first.or(Some(d));
+            });
+            let is_continued = first.map_or(false, |first| {
+                T::canonical_combining_class(first) != 0
+                    || compose_all(&tokens[start..])
+                        .and_then(|composed| T::compose(composed, first))
+                        .is_some()
+            });
+            if !is_continued {
+                break;
+            }
+
+            let mark = tokens.len();
+            end = match self.haystack.next_cluster(end, tokens) {
+                Some((_, next_end)) => next_end,
+                None => break,
+            };
+            decompose_from(tokens, mark, self.normalization);
+        }
+
+        reorder(&mut tokens[start..]);
+        Some((compose_all(&tokens[start..]), end))
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(T, usize)> {
+        self.haystack.prev(pos)
+    }
+
+    #[inline]
+    fn step(&self, pos: usize) -> usize {
+        self.next_cluster(pos, &mut Vec::new())
+            .map_or(pos + 1, |(_, end)| end)
+    }
+}
+
+impl<'h, H> Normalized<'h, H>
+where
+    H: ?Sized,
+{
+    pub const fn new(haystack: &'h H, normalization: Normalization) -> Self {
+        Self {
+            haystack,
+            normalization,
+        }
+    }
+}
+
+/// Decompose the literals of the `lexemes` and reorder their combining marks.
+///
+/// The single characters of classes are replaced by their composed form, which is the form
+/// a [`Normalized`] haystack offers to classes.
+pub fn normalize_lexemes<T>(lexemes: Vec<Lexeme<T>>, normalization: Normalization) -> Vec<Lexeme<T>>
+where
+    T: Wildcard,
+{
+    let mut normalized = Vec::with_capacity(lexemes.len());
+    let mut run = Vec::new();
+    for lexeme in lexemes {
+        match lexeme {
+            Lexeme::Token(Token::Literal(c)) => {
+                T::decompose(c, normalization, |d| run.push(d));
+            }
+            Lexeme::Token(Token::Class(class)) => {
+                flush(&mut run, &mut normalized);
+                normalized.push(Lexeme::Token(Token::Class(normalize_class(
+                    class,
+                    normalization,
+                ))));
+            }
+            lexeme => {
+                flush(&mut run, &mut normalized);
+                normalized.push(lexeme);
+            }
+        }
+    }
+    flush(&mut run, &mut normalized);
+
+    normalized
+}
+
+/// Returns the composition of all `tokens` if they compose to a single token
+fn compose_all<T>(tokens: &[T]) -> Option<T>
+where
+    T: Wildcard,
+{
+    let (first, rest) = tokens.split_first()?;
+    rest.iter()
+        .try_fold(*first, |composed, c| T::compose(composed, *c))
+}
+
+/// Replace the tokens starting at `start` with their decomposition.
+fn decompose_from<T>(tokens: &mut Vec<T>, start: usize, normalization: Normalization)
+where
+    T: Wildcard,
+{
+    let end = tokens.len();
+    for index in start..end {
+        let c = tokens[index];
+        T::decompose(c, normalization, |d| tokens.push(d));
+    }
+    tokens.drain(start..end);
+}
+
+/// Push the reordered `run` of decomposed literals as lexemes.
+fn flush<T>(run: &mut Vec<T>, lexemes: &mut Vec<Lexeme<T>>)
+where
+    T: Wildcard,
+{
+    reorder(run);
+    lexemes.extend(run.drain(..).map(|c| Lexeme::Token(Token::Literal(c))));
+}
+
+/// Replace the single characters of the `class` by their composed form.
+fn normalize_class<T>(class: Class<T>, normalization: Normalization) -> Class<T>
+where
+    T: Wildcard,
+{
+    let normalize = |kind| match kind {
+        ClassKind::Escaped(c) => ClassKind::Escaped(normalize_one(c, normalization)),
+        ClassKind::One(c) => ClassKind::One(normalize_one(c, normalization)),
+        ClassKind::RangeOne(c) => ClassKind::RangeOne(normalize_one(c, normalization)),
+        kind => kind,
+    };
+    match class {
+        Class::Positive(kinds) => Class::Positive(kinds.into_iter().map(normalize).collect()),
+        Class::Negative(kinds) => Class::Negative(kinds.into_iter().map(normalize).collect()),
+    }
+}
+
+/// Returns the composed form of the character `c` or `c` itself if it has none
+fn normalize_one<T>(c: T, normalization: Normalization) -> T
+where
+    T: Wildcard,
+{
+    let mut tokens = Vec::new();
+    T::decompose(c, normalization, |d| tokens.push(d));
+    reorder(&mut tokens);
+    compose_all(&tokens).unwrap_or(c)
+}
+
+/// Sort every run of combining marks by their canonical combining class.
+///
+/// The sort is stable, so marks of the same class keep their order like the canonical
+/// ordering algorithm demands.
+fn reorder<T>(tokens: &mut [T])
+where
+    T: Wildcard,
+{
+    for marks in tokens.split_mut(|c| T::canonical_combining_class(*c) == 0) {
+        if marks.len() > 1 {
+            marks.sort_by_key(|c| T::canonical_combining_class(*c));
+        }
+    }
+}
@@ -0,0 +1,868 @@
+//! The precompiled [`Pattern`]
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::haystack::Haystack;
+#[cfg(feature = "normalization")]
+use crate::normalization;
+use crate::program::{Extglob, Lexeme, Program, Search};
+use crate::{
+    diagnostic, BorrowedOrOwned, CharacterClass, Class, Options, SimpleMatchError, Wildcard,
+};
+
+/// A single unit of a compiled [`Pattern`]
+#[derive(Debug, Clone)]
+pub enum Token<T> {
+    /// The `wildcard_any` character `*`
+    Any,
+    /// A valid character class `[...]`
+    Class(Class<T>),
+    /// A character following the escape character or the escape character itself. Matched
+    /// case-sensitive like in [`dowild_with`](crate::dowild_with).
+    Escaped(T),
+    /// Any other character including the opening bracket of an invalid class
+    Literal(T),
+    /// The `wildcard_one` character `?`
+    One,
+}
+
+/// A wildcard pattern which is parsed once and can be matched against many haystacks
+///
+/// Calling [`dowild_with`](crate::dowild_with) repeatedly with the same pattern scans the
+/// pattern, evaluates the [`Options`] and parses character classes on every call. A `Pattern`
+/// does all this work up front in [`Pattern::new`], so [`Pattern::is_match`] only has to walk
+/// the haystack.
+///
+/// The matching semantics are exactly the same as with [`dowild`](crate::dowild) and
+/// [`dowild_with`](crate::dowild_with) given the same [`Options`]. A `Pattern` owns its data
+/// and is `Clone`, `Send` and `Sync` if `T` is.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{Options, Pattern};
+///
+/// let pattern = Pattern::new(b"*.[ch]", Options::default().enable_classes(true)).unwrap();
+///
+/// assert_eq!(pattern.is_match(b"main.c"), true);
+/// assert_eq!(pattern.is_match(b"lib.h"), true);
+/// assert_eq!(pattern.is_match(b"lib.rs"), false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pattern<T>
+where
+    T: Wildcard,
+{
+    /// `true` if the pattern contains at least one `wildcard_any` token
+    has_any: bool,
+    /// The minimum length of a haystack to be able to match
+    min_len: usize,
+    /// `true` if the `tokens` are not enough to express the pattern, for example if it
+    /// contains brace alternations. The `tokens` are empty then.
+    needs_program: bool,
+    options: Options<T>,
+    /// The compiled pattern which finds the matches in a haystack and matches the haystacks
+    /// the `tokens` can't
+    program: Program<T>,
+    tokens: Vec<Token<T>>,
+}
+
+/// An iterator over all non-overlapping matches of a [`Pattern`] in a haystack
+///
+/// This `struct` is created by [`Pattern::find_iter`] or [`find_iter`](crate::find_iter). The
+/// matches are found from left to right like with [`Pattern::find`], but all of them in a
+/// single scan of the haystack. A match is returned as soon as no match from an earlier start
+/// is possible anymore. The characters in front of a match are still seen, for example by
+/// [`Options::protect_leading_dot`], and all spans are indices of the whole haystack.
+///
+/// If a pattern can match an empty span, an empty match is only reported if it doesn't end
+/// where the previous match ended. After an empty match, the search continues at the next
+/// index. For example, the pattern `*` matches four times in the haystack `abc` at `0..0`,
+/// `1..1`, `2..2` and `3..3`.
+#[derive(Debug, Clone)]
+pub struct FindIter<'p, 'h, T>
+where
+    T: Wildcard,
+{
+    haystack: &'h [T],
+    program: BorrowedOrOwned<'p, Program<T>>,
+    search: Search<T>,
+}
+
+/// Record the haystack index at which a token of the [`Pattern`] starts to match
+///
+/// The unit type `()` records nothing, so matching without captures doesn't pay for them.
+trait Recorder {
+    fn record(&mut self, t_idx: usize, h_idx: usize);
+}
+
+impl<'p, 'h, T> FindIter<'p, 'h, T>
+where
+    T: Wildcard + Ord,
+{
+    /// Create a new `FindIter` starting at the beginning of the `haystack`
+    pub(crate) fn new(program: BorrowedOrOwned<'p, Program<T>>, haystack: &'h [T]) -> Self {
+        let search = program.search();
+        Self {
+            haystack,
+            program,
+            search,
+        }
+    }
+}
+
+impl<T> FusedIterator for FindIter<'_, '_, T> where T: Wildcard + Ord {}
+
+impl<T> Iterator for FindIter<'_, '_, T>
+where
+    T: Wildcard + Ord,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.program.find_next(&mut self.search, self.haystack)
+    }
+}
+
+impl<T> Pattern<T>
+where
+    T: Wildcard + Ord,
+{
+    /// Parse the `pattern` with the given [`Options`] into a new `Pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Options`] are invalid. See also [`Options::verify`]. If
+    /// [`Options::is_strict`] is set, returns an error if the `pattern` is malformed, too. See
+    /// also [`validate`](crate::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new("foo*".as_bytes(), Options::default()).unwrap();
+    /// assert_eq!(pattern.is_match("foobar".as_bytes()), true);
+    /// ```
+    pub fn new(pattern: &[T], options: Options<T>) -> Result<Self, SimpleMatchError> {
+        options.verify()?;
+        diagnostic::validate_strict(pattern, options)?;
+        Ok(Self::parse(pattern, options))
+    }
+
+    /// Returns the spans of the `haystack` matched by each `*`, `?` and character class in the
+    /// order of their appearance in this `Pattern` or `None` if the pattern doesn't match.
+    ///
+    /// Every `*` is lazy and captures the shortest possible span with a `*` further to the
+    /// left taking precedence over the ones to the right. Since the whole `haystack` has
+    /// to match, a `*` at the end of the pattern captures the rest of the `haystack`. Of
+    /// consecutive `*` only the last one captures anything. A `?` and a character class
+    /// always capture a single character. Escaped characters, literal characters and
+    /// invalid classes capture nothing. A brace alternation captures the span matched by the
+    /// first alternative which leads to a match, while the tokens inside of it capture
+    /// nothing.
+    /// A globstar `**` captures the whole segments it matched including their separators.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"*.tar.?z", Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.captures(b"backup.tar.gz"), Some(vec![0..6, 11..12]));
+    /// assert_eq!(pattern.captures(b"backup.zip"), None);
+    /// ```
+    ///
+    /// The first `*` stops as early as possible:
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"*.*", Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.captures(b"a.b.c"), Some(vec![0..1, 2..5]));
+    /// ```
+    #[must_use]
+    pub fn captures(&self, haystack: &[T]) -> Option<Vec<Range<usize>>> {
+        self.captures_in(haystack)
+    }
+
+    /// Returns the number of non-overlapping matches of this `Pattern` in the `haystack`.
+    ///
+    /// This is the same as counting the matches of [`Pattern::find_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"ERR*timeout", Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.count(b"ERROR timeout, ERROR: timeout, WARN"), 2);
+    /// ```
+    #[must_use]
+    pub fn count(&self, haystack: &[T]) -> usize {
+        self.find_iter(haystack).count()
+    }
+
+    /// Returns the leftmost and shortest span of the `haystack` matched by this `Pattern`.
+    ///
+    /// In contrast to [`Pattern::is_match`], the pattern doesn't need to match the whole
+    /// `haystack`. Of all matches, the one which starts first is returned and of these the
+    /// shortest. Returns `None` if there is no match in the `haystack`. All starts are tried
+    /// side by side in a single pass over the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"ERR?R*timeout", Options::default()).unwrap();
+    /// let haystack = b"[12:00] ERROR: timeout, ERROR: timeout";
+    ///
+    /// assert_eq!(pattern.find(haystack), Some(8..22));
+    /// ```
+    #[must_use]
+    pub fn find(&self, haystack: &[T]) -> Option<Range<usize>> {
+        self.find_leftmost(haystack, false)
+    }
+
+    /// Returns an iterator over all non-overlapping matches of this `Pattern` in the
+    /// `haystack`.
+    ///
+    /// Every match is the leftmost and shortest match like with [`Pattern::find`] searched
+    /// from the end of the previous match. See [`FindIter`] for the handling of empty
+    /// matches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"ERR*timeout", Options::default()).unwrap();
+    /// let matches = pattern
+    ///     .find_iter(b"ERROR timeout, ERROR: timeout, WARN")
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(matches, [0..13, 15..29]);
+    /// ```
+    pub fn find_iter<'p, 'h>(&'p self, haystack: &'h [T]) -> FindIter<'p, 'h, T> {
+        FindIter::new(BorrowedOrOwned::Borrowed(&self.program), haystack)
+    }
+
+    /// Returns the leftmost and longest span of the `haystack` matched by this `Pattern`.
+    ///
+    /// Like [`Pattern::find`] but of all matches starting at the leftmost index the longest is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"ERR?R*timeout", Options::default()).unwrap();
+    /// let haystack = b"[12:00] ERROR: timeout, ERROR: timeout";
+    ///
+    /// assert_eq!(pattern.find_longest(haystack), Some(8..38));
+    /// ```
+    #[must_use]
+    pub fn find_longest(&self, haystack: &[T]) -> Option<Range<usize>> {
+        self.find_leftmost(haystack, true)
+    }
+
+    /// Returns `true` if this `Pattern` matches the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"fo?*", Options::default().case_insensitive(true)).unwrap();
+    ///
+    /// assert_eq!(pattern.is_match(b"FOO"), true);
+    /// assert_eq!(pattern.is_match(b"FOOBAR"), true);
+    /// assert_eq!(pattern.is_match(b"FO"), false);
+    /// ```
+    #[must_use]
+    pub fn is_match(&self, haystack: &[T]) -> bool {
+        self.is_match_in(haystack)
+    }
+
+    /// The [`Options`] this `Pattern` was created with.
+    #[must_use]
+    pub const fn options(&self) -> &Options<T> {
+        &self.options
+    }
+
+    /// Returns the spans of the captures in any [`Haystack`] like [`Pattern::captures`]
+    pub(crate) fn captures_in<H>(&self, haystack: &H) -> Option<Vec<Range<usize>>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        if let Some(program) = self.program_for(haystack) {
+            return program.captures(haystack);
+        }
+
+        let mut starts = vec![0; self.tokens.len()];
+        if !self.run(haystack, &mut starts) {
+            return None;
+        }
+
+        let mut spans = Vec::new();
+        let mut t_idx = 0;
+        while t_idx < self.tokens.len() {
+            match self.tokens[t_idx] {
+                Token::Any => {
+                    let start = starts[t_idx];
+                    while t_idx + 1 < self.tokens.len()
+                        && matches!(self.tokens[t_idx + 1], Token::Any)
+                    {
+                        spans.push(start..start);
+                        t_idx += 1;
+                    }
+
+                    // The `?` directly following a `*` are matched in one go with the `*` without
+                    // recording them. However, they have to end where the next token starts.
+                    let mut num_ones = 0;
+                    while t_idx + num_ones + 1 < self.tokens.len()
+                        && matches!(self.tokens[t_idx + num_ones + 1], Token::One)
+                    {
+                        num_ones += 1;
+                    }
+                    let end = starts
+                        .get(t_idx + num_ones + 1)
+                        .map_or_else(|| haystack.end(), |end| *end);
+
+                    let mut ones_start = end;
+                    for _ in 0..num_ones {
+                        ones_start = haystack.prev(ones_start).map_or(ones_start, |(_, pos)| pos);
+                    }
+                    spans.push(start..ones_start);
+
+                    let mut h_idx = ones_start;
+                    while h_idx < end {
+                        let next = haystack.step(h_idx);
+                        spans.push(h_idx..next);
+                        h_idx = next;
+                    }
+                    t_idx += num_ones;
+                }
+                Token::Class(_) | Token::One => {
+                    let start = starts[t_idx];
+                    spans.push(start..haystack.step(start));
+                }
+                Token::Escaped(_) | Token::Literal(_) => {}
+            }
+            t_idx += 1;
+        }
+
+        Some(spans)
+    }
+
+    /// Returns `true` if this `Pattern` matches any [`Haystack`] like [`Pattern::is_match`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::VecDeque;
+    ///
+    /// use simplematch::{Chunks, Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(b"*.rs", Options::default()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     pattern.is_match_in(&Chunks::new(&[&b"src/"[..], b"lib.rs"])),
+    ///     true
+    /// );
+    /// assert_eq!(
+    ///     pattern.is_match_in(&VecDeque::from(b"lib.c".to_vec())),
+    ///     false
+    /// );
+    /// ```
+    #[must_use]
+    pub fn is_match_in<H>(&self, haystack: &H) -> bool
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        if let Some(program) = self.program_for(haystack) {
+            return program.is_match(haystack);
+        }
+        self.run(haystack, &mut ())
+    }
+
+    /// Returns `true` if this `Pattern` consists of literal and escaped characters only.
+    pub(crate) fn is_literal(&self) -> bool {
+        !self.needs_program && self.tokens.iter().all(|token| token.literal().is_some())
+    }
+
+    /// Returns the literal and escaped characters at the start of this `Pattern`.
+    pub(crate) fn literal_prefix(&self) -> Vec<T> {
+        if self.needs_program {
+            return self.program.literal_prefix().clone();
+        }
+        self.tokens.iter().map_while(Token::literal).collect()
+    }
+
+    /// Returns the literal and escaped characters at the end of this `Pattern`.
+    pub(crate) fn literal_suffix(&self) -> Vec<T> {
+        if self.needs_program {
+            return self.program.literal_suffix().clone();
+        }
+        let mut suffix = self
+            .tokens
+            .iter()
+            .rev()
+            .map_while(Token::literal)
+            .collect::<Vec<T>>();
+        suffix.reverse();
+        suffix
+    }
+
+    /// Parse the `pattern` without verifying the [`Options`]
+    pub(crate) fn parse(pattern: &[T], options: Options<T>) -> Self {
+        Self::from_lexemes(Self::lexemes(pattern, options), options)
+    }
+
+    /// Split the `pattern` into lexemes with matched braces and extended globs
+    pub(crate) fn lexemes(pattern: &[T], options: Options<T>) -> Vec<Lexeme<T>> {
+        Lexeme::resolve_groups(Self::lex(pattern, options, |_| {}))
+    }
+
+    /// Build the `Pattern` from already lexed `lexemes` with matched braces
+    ///
+    /// The [`Options`] are not verified.
+    pub(crate) fn from_lexemes(mut lexemes: Vec<Lexeme<T>>, options: Options<T>) -> Self {
+        let Options {
+            is_globstar_enabled,
+            is_leading_dot_protected,
+            path_separator,
+            ..
+        } = options;
+
+        if let (Some(separator), true) = (path_separator, is_globstar_enabled) {
+            lexemes = Lexeme::resolve_globstars(lexemes, separator);
+        }
+
+        #[cfg(feature = "normalization")]
+        let is_normalized = match options.normalization {
+            Some(normalization) => {
+                lexemes = normalization::normalize_lexemes(lexemes, normalization);
+                true
+            }
+            None => false,
+        };
+        #[cfg(not(feature = "normalization"))]
+        let is_normalized = false;
+
+        // Only patterns without any path semantics, special lexemes, normalization and
+        // characters folding to multiple characters are simple enough for the tokens
+        if path_separator.is_some()
+            || is_leading_dot_protected
+            || is_normalized
+            || (!options.case_sensitive && Lexeme::has_full_case_folding(&lexemes))
+            || lexemes
+                .iter()
+                .any(|lexeme| !matches!(lexeme, Lexeme::Token(_)))
+        {
+            return Self {
+                has_any: false,
+                min_len: 0,
+                needs_program: true,
+                options,
+                program: Program::new(lexemes, options),
+                tokens: Vec::new(),
+            };
+        }
+
+        let tokens = lexemes
+            .into_iter()
+            .filter_map(|lexeme| match lexeme {
+                Lexeme::Token(token) => Some(token),
+                Lexeme::AnySegment
+                | Lexeme::BraceClose
+                | Lexeme::BraceOpen
+                | Lexeme::BraceSeparator
+                | Lexeme::ExtglobClose
+                | Lexeme::ExtglobOpen(_)
+                | Lexeme::ExtglobSeparator
+                | Lexeme::Globstar
+                | Lexeme::GlobstarSegments => None,
+            })
+            .collect::<Vec<Token<T>>>();
+        let min_len = tokens
+            .iter()
+            .filter(|token| !matches!(token, Token::Any))
+            .count();
+
+        Self {
+            has_any: min_len != tokens.len(),
+            min_len,
+            needs_program: false,
+            options,
+            program: Program::new(tokens.iter().cloned().map(Lexeme::Token).collect(), options),
+            tokens,
+        }
+    }
+
+    /// Split the `pattern` into tokens and the special lexemes of the [`Program`]
+    ///
+    /// The braces are not checked for a matching counterpart yet. The `on_lexeme` callback
+    /// receives the index of the `pattern` at which the next lexeme starts.
+    pub(crate) fn lex<F>(pattern: &[T], options: Options<T>, mut on_lexeme: F) -> Vec<Lexeme<T>>
+    where
+        F: FnMut(usize),
+    {
+        let Options {
+            class_open,
+            is_classes_enabled,
+            is_escape_enabled,
+            is_extglob_enabled,
+            wildcard_any,
+            wildcard_any_segment,
+            wildcard_escape,
+            wildcard_one,
+            ..
+        } = options;
+
+        let is_special = |token: T| {
+            token == wildcard_any
+                || token == wildcard_one
+                || token == wildcard_escape
+                || (is_classes_enabled && token == class_open)
+                || Lexeme::punctuation(token, &options).is_some()
+                || (is_extglob_enabled
+                    && (token == T::DEFAULT_EXTGLOB_OPEN
+                        || token == T::DEFAULT_EXTGLOB_AT
+                        || token == T::DEFAULT_EXTGLOB_NOT
+                        || token == T::DEFAULT_EXTGLOB_PLUS))
+                || Some(token) == wildcard_any_segment
+        };
+
+        let mut lexemes = Vec::with_capacity(pattern.len());
+        let mut invalid_class_idx = usize::MAX;
+        let mut p_idx = 0;
+
+        while p_idx < pattern.len() {
+            on_lexeme(p_idx);
+            if let Some(extglob) = is_extglob_enabled
+                .then(|| Extglob::parse(p_idx, pattern, &options))
+                .flatten()
+            {
+                lexemes.push(Lexeme::ExtglobOpen(extglob));
+                p_idx += 2;
+                continue;
+            }
+
+            let token = match pattern[p_idx] {
+                c if c == wildcard_any => Token::Any,
+                c if Some(c) == wildcard_any_segment => {
+                    lexemes.push(Lexeme::AnySegment);
+                    p_idx += 1;
+                    continue;
+                }
+                c if c == wildcard_one => Token::One,
+                // Like in `dowild_with`, an escape character as last character can only stand for
+                // itself and escaping a non-special character matches the escape character
+                // literally.
+                c if is_escape_enabled && c == wildcard_escape && p_idx + 1 < pattern.len() => {
+                    let next_c = pattern[p_idx + 1];
+                    if is_special(next_c) {
+                        p_idx += 1;
+                        Token::Escaped(next_c)
+                    } else {
+                        Token::Escaped(c)
+                    }
+                }
+                // If a class is invalid, all classes with a higher index are invalid, too. There's
+                // no need to parse them.
+                c if is_classes_enabled
+                    && c == class_open
+                    && p_idx < invalid_class_idx
+                    && p_idx + 1 < pattern.len() =>
+                {
+                    let class = CharacterClass::parse(p_idx, pattern, &options);
+                    let len = class.len();
+                    if let Some(class) = class.class {
+                        p_idx += len - 1;
+                        Token::Class(class)
+                    } else {
+                        invalid_class_idx = p_idx;
+                        Token::Literal(c)
+                    }
+                }
+                c => match Lexeme::punctuation(c, &options) {
+                    Some(lexeme) => {
+                        lexemes.push(lexeme);
+                        p_idx += 1;
+                        continue;
+                    }
+                    None => Token::Literal(c),
+                },
+            };
+
+            lexemes.push(Lexeme::Token(token));
+            p_idx += 1;
+        }
+
+        lexemes
+    }
+
+    /// Returns the compiled program of this `Pattern`.
+    pub(crate) fn into_program(self) -> Program<T> {
+        self.program
+    }
+
+    /// Returns the compiled program which matches the `haystack` if the tokens can't.
+    ///
+    /// Only the program matches a haystack with clusters of multiple tokens.
+    #[inline]
+    fn program_for<H>(&self, _haystack: &H) -> Option<&Program<T>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        (self.needs_program || H::HAS_CLUSTERS).then_some(&self.program)
+    }
+
+    /// Find the leftmost match in the `haystack` in a single pass with the compiled program
+    #[inline]
+    pub(crate) fn find_leftmost<H>(&self, haystack: &H, is_longest: bool) -> Option<Range<usize>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        self.program.find(haystack, 0, is_longest)
+    }
+
+    /// Match the `haystack` with the case sensitivity of the [`Options`]
+    #[inline]
+    fn run<H, R>(&self, haystack: &H, recorder: &mut R) -> bool
+    where
+        H: Haystack<T> + ?Sized,
+        R: Recorder,
+    {
+        if self.options.case_sensitive {
+            self.run_worker(
+                haystack,
+                recorder,
+                T::match_one_case_sensitive,
+                T::match_range_case_sensitive,
+            )
+        } else {
+            self.run_worker(
+                haystack,
+                recorder,
+                T::match_one_case_insensitive,
+                T::match_range_case_insensitive,
+            )
+        }
+    }
+
+    /// The same algorithm as in [`dowild_with`](crate::dowild_with) but on the parsed tokens
+    ///
+    /// The `?` directly following a `*` are not recorded.
+    #[inline]
+    fn run_worker<F, G, H, R>(
+        &self,
+        haystack: &H,
+        recorder: &mut R,
+        match_one: F,
+        match_range: G,
+    ) -> bool
+    where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+        H: Haystack<T> + ?Sized,
+        R: Recorder,
+    {
+        // Every token except `*` needs at least one position. Only the positions of an indexed
+        // haystack correspond exactly to its characters.
+        let len = haystack.end();
+        if len < self.min_len || (H::IS_INDEXED && !self.has_any && len != self.min_len) {
+            return false;
+        }
+
+        let tokens = self.tokens.as_slice();
+
+        let mut t_idx = 0;
+        let mut h_idx = 0;
+
+        let mut next_t_idx = 0;
+        let mut next_h_idx = 0;
+
+        let mut has_seen_wildcard_any = false;
+        while t_idx < tokens.len() || h_idx < haystack.end() {
+            if t_idx < tokens.len() {
+                recorder.record(t_idx, h_idx);
+                match &tokens[t_idx] {
+                    Token::Any => {
+                        has_seen_wildcard_any = true;
+                        t_idx += 1;
+
+                        while t_idx < tokens.len() && matches!(tokens[t_idx], Token::Any) {
+                            recorder.record(t_idx, h_idx);
+                            t_idx += 1;
+                        }
+                        if t_idx >= tokens.len() {
+                            return true;
+                        }
+
+                        match tokens[t_idx] {
+                            Token::One => {
+                                while let Some((_, next)) = haystack.next(h_idx) {
+                                    t_idx += 1;
+                                    h_idx = next;
+                                    if !(t_idx < tokens.len() && matches!(tokens[t_idx], Token::One))
+                                    {
+                                        break;
+                                    }
+                                }
+                                if t_idx >= tokens.len() {
+                                    return true;
+                                }
+                            }
+                            Token::Literal(_) | Token::Escaped(_) => {
+                                h_idx = tokens[t_idx].skip(haystack, h_idx, match_one);
+                                if h_idx >= haystack.end() {
+                                    return false;
+                                }
+                            }
+                            Token::Any | Token::Class(_) => {}
+                        }
+
+                        next_t_idx = t_idx;
+                        next_h_idx = h_idx;
+                        continue;
+                    }
+                    Token::One => {
+                        if let Some((_, next)) = haystack.next(h_idx) {
+                            t_idx += 1;
+                            h_idx = next;
+                            continue;
+                        }
+                    }
+                    Token::Escaped(c) => {
+                        if let Some((_, next)) = haystack.next(h_idx).filter(|(h, _)| h == c) {
+                            t_idx += 1;
+                            h_idx = next;
+                            continue;
+                        }
+                    }
+                    Token::Class(class) => {
+                        if let Some((_, next)) = haystack
+                            .next(h_idx)
+                            .filter(|(h, _)| class.is_match(*h, match_one, match_range))
+                        {
+                            t_idx += 1;
+                            h_idx = next;
+                            continue;
+                        }
+                    }
+                    Token::Literal(c) => {
+                        if let Some((_, next)) =
+                            haystack.next(h_idx).filter(|(h, _)| match_one(*h, *c))
+                        {
+                            t_idx += 1;
+                            h_idx = next;
+                            continue;
+                        }
+                    }
+                }
+            }
+            if has_seen_wildcard_any && next_h_idx < haystack.end() {
+                t_idx = next_t_idx;
+                next_h_idx = haystack.step(next_h_idx);
+
+                next_h_idx = tokens[t_idx].skip(haystack, next_h_idx, match_one);
+                h_idx = next_h_idx;
+                continue;
+            }
+
+            return false;
+        }
+
+        true
+    }
+}
+
+impl<T> Token<T>
+where
+    T: Wildcard,
+{
+    /// Returns `true` if this token matches the single character `c`.
+    ///
+    /// A `*` never matches a single character.
+    #[inline]
+    pub(crate) fn is_match_one<F, G>(&self, c: T, match_one: F, match_range: G) -> bool
+    where
+        T: Ord,
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+    {
+        match self {
+            Self::Any => false,
+            Self::Class(class) => class.is_match(c, match_one, match_range),
+            Self::Escaped(token) => c == *token,
+            Self::Literal(token) => match_one(c, *token),
+            Self::One => true,
+        }
+    }
+
+    /// Returns the character of a literal or escaped token and `None` for any other token.
+    #[inline]
+    pub(crate) const fn literal(&self) -> Option<T> {
+        match self {
+            Self::Escaped(c) | Self::Literal(c) => Some(*c),
+            Self::Any | Self::Class(_) | Self::One => None,
+        }
+    }
+
+    /// Returns the index of the first character in the `haystack` starting at `h_idx` which
+    /// can be matched by this literal token.
+    ///
+    /// This is the optimization of [`dowild_with`](crate::dowild_with) to advance the haystack
+    /// after a `wildcard_any` to the first possible match. Other tokens don't skip anything.
+    #[inline]
+    fn skip<F, H>(&self, haystack: &H, mut h_idx: usize, match_one: F) -> usize
+    where
+        F: Fn(T, T) -> bool,
+        H: Haystack<T> + ?Sized,
+    {
+        match self {
+            Self::Literal(c) => {
+                while let Some((h, next)) = haystack.next(h_idx) {
+                    if match_one(h, *c) {
+                        break;
+                    }
+                    h_idx = next;
+                }
+            }
+            Self::Escaped(c) => {
+                while let Some((h, next)) = haystack.next(h_idx) {
+                    if h == *c {
+                        break;
+                    }
+                    h_idx = next;
+                }
+            }
+            Self::Any | Self::Class(_) | Self::One => {}
+        }
+        h_idx
+    }
+}
+
+impl Recorder for () {
+    #[inline]
+    fn record(&mut self, _: usize, _: usize) {}
+}
+
+impl Recorder for Vec<usize> {
+    #[inline]
+    fn record(&mut self, t_idx: usize, h_idx: usize) {
+        self[t_idx] = h_idx;
+    }
+}
@@ -0,0 +1,1726 @@
+//! The compiled program of a [`Pattern`](crate::Pattern) with alternations
+//!
+//! Patterns which consist of [`Token`]s only are matched directly by the [`Pattern`]. More
+//! complex patterns like brace alternations are compiled into a small program of
+//! instructions similar to a regular expression. Instead of expanding the alternatives into
+//! all possible patterns, the program runs the alternatives side by side.
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BinaryHeap, VecDeque};
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::ops::Range;
+use core::{iter, mem};
+#[cfg(feature = "std")]
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::haystack::Haystack;
+#[cfg(feature = "normalization")]
+use crate::normalization::Normalized;
+use crate::pattern::Token;
+use crate::{fold_case_full, is_normalized, Class, Options, Wildcard};
+
+/// A single instruction of a [`Program`]
+#[derive(Debug, Clone)]
+enum Inst<T> {
+    /// Consume any single character of the haystack including the path separator
+    Any,
+    /// Continue at the instruction with this index
+    Jump(usize),
+    /// The whole pattern matched
+    Match,
+    /// Consume any span of the haystack which the negated program with this index doesn't
+    /// match
+    Not(usize),
+    /// Store the haystack index in the capture slot with this index
+    Save(usize),
+    /// Continue at both instructions preferring the first one
+    Split(usize, usize),
+    /// Consume a single character of the haystack matched by this token. A `*` is never a
+    /// single instruction but compiled to a loop.
+    Token(Token<T>),
+}
+
+/// The operator of an extglob like `+(...)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extglob {
+    /// `!(...)`, anything except one of the sub-patterns
+    Not,
+    /// `@(...)`, exactly one of the sub-patterns
+    One,
+    /// `+(...)`, one or more of the sub-patterns
+    OneOrMore,
+    /// `*(...)`, zero or more of the sub-patterns
+    ZeroOrMore,
+    /// `?(...)`, zero or one of the sub-patterns
+    ZeroOrOne,
+}
+
+/// A step of the backtracking in [`Program::captures`]
+enum Job {
+    Explore(usize, usize),
+    Restore(usize, usize),
+}
+
+/// A lexical unit of a pattern with brace alternations or path semantics
+///
+/// The braces are only valid if they have a matching counterpart. Unmatched braces and
+/// separators outside of braces are [`Lexeme::Token`]s.
+#[derive(Debug, Clone)]
+pub enum Lexeme<T> {
+    /// The `wildcard_any_segment` which doesn't match the path separator
+    AnySegment,
+    BraceClose,
+    BraceOpen,
+    BraceSeparator,
+    /// The `)` which closes an extglob
+    ExtglobClose,
+    /// An extglob operator followed by `(`
+    ExtglobOpen(Extglob),
+    /// The `|` which separates the sub-patterns of an extglob
+    ExtglobSeparator,
+    /// A `**` segment at the end of the pattern or an alternative
+    Globstar,
+    /// A `**` segment followed by the path separator
+    GlobstarSegments,
+    Token(Token<T>),
+}
+
+/// A pattern compiled to a program of instructions
+#[derive(Debug, Clone)]
+pub struct Program<T>
+where
+    T: Wildcard,
+{
+    insts: Vec<Inst<T>>,
+    literal_prefix: Vec<T>,
+    literal_suffix: Vec<T>,
+    /// The programs of the `!(...)` extglobs
+    negations: Vec<Self>,
+    num_slots: usize,
+    options: Options<T>,
+}
+
+/// The state of a [`Program`] matching a haystack which arrives in chunks
+///
+/// The state consists of the active instructions and the last token, so it doesn't grow with
+/// the haystack.
+#[derive(Debug, Clone)]
+pub struct Stream<T> {
+    current: Threads,
+    /// `true` for the instructions of a `*` which matches whatever follows
+    is_universal: Vec<bool>,
+    next: Threads,
+    /// The last token of the haystack so far
+    prev: Option<T>,
+    stack: Vec<usize>,
+    /// The verdict if it is certain already
+    verdict: Option<bool>,
+}
+
+/// The state of a [`Program`] advancing through a haystack, see [`Program::step`]
+#[derive(Debug, Clone)]
+struct Scan<T> {
+    cluster: Cluster<T>,
+    current: Threads,
+    /// The ends of the spans a `!(...)` consumes, reused for every `!(...)`
+    ends: Vec<usize>,
+    /// The haystack index of the next step or `None` if the scan is finished
+    h_idx: Option<usize>,
+    /// `true` if a match can start at any haystack index from the `start` on
+    is_unanchored: bool,
+    next: Threads,
+    /// The instructions following a `!(...)` which continue at a later haystack index with
+    /// the start of their match
+    pending: BinaryHeap<Reverse<(usize, usize, usize)>>,
+    stack: Vec<usize>,
+    start: usize,
+}
+
+/// The state of a [`Program`] finding all non-overlapping matches in a haystack in a single
+/// scan
+///
+/// A match is found when its end is reached, but it is only certain once no match from an
+/// earlier start is possible anymore. Until then, it is kept as a tentative match while the
+/// scan continues with the starts which could replace it and the starts after it.
+#[derive(Debug, Clone)]
+pub struct Search<T> {
+    matches: NonOverlapping,
+    scan: Scan<T>,
+}
+
+/// The [`Starts`] of a [`Search`] and the matches it found
+#[derive(Debug, Clone)]
+struct NonOverlapping {
+    /// The matches start at this haystack index or later
+    from: usize,
+    /// The end of the last returned match
+    last_end: Option<usize>,
+    /// The matches found since the last returned match in ascending order, each with the
+    /// haystack index at which the matches following it start. A match from an earlier start
+    /// replaces a tentative match and all matches following it.
+    tentative: VecDeque<(Range<usize>, usize)>,
+}
+
+/// The [`Starts`] of a run which stops all matches from a haystack index on
+struct Limit<M> {
+    /// The matches which start at this haystack index or later are stopped
+    limit: usize,
+    on_match: M,
+}
+
+/// The set of instructions which are active at the same haystack index
+#[derive(Debug, Clone)]
+struct Threads {
+    pcs: Vec<usize>,
+    seen: Vec<bool>,
+    /// The earliest haystack index from which each active instruction was reached
+    starts: Vec<usize>,
+}
+
+/// A brace alternation or extglob which is not closed yet during the compilation
+struct Frame {
+    /// The operator if this is an extglob
+    extglob: Option<Extglob>,
+    /// The indices of the jumps at the end of each alternative to patch with the end
+    jumps: Vec<usize>,
+    /// The index of the split instruction in front of the current alternative
+    split: usize,
+    /// The index of the first instruction of the group
+    start: usize,
+}
+
+/// The cluster of a haystack with clusters at the current haystack index
+#[derive(Debug, Clone)]
+struct Cluster<T> {
+    /// The cluster as a single token if there is one
+    single: Option<T>,
+    /// The positions of the cluster in the haystack
+    span: Range<usize>,
+    /// The instructions and token indices of the literal matching in
+    /// [`Program::match_cluster`]
+    stack: Vec<(usize, usize)>,
+    /// The tokens of the cluster matched by the literals of the pattern
+    tokens: Vec<T>,
+}
+
+/// Decides which matches a scan of a [`Program`] still looks for
+trait Starts {
+    /// Returns `true` if matches from the haystack index `start` are still wanted.
+    fn is_wanted(&self, start: usize) -> bool;
+
+    /// Receives the match from the haystack index `start` to `end`.
+    ///
+    /// Returns `true` if the match stopped a start in front of another start which is still
+    /// wanted, since the instructions of the stopped start could block the other one.
+    fn on_match(&mut self, start: usize, end: usize) -> bool;
+}
+
+impl Extglob {
+    /// Parse the extglob operator at `index` of the `pattern` if it is followed by `(`
+    pub(crate) fn parse<T>(index: usize, pattern: &[T], options: &Options<T>) -> Option<Self>
+    where
+        T: Wildcard,
+    {
+        if pattern.get(index + 1) != Some(&T::DEFAULT_EXTGLOB_OPEN) {
+            return None;
+        }
+
+        match pattern[index] {
+            c if c == options.wildcard_one => Some(Self::ZeroOrOne),
+            c if c == options.wildcard_any => Some(Self::ZeroOrMore),
+            c if c == T::DEFAULT_EXTGLOB_PLUS => Some(Self::OneOrMore),
+            c if c == T::DEFAULT_EXTGLOB_AT => Some(Self::One),
+            c if c == T::DEFAULT_EXTGLOB_NOT => Some(Self::Not),
+            _ => None,
+        }
+    }
+
+    /// Returns the token of the operator if the extglob has no matching `)`
+    const fn into_token<T>(self) -> Token<T>
+    where
+        T: Wildcard,
+    {
+        match self {
+            Self::Not => Token::Literal(T::DEFAULT_EXTGLOB_NOT),
+            Self::One => Token::Literal(T::DEFAULT_EXTGLOB_AT),
+            Self::OneOrMore => Token::Literal(T::DEFAULT_EXTGLOB_PLUS),
+            Self::ZeroOrMore => Token::Any,
+            Self::ZeroOrOne => Token::One,
+        }
+    }
+}
+
+impl<T> Lexeme<T>
+where
+    T: Wildcard,
+{
+    /// Returns the lexeme of a brace, a `)` or a `|` if the `token` is one of them and they
+    /// are enabled in the `options`
+    pub(crate) fn punctuation(token: T, options: &Options<T>) -> Option<Self> {
+        let Options {
+            is_braces_enabled,
+            is_extglob_enabled,
+            ..
+        } = *options;
+
+        match token {
+            c if is_braces_enabled && c == T::DEFAULT_BRACE_OPEN => Some(Self::BraceOpen),
+            c if is_braces_enabled && c == T::DEFAULT_BRACE_CLOSE => Some(Self::BraceClose),
+            c if is_braces_enabled && c == T::DEFAULT_BRACE_SEPARATOR => Some(Self::BraceSeparator),
+            c if is_extglob_enabled && c == T::DEFAULT_EXTGLOB_CLOSE => Some(Self::ExtglobClose),
+            c if is_extglob_enabled && c == T::DEFAULT_EXTGLOB_SEPARATOR => {
+                Some(Self::ExtglobSeparator)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns for every lexeme if it is a brace or extglob with a matching counterpart.
+    ///
+    /// A closing brace or parenthesis closes the innermost open group of its kind and groups
+    /// opened in between stay unmatched.
+    pub(crate) fn match_groups(lexemes: &[Self]) -> Vec<bool> {
+        let is_brace = |lexeme: &Self| matches!(lexeme, Self::BraceOpen | Self::BraceClose);
+
+        let mut is_matched = vec![false; lexemes.len()];
+        let mut opens: Vec<usize> = Vec::new();
+        for (index, lexeme) in lexemes.iter().enumerate() {
+            match lexeme {
+                Self::BraceOpen | Self::ExtglobOpen(_) => opens.push(index),
+                Self::BraceClose | Self::ExtglobClose => {
+                    let position = opens
+                        .iter()
+                        .rposition(|open| is_brace(&lexemes[*open]) == is_brace(lexeme));
+                    if let Some(position) = position {
+                        is_matched[opens[position]] = true;
+                        is_matched[index] = true;
+                        opens.truncate(position);
+                    }
+                }
+                Self::AnySegment
+                | Self::BraceSeparator
+                | Self::ExtglobSeparator
+                | Self::Globstar
+                | Self::GlobstarSegments
+                | Self::Token(_) => {}
+            }
+        }
+
+        is_matched
+    }
+
+    /// Turn all braces and extglobs without a matching counterpart and all separators
+    /// outside of them into literal tokens.
+    ///
+    /// The counterparts are found with [`Lexeme::match_groups`]. A separator belongs to the
+    /// innermost group if it is of the same kind.
+    pub(crate) fn resolve_groups(lexemes: Vec<Self>) -> Vec<Self> {
+        let is_brace = |lexeme: &Self| matches!(lexeme, Self::BraceOpen | Self::BraceClose);
+        let is_valid = Self::match_groups(&lexemes);
+
+        // The kinds of the open groups, `true` for braces
+        let mut groups = Vec::new();
+        let mut resolved = Vec::with_capacity(lexemes.len());
+        for (lexeme, is_valid) in lexemes.into_iter().zip(is_valid) {
+            let literal = match lexeme {
+                Self::BraceOpen | Self::ExtglobOpen(_) if is_valid => {
+                    groups.push(is_brace(&lexeme));
+                    resolved.push(lexeme);
+                    continue;
+                }
+                Self::BraceClose | Self::ExtglobClose if is_valid => {
+                    groups.pop();
+                    resolved.push(lexeme);
+                    continue;
+                }
+                Self::BraceSeparator if groups.last() == Some(&true) => {
+                    resolved.push(lexeme);
+                    continue;
+                }
+                Self::ExtglobSeparator if groups.last() == Some(&false) => {
+                    resolved.push(lexeme);
+                    continue;
+                }
+                Self::BraceClose => T::DEFAULT_BRACE_CLOSE,
+                Self::BraceOpen => T::DEFAULT_BRACE_OPEN,
+                Self::BraceSeparator => T::DEFAULT_BRACE_SEPARATOR,
+                Self::ExtglobClose => T::DEFAULT_EXTGLOB_CLOSE,
+                Self::ExtglobOpen(extglob) => {
+                    resolved.push(Self::Token(extglob.into_token()));
+                    T::DEFAULT_EXTGLOB_OPEN
+                }
+                Self::ExtglobSeparator => T::DEFAULT_EXTGLOB_SEPARATOR,
+                Self::AnySegment | Self::Globstar | Self::GlobstarSegments | Self::Token(_) => {
+                    resolved.push(lexeme);
+                    continue;
+                }
+            };
+            resolved.push(Self::Token(Token::Literal(literal)));
+        }
+
+        resolved
+    }
+
+    /// Returns `true` if a literal of the `lexemes` or a character of the haystack matching a
+    /// run of literals can fold to multiple characters.
+    ///
+    /// Only the [`Program`] can match the full case folding like `ß` for `ss`.
+    pub(crate) fn has_full_case_folding(lexemes: &[Self]) -> bool
+    where
+        T: Ord,
+    {
+        if T::FULL_CASE_FOLDING.is_empty() {
+            return false;
+        }
+
+        let contains_folding = |run: &[T]| {
+            T::FULL_CASE_FOLDING
+                .iter()
+                .any(|(_, folded)| run.windows(folded.len()).any(|window| window == *folded))
+        };
+
+        let mut run = Vec::new();
+        for lexeme in lexemes {
+            if let Self::Token(Token::Literal(c)) = lexeme {
+                if fold_case_full(*c).is_some() {
+                    return true;
+                }
+                run.push(T::fold_case(*c));
+            } else if contains_folding(&run) {
+                return true;
+            } else {
+                run.clear();
+            }
+        }
+
+        contains_folding(&run)
+    }
+
+    /// Replace every run of two or more `*` which form a whole path segment with a globstar.
+    ///
+    /// A segment starts at the start of the pattern or after a `separator` and ends
+    /// accordingly. At the start or the end of an alternative, this depends on the text in
+    /// front of or after the whole group. A following `separator` is part of the globstar.
+    pub(crate) fn resolve_globstars(lexemes: Vec<Self>, separator: T) -> Vec<Self> {
+        let is_separator =
+            |lexeme: &Self| matches!(lexeme, Self::Token(Token::Literal(c)) if *c == separator);
+        let (is_segment_start, is_segment_end) = Self::segment_bounds(&lexemes, is_separator);
+
+        let mut resolved: Vec<Self> = Vec::with_capacity(lexemes.len());
+        let mut lexemes = lexemes.into_iter().enumerate().peekable();
+        while let Some((index, lexeme)) = lexemes.next() {
+            if !(is_segment_start[index] && matches!(lexeme, Self::Token(Token::Any))) {
+                resolved.push(lexeme);
+                continue;
+            }
+
+            let mut last = index;
+            while let Some((next, _)) =
+                lexemes.next_if(|(_, lexeme)| matches!(lexeme, Self::Token(Token::Any)))
+            {
+                last = next;
+            }
+
+            match lexemes.peek() {
+                _ if last == index => resolved.push(lexeme),
+                Some((_, next)) if is_separator(next) => {
+                    lexemes.next();
+                    resolved.push(Self::GlobstarSegments);
+                }
+                _ if is_segment_end[last] => resolved.push(Self::Globstar),
+                _ => resolved.extend(iter::repeat(Self::Token(Token::Any)).take(last - index + 1)),
+            }
+        }
+
+        resolved
+    }
+
+    /// Returns for every lexeme if a path segment can start in front of it and if a path
+    /// segment can end after it.
+    ///
+    /// The start and the end of an alternative are the start and the end of a segment only
+    /// if the whole group is.
+    fn segment_bounds<F>(lexemes: &[Self], is_separator: F) -> (Vec<bool>, Vec<bool>)
+    where
+        F: Fn(&Self) -> bool,
+    {
+        let mut is_start: Vec<bool> = Vec::with_capacity(lexemes.len());
+        let mut opens = Vec::new();
+        for (index, lexeme) in lexemes.iter().enumerate() {
+            let value = match index.checked_sub(1).map(|prev| &lexemes[prev]) {
+                None => true,
+                Some(prev) if is_separator(prev) => true,
+                Some(
+                    Self::BraceOpen
+                    | Self::BraceSeparator
+                    | Self::ExtglobOpen(_)
+                    | Self::ExtglobSeparator,
+                ) => opens.last().map_or(false, |open: &usize| is_start[*open]),
+                Some(_) => false,
+            };
+            is_start.push(value);
+            match lexeme {
+                Self::BraceOpen | Self::ExtglobOpen(_) => opens.push(index),
+                Self::BraceClose | Self::ExtglobClose => {
+                    opens.pop();
+                }
+                _ => {}
+            }
+        }
+
+        let mut is_end = vec![false; lexemes.len()];
+        let mut closes = Vec::new();
+        for (index, lexeme) in lexemes.iter().enumerate().rev() {
+            is_end[index] = match lexemes.get(index + 1) {
+                None => true,
+                Some(next) if is_separator(next) => true,
+                Some(
+                    Self::BraceClose
+                    | Self::BraceSeparator
+                    | Self::ExtglobClose
+                    | Self::ExtglobSeparator,
+                ) => closes.last().map_or(false, |close: &usize| is_end[*close]),
+                Some(_) => false,
+            };
+            match lexeme {
+                Self::BraceClose | Self::ExtglobClose => closes.push(index),
+                Self::BraceOpen | Self::ExtglobOpen(_) => {
+                    closes.pop();
+                }
+                _ => {}
+            }
+        }
+
+        (is_start, is_end)
+    }
+}
+
+impl<T> Program<T>
+where
+    T: Wildcard + Ord,
+{
+    /// Compile the `lexemes` of a pattern.
+    ///
+    /// Every `*`, `?`, character class, globstar and outermost brace alternation gets a pair
+    /// of capture slots. The tokens inside of an alternation don't capture anything, so
+    /// the number of captures doesn't depend on the alternative which matched.
+    pub(crate) fn new(lexemes: Vec<Lexeme<T>>, options: Options<T>) -> Self {
+        // A normalized haystack can spell the literals differently, so they can't select
+        // the candidates of a `WildSet`
+        let literal = |lexeme: &Lexeme<T>| match lexeme {
+            Lexeme::Token(token) if !is_normalized(&options) => token.literal(),
+            _ => None,
+        };
+        let literal_prefix = lexemes.iter().map_while(literal).collect::<Vec<T>>();
+        let mut literal_suffix = lexemes.iter().rev().map_while(literal).collect::<Vec<T>>();
+        literal_suffix.reverse();
+
+        // Without a path separator or with a `wildcard_any_segment`, the `*` crosses segments
+        let any = if options.path_separator.is_none() || options.wildcard_any_segment.is_some() {
+            Inst::Any
+        } else {
+            Inst::Token(Token::One)
+        };
+
+        let mut insts = Vec::with_capacity(lexemes.len() + 1);
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut negations = Vec::new();
+        let mut num_slots = 0;
+
+        let mut lexemes = lexemes.into_iter();
+        while let Some(lexeme) = lexemes.next() {
+            let is_capture = frames.is_empty()
+                && matches!(
+                    lexeme,
+                    Lexeme::AnySegment
+                        | Lexeme::BraceOpen
+                        | Lexeme::ExtglobOpen(_)
+                        | Lexeme::Globstar
+                        | Lexeme::GlobstarSegments
+                        | Lexeme::Token(Token::Any | Token::Class(_) | Token::One)
+                );
+            if is_capture {
+                insts.push(Inst::Save(num_slots));
+            }
+
+            match lexeme {
+                Lexeme::AnySegment => Self::push_loop(&mut insts, Inst::Token(Token::One)),
+                Lexeme::Token(Token::Any) => Self::push_loop(&mut insts, any.clone()),
+                Lexeme::Globstar => Self::push_loop(&mut insts, Inst::Any),
+                Lexeme::GlobstarSegments => {
+                    // Zero or more segments each followed by a separator
+                    let start = insts.len();
+                    insts.push(Inst::Jump(0));
+                    Self::push_loop(&mut insts, Inst::Token(Token::One));
+                    if let Some(separator) = options.path_separator {
+                        insts.push(Inst::Token(Token::Literal(separator)));
+                    }
+                    insts.push(Inst::Jump(start));
+                    insts[start] = Inst::Split(insts.len(), start + 1);
+                }
+                // Case-insensitive, a literal like `ß` is matched by its full case folding `ss`
+                Lexeme::Token(Token::Literal(c)) => {
+                    match fold_case_full(c).filter(|_| !options.case_sensitive) {
+                        Some(folded) => insts.extend(
+                            folded
+                                .iter()
+                                .map(|folded_c| Inst::Token(Token::Literal(*folded_c))),
+                        ),
+                        None => insts.push(Inst::Token(Token::Literal(c))),
+                    }
+                }
+                Lexeme::Token(token) => insts.push(Inst::Token(token)),
+                Lexeme::BraceOpen => Self::open_group(&mut insts, &mut frames, None),
+                Lexeme::ExtglobOpen(Extglob::Not) => {
+                    insts.push(Inst::Not(negations.len()));
+                    negations.push(Self::new_negation(&mut lexemes, options));
+                }
+                Lexeme::ExtglobOpen(extglob) => {
+                    Self::open_group(&mut insts, &mut frames, Some(extglob));
+                }
+                Lexeme::BraceSeparator | Lexeme::ExtglobSeparator => {
+                    if let Some(frame) = frames.last_mut() {
+                        Self::separate_group(&mut insts, frame);
+                    }
+                }
+                Lexeme::BraceClose | Lexeme::ExtglobClose => {
+                    if let Some(frame) = frames.pop() {
+                        Self::close_group(&mut insts, frame);
+
+                        if frames.is_empty() {
+                            insts.push(Inst::Save(num_slots + 1));
+                            num_slots += 2;
+                        }
+                    }
+                }
+            }
+
+            // The capture of an alternation which was just opened ends with its closing brace
+            if is_capture && frames.is_empty() {
+                insts.push(Inst::Save(num_slots + 1));
+                num_slots += 2;
+            }
+        }
+
+        insts.push(Inst::Match);
+
+        Self {
+            insts,
+            literal_prefix,
+            literal_suffix,
+            negations,
+            num_slots,
+            options,
+        }
+    }
+
+    /// Compile the sub-patterns of a `!(...)` up to its closing `)` into a program of its own
+    ///
+    /// The program matches exactly one of the sub-patterns like `@(...)`.
+    fn new_negation<I>(lexemes: &mut I, options: Options<T>) -> Self
+    where
+        I: Iterator<Item = Lexeme<T>>,
+    {
+        let mut inner = vec![Lexeme::ExtglobOpen(Extglob::One)];
+        let mut depth = 0_usize;
+        for lexeme in lexemes {
+            match lexeme {
+                Lexeme::BraceOpen | Lexeme::ExtglobOpen(_) => depth += 1,
+                Lexeme::ExtglobClose if depth == 0 => break,
+                Lexeme::BraceClose | Lexeme::ExtglobClose => depth -= 1,
+                _ => {}
+            }
+            inner.push(lexeme);
+        }
+        inner.push(Lexeme::ExtglobClose);
+
+        Self::new(inner, options)
+    }
+
+    /// Close the brace alternation or extglob of the `frame` and patch its placeholders.
+    ///
+    /// The repetitions of an extglob are lazy like `*` and prefer to leave the loop.
+    fn close_group(insts: &mut Vec<Inst<T>>, frame: Frame) {
+        // The last alternative has nothing left to split to
+        insts[frame.split] = Inst::Jump(frame.split + 1);
+        let end = insts.len();
+        for jump in frame.jumps {
+            insts[jump] = Inst::Jump(end);
+        }
+
+        let start = frame.start;
+        match frame.extglob {
+            None => {}
+            Some(Extglob::Not | Extglob::One) => insts[start] = Inst::Jump(start + 1),
+            Some(Extglob::ZeroOrOne) => insts[start] = Inst::Split(end, start + 1),
+            Some(Extglob::ZeroOrMore) => {
+                insts.push(Inst::Jump(start));
+                insts[start] = Inst::Split(end + 1, start + 1);
+            }
+            Some(Extglob::OneOrMore) => {
+                insts.push(Inst::Split(end + 1, start + 1));
+                insts[start] = Inst::Jump(start + 1);
+            }
+        }
+    }
+
+    /// Open a brace alternation or an extglob.
+    ///
+    /// The capture of the group ends with its closing lexeme. The placeholders are patched by
+    /// the separators and the closing lexeme.
+    fn open_group(insts: &mut Vec<Inst<T>>, frames: &mut Vec<Frame>, extglob: Option<Extglob>) {
+        let start = insts.len();
+        if extglob.is_some() {
+            insts.push(Inst::Jump(0));
+        }
+        frames.push(Frame {
+            extglob,
+            jumps: Vec::new(),
+            split: insts.len(),
+            start,
+        });
+        insts.push(Inst::Jump(0));
+    }
+
+    /// Start the next alternative of the group of the `frame`.
+    fn separate_group(insts: &mut Vec<Inst<T>>, frame: &mut Frame) {
+        frame.jumps.push(insts.len());
+        insts.push(Inst::Jump(0));
+
+        let next = insts.len();
+        insts[frame.split] = Inst::Split(frame.split + 1, next);
+        frame.split = next;
+        insts.push(Inst::Jump(0));
+    }
+
+    /// Returns `true` if the consuming instruction `inst` matches the character `c` of the
+    /// haystack.
+    ///
+    /// The path separator is only matched by the tokens which name a character explicitly. A
+    /// protected leading dot, see [`Program::is_leading_dot`], is only matched by a literal
+    /// dot.
+    #[inline]
+    fn is_match_one<F, G>(
+        &self,
+        inst: &Inst<T>,
+        c: T,
+        is_leading_dot: bool,
+        match_one: F,
+        match_range: G,
+    ) -> bool
+    where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+    {
+        match inst {
+            Inst::Token(token @ (Token::Escaped(_) | Token::Literal(_))) => {
+                token.is_match_one(c, match_one, match_range)
+            }
+            _ if is_leading_dot => false,
+            Inst::Token(Token::Class(Class::Negative(_)) | Token::One)
+                if Some(c) == self.options.path_separator =>
+            {
+                false
+            }
+            Inst::Token(token) => token.is_match_one(c, match_one, match_range),
+            Inst::Any => true,
+            Inst::Jump(_) | Inst::Match | Inst::Not(_) | Inst::Save(_) | Inst::Split(..) => false,
+        }
+    }
+
+    /// Returns `true` if the character `c` at `h_idx` is a protected leading dot.
+    #[inline]
+    fn is_leading_dot<H>(&self, c: T, haystack: &H, h_idx: usize) -> bool
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        // The previous character is only looked up if necessary
+        self.options.is_leading_dot_protected
+            && c == T::DEFAULT_DOT
+            && self.is_leading_dot_after(c, haystack.prev(h_idx).map(|(prev, _)| prev))
+    }
+
+    /// Returns `true` if the character `c` following the character `prev` is a protected
+    /// leading dot.
+    ///
+    /// If `prev` is `None`, the character `c` is the first one of the haystack.
+    #[inline]
+    fn is_leading_dot_after(&self, c: T, prev: Option<T>) -> bool {
+        self.options.is_leading_dot_protected
+            && c == T::DEFAULT_DOT
+            && prev.map_or(true, |prev| Some(prev) == self.options.path_separator)
+    }
+
+    /// Calls `on_match` with the instruction following the consuming instruction at `pc` if
+    /// it matches the `cluster` as a whole.
+    ///
+    /// A `?`, a negated class and the `Any` instruction consume the whole cluster, while a
+    /// positive class only matches a cluster which is a single character. Literal
+    /// instructions match if they match all tokens of the cluster one after another.
+    fn match_cluster<F, G, H, M>(
+        &self,
+        pc: usize,
+        haystack: &H,
+        cluster: &mut Cluster<T>,
+        match_one: F,
+        match_range: G,
+        mut on_match: M,
+    ) where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+        H: Haystack<T> + ?Sized,
+        M: FnMut(usize),
+    {
+        let first = match haystack.next(cluster.span.start) {
+            Some((first, _)) => first,
+            None => return,
+        };
+        let is_unprotected = !self.is_leading_dot(first, haystack, cluster.span.start);
+        let is_separator = Some(first) == self.options.path_separator;
+
+        match &self.insts[pc] {
+            Inst::Token(Token::Escaped(_) | Token::Literal(_)) => {
+                let Cluster { stack, tokens, .. } = cluster;
+                stack.clear();
+                stack.push((pc, 0));
+                while let Some((pc, t_idx)) = stack.pop() {
+                    let c = if let Some(c) = tokens.get(t_idx) {
+                        *c
+                    } else {
+                        on_match(pc);
+                        continue;
+                    };
+                    if let Some(Inst::Token(token @ (Token::Escaped(_) | Token::Literal(_)))) =
+                        self.insts.get(pc)
+                    {
+                        if token.is_match_one(c, match_one, match_range) {
+                            stack.push((pc + 1, t_idx + 1));
+                        }
+                    }
+                    if let Some(len) = self.match_full_case_folding(pc, c) {
+                        stack.push((pc + len, t_idx + 1));
+                    }
+                }
+            }
+            Inst::Any if is_unprotected => on_match(pc + 1),
+            Inst::Token(Token::One) if is_unprotected && !is_separator => on_match(pc + 1),
+            Inst::Token(token @ Token::Class(class)) if is_unprotected => {
+                let is_match = match cluster.single {
+                    Some(c) => {
+                        token.is_match_one(c, match_one, match_range)
+                            && !(is_separator && matches!(class, Class::Negative(_)))
+                    }
+                    None => matches!(class, Class::Negative(_)) && !is_separator,
+                };
+                if is_match {
+                    on_match(pc + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace the `ends` with the ends of all spans starting at `start` which the negated
+    /// program with the `index` doesn't match in ascending order.
+    ///
+    /// Like a `*`, the span doesn't cross the path separator and doesn't start with a
+    /// protected leading dot unless it is empty.
+    fn match_negation<H>(&self, index: usize, haystack: &H, start: usize, ends: &mut Vec<usize>)
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        ends.clear();
+        let mut excluded = Vec::new();
+        self.negations[index].run(haystack, start, false, |_, end| {
+            excluded.push(end);
+            None
+        });
+
+        let crosses_separator = self.options.wildcard_any_segment.is_some();
+        let mut excluded = excluded.into_iter().peekable();
+        let mut end = start;
+        loop {
+            while excluded
+                .next_if(|excluded_end| *excluded_end < end)
+                .is_some()
+            {}
+            if excluded.peek() != Some(&end) {
+                ends.push(end);
+            }
+
+            let c = match haystack.next(end) {
+                Some((c, _)) => c,
+                None => break,
+            };
+            if self.is_leading_dot(c, haystack, end)
+                || (!crosses_separator && Some(c) == self.options.path_separator)
+            {
+                break;
+            }
+            end = haystack.step(end);
+        }
+    }
+
+    /// Returns the number of literal instructions starting at `pc` which match the full case
+    /// folding of the character `c` if it folds to multiple characters.
+    ///
+    /// Only case-insensitive programs fold this way, so the `ß` of a haystack consumes the
+    /// literals `ss` of the pattern at once.
+    #[inline]
+    fn match_full_case_folding(&self, pc: usize, c: T) -> Option<usize> {
+        if self.options.case_sensitive {
+            return None;
+        }
+
+        let folded = fold_case_full(c)?;
+        let insts = self.insts.get(pc..pc + folded.len())?;
+        insts
+            .iter()
+            .zip(folded)
+            .all(|(inst, folded_c)| {
+                matches!(inst, Inst::Token(Token::Literal(literal))
+                    if T::match_one_case_insensitive(*literal, *folded_c))
+            })
+            .then_some(folded.len())
+    }
+
+    /// Returns the literal and escaped characters at the start of the pattern.
+    pub(crate) const fn literal_prefix(&self) -> &Vec<T> {
+        &self.literal_prefix
+    }
+
+    /// Returns the literal and escaped characters at the end of the pattern.
+    pub(crate) const fn literal_suffix(&self) -> &Vec<T> {
+        &self.literal_suffix
+    }
+
+    /// Push a loop which matches the `body` zero or more times, preferring to stop early like
+    /// the `*` of the `Pattern`.
+    fn push_loop(insts: &mut Vec<Inst<T>>, body: Inst<T>) {
+        let start = insts.len();
+        insts.push(Inst::Split(start + 3, start + 1));
+        insts.push(body);
+        insts.push(Inst::Jump(start));
+    }
+
+    /// Returns the spans of the captures or `None` if the program doesn't match the
+    /// `haystack`.
+    pub(crate) fn captures<H>(&self, haystack: &H) -> Option<Vec<Range<usize>>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        #[cfg(feature = "normalization")]
+        if let Some(normalization) = self.options.normalization {
+            return self.captures_in(&Normalized::new(haystack, normalization));
+        }
+        self.captures_in(haystack)
+    }
+
+    /// Returns the spans of the captures in the `haystack` as it is.
+    ///
+    /// The backtracking only starts after a run of the program found a match, so a haystack
+    /// which doesn't match never pays for it.
+    fn captures_in<H>(&self, haystack: &H) -> Option<Vec<Range<usize>>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        if !self.is_match_in(haystack) {
+            return None;
+        }
+
+        if self.options.case_sensitive {
+            self.captures_worker(
+                haystack,
+                T::match_one_case_sensitive,
+                T::match_range_case_sensitive,
+            )
+        } else {
+            self.captures_worker(
+                haystack,
+                T::match_one_case_insensitive,
+                T::match_range_case_insensitive,
+            )
+        }
+    }
+
+    /// Returns the leftmost shortest or longest match in the `haystack` which starts at the
+    /// haystack index `from` or later.
+    pub(crate) fn find<H>(&self, haystack: &H, from: usize, is_longest: bool) -> Option<Range<usize>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        #[cfg(feature = "normalization")]
+        if let Some(normalization) = self.options.normalization {
+            return self.find_in(&Normalized::new(haystack, normalization), from, is_longest);
+        }
+        self.find_in(haystack, from, is_longest)
+    }
+
+    /// Returns the leftmost shortest or longest match in the `haystack` as it is.
+    ///
+    /// A match is only replaced by a match from an earlier start or, if `is_longest`, by a
+    /// longer match from the same start, so the matches from later starts are stopped.
+    fn find_in<H>(&self, haystack: &H, from: usize, is_longest: bool) -> Option<Range<usize>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        let mut found = None;
+        self.run(haystack, from, true, |start, end| {
+            found = Some(start..end);
+            Some(if is_longest { start + 1 } else { start })
+        });
+        found
+    }
+
+    /// Returns `true` if the program matches the whole `haystack`.
+    pub(crate) fn is_match<H>(&self, haystack: &H) -> bool
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        #[cfg(feature = "normalization")]
+        if let Some(normalization) = self.options.normalization {
+            return self.is_match_in(&Normalized::new(haystack, normalization));
+        }
+        self.is_match_in(haystack)
+    }
+
+    /// Returns `true` if the program matches the whole `haystack` as it is.
+    fn is_match_in<H>(&self, haystack: &H) -> bool
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        let mut is_match = false;
+        self.run(haystack, 0, false, |_, end| {
+            is_match = end == haystack.end();
+            is_match.then_some(0)
+        });
+        is_match
+    }
+
+    /// Returns `true` if the program can match a haystack in chunks with a [`Stream`].
+    ///
+    /// A `!(...)` looks ahead in the haystack, so it can't.
+    pub(crate) fn is_streamable(&self) -> bool {
+        self.negations.is_empty()
+    }
+
+    /// Returns a new [`Stream`] at the start of a haystack.
+    pub(crate) fn stream(&self) -> Stream<T> {
+        let mut threads = Threads::new(self.insts.len());
+        let mut stack = Vec::new();
+
+        // A loop around an `Any` which can reach the match without consuming anything matches
+        // whatever follows
+        let is_universal = (0..self.insts.len())
+            .map(|pc| {
+                matches!(self.insts[pc], Inst::Any) && {
+                    threads.clear();
+                    self.add(&mut threads, &mut stack, pc + 1, 0);
+                    threads.seen[pc]
+                        && threads
+                            .pcs
+                            .iter()
+                            .any(|pc| matches!(self.insts[*pc], Inst::Match))
+                }
+            })
+            .collect();
+
+        threads.clear();
+        let mut stream = Stream {
+            current: threads,
+            is_universal,
+            next: Threads::new(self.insts.len()),
+            prev: None,
+            stack,
+            verdict: None,
+        };
+        self.restart(&mut stream);
+        stream
+    }
+
+    /// Reset the `stream` to the start of a new haystack.
+    pub(crate) fn restart(&self, stream: &mut Stream<T>) {
+        stream.current.clear();
+        stream.prev = None;
+        self.add(&mut stream.current, &mut stream.stack, 0, 0);
+        stream.verdict = self.stream_verdict(stream);
+    }
+
+    /// Advance the `stream` through the next `chunk` of the haystack.
+    ///
+    /// Returns the verdict as soon as it is certain. The rest of the `chunk` is skipped then.
+    pub(crate) fn feed(&self, stream: &mut Stream<T>, chunk: &[T]) -> Option<bool> {
+        if self.options.case_sensitive {
+            self.feed_worker(
+                stream,
+                chunk,
+                T::match_one_case_sensitive,
+                T::match_range_case_sensitive,
+            )
+        } else {
+            self.feed_worker(
+                stream,
+                chunk,
+                T::match_one_case_insensitive,
+                T::match_range_case_insensitive,
+            )
+        }
+    }
+
+    /// Returns `true` if the `stream` matched the haystack so far.
+    pub(crate) fn is_stream_match(&self, stream: &Stream<T>) -> bool {
+        stream.verdict.unwrap_or_else(|| {
+            stream
+                .current
+                .pcs
+                .iter()
+                .any(|pc| matches!(self.insts[*pc], Inst::Match))
+        })
+    }
+
+    /// Advance all active instructions of the `stream` in lockstep through the `chunk` like
+    /// [`Program::step`]
+    fn feed_worker<F, G>(
+        &self,
+        stream: &mut Stream<T>,
+        chunk: &[T],
+        match_one: F,
+        match_range: G,
+    ) -> Option<bool>
+    where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+    {
+        for c in chunk.iter().copied() {
+            if stream.verdict.is_some() {
+                break;
+            }
+
+            let Stream {
+                current,
+                next,
+                prev,
+                stack,
+                ..
+            } = stream;
+            let is_leading_dot = self.is_leading_dot_after(c, *prev);
+            for pc in current.pcs.iter().copied() {
+                let inst = &self.insts[pc];
+                if !matches!(inst, Inst::Any | Inst::Token(_)) {
+                    continue;
+                }
+                if self.is_match_one(inst, c, is_leading_dot, match_one, match_range) {
+                    self.add(next, stack, pc + 1, 0);
+                }
+                if let Some(len) = self.match_full_case_folding(pc, c) {
+                    self.add(next, stack, pc + len, 0);
+                }
+            }
+
+            mem::swap(current, next);
+            next.clear();
+            *prev = Some(c);
+            stream.verdict = self.stream_verdict(stream);
+        }
+
+        stream.verdict
+    }
+
+    /// Returns the verdict of the `stream` if it doesn't depend on the rest of the haystack.
+    ///
+    /// Without active instructions, nothing can match anymore. If the match is reachable
+    /// through a `*` which matches whatever follows, everything matches. A protected leading
+    /// dot could still stop such a `*` at the start of the haystack or after a separator.
+    fn stream_verdict(&self, stream: &Stream<T>) -> Option<bool> {
+        if stream.current.pcs.is_empty() {
+            return Some(false);
+        }
+
+        let is_blockable = self.options.is_leading_dot_protected
+            && (stream.prev.is_none() || self.options.path_separator.is_some());
+        let is_universal = !is_blockable
+            && stream.current.pcs.iter().any(|pc| stream.is_universal[*pc])
+            && stream
+                .current
+                .pcs
+                .iter()
+                .any(|pc| matches!(self.insts[*pc], Inst::Match));
+
+        is_universal.then_some(true)
+    }
+
+    /// Add the instruction at `pc` and all instructions reachable from it without consuming
+    /// a character to the `threads` for a match from the haystack index `start`.
+    ///
+    /// Of all matches which reach the same instruction, only the earliest start is kept. An
+    /// instruction which is reached from an earlier start again is added once more, so it is
+    /// processed again with the earlier start.
+    fn add(&self, threads: &mut Threads, stack: &mut Vec<usize>, pc: usize, start: usize) {
+        stack.push(pc);
+        while let Some(pc) = stack.pop() {
+            if threads.seen[pc] && threads.starts[pc] <= start {
+                continue;
+            }
+            threads.seen[pc] = true;
+            threads.starts[pc] = start;
+            threads.pcs.push(pc);
+
+            match self.insts[pc] {
+                Inst::Jump(next) => stack.push(next),
+                Inst::Save(_) => stack.push(pc + 1),
+                Inst::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::Any | Inst::Match | Inst::Not(_) | Inst::Token(_) => {}
+            }
+        }
+    }
+
+    /// Find the first path through the program matching the `haystack` in the order of
+    /// preference and return its captures.
+    ///
+    /// The instructions reached at a haystack index are remembered with one bit each, since
+    /// continuing from there fails the same way every time. This bounds the backtracking to
+    /// the number of instructions times the length of the `haystack`.
+    fn captures_worker<F, G, H>(
+        &self,
+        haystack: &H,
+        match_one: F,
+        match_range: G,
+    ) -> Option<Vec<Range<usize>>>
+    where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+        H: Haystack<T> + ?Sized,
+    {
+        let width = haystack.end() + 1;
+        let mut visited = vec![0_u64; (self.insts.len() * width + 63) / 64];
+        let mut slots = vec![0; self.num_slots];
+        let mut stack = vec![Job::Explore(0, 0)];
+        let mut cluster = Cluster::new();
+
+        while let Some(job) = stack.pop() {
+            let (mut pc, mut h_idx) = match job {
+                Job::Explore(pc, h_idx) => (pc, h_idx),
+                Job::Restore(slot, h_idx) => {
+                    slots[slot] = h_idx;
+                    continue;
+                }
+            };
+
+            loop {
+                let bit = pc * width + h_idx;
+                let mask = 1 << (bit % 64);
+                if visited[bit / 64] & mask != 0 {
+                    break;
+                }
+                visited[bit / 64] |= mask;
+
+                match &self.insts[pc] {
+                    Inst::Jump(next) => pc = *next,
+                    Inst::Match if h_idx == haystack.end() => {
+                        return Some(slots.chunks(2).map(|span| span[0]..span[1]).collect());
+                    }
+                    Inst::Match => break,
+                    Inst::Save(slot) => {
+                        stack.push(Job::Restore(*slot, slots[*slot]));
+                        slots[*slot] = h_idx;
+                        pc += 1;
+                    }
+                    Inst::Split(first, second) => {
+                        stack.push(Job::Explore(*second, h_idx));
+                        pc = *first;
+                    }
+                    Inst::Not(index) => {
+                        // The shortest span is explored first like with a lazy `*`
+                        let mut ends = Vec::new();
+                        self.match_negation(*index, haystack, h_idx, &mut ends);
+                        stack.extend(ends.into_iter().rev().map(|end| Job::Explore(pc + 1, end)));
+                        break;
+                    }
+                    inst @ (Inst::Any | Inst::Token(_)) => {
+                        let (c, next) = match haystack.next(h_idx) {
+                            Some(next) => next,
+                            None => break,
+                        };
+                        if cluster.read(haystack, h_idx, c, next) {
+                            let end = cluster.span.end;
+                            self.match_cluster(
+                                pc,
+                                haystack,
+                                &mut cluster,
+                                match_one,
+                                match_range,
+                                |pc| stack.push(Job::Explore(pc, end)),
+                            );
+                            break;
+                        }
+                        if let Some(len) = self.match_full_case_folding(pc, c) {
+                            stack.push(Job::Explore(pc + len, next));
+                        }
+                        let is_leading_dot = self.is_leading_dot(c, haystack, h_idx);
+                        if self.is_match_one(inst, c, is_leading_dot, match_one, match_range) {
+                            pc += 1;
+                            h_idx = next;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the next of the non-overlapping matches in the `haystack` which the `search`
+    /// hasn't returned yet.
+    pub(crate) fn find_next<H>(&self, search: &mut Search<T>, haystack: &H) -> Option<Range<usize>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        #[cfg(feature = "normalization")]
+        if let Some(normalization) = self.options.normalization {
+            return self.find_next_in(search, &Normalized::new(haystack, normalization));
+        }
+        self.find_next_in(search, haystack)
+    }
+
+    /// Returns the next match of the `search` in the `haystack` as it is.
+    fn find_next_in<H>(&self, search: &mut Search<T>, haystack: &H) -> Option<Range<usize>>
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        if self.options.case_sensitive {
+            self.find_next_worker(
+                search,
+                haystack,
+                T::match_one_case_sensitive,
+                T::match_range_case_sensitive,
+            )
+        } else {
+            self.find_next_worker(
+                search,
+                haystack,
+                T::match_one_case_insensitive,
+                T::match_range_case_insensitive,
+            )
+        }
+    }
+
+    /// Advance the scan of the `search` until its first tentative match is certain
+    ///
+    /// The match is certain as soon as no active instruction is left whose match starts in
+    /// front of it, or at the end of the `haystack`.
+    fn find_next_worker<F, G, H>(
+        &self,
+        search: &mut Search<T>,
+        haystack: &H,
+        match_one: F,
+        match_range: G,
+    ) -> Option<Range<usize>>
+    where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+        H: Haystack<T> + ?Sized,
+    {
+        let Search { matches, scan } = search;
+        loop {
+            if scan.h_idx.is_none() {
+                return matches.pop_certain(None);
+            }
+            if !matches.tentative.is_empty() {
+                if let Some(span) = matches.pop_certain(scan.earliest_start(matches)) {
+                    return Some(span);
+                }
+            }
+            self.step(scan, haystack, matches, match_one, match_range);
+        }
+    }
+
+    /// Returns a new [`Search`] for the non-overlapping matches from the start of a haystack.
+    pub(crate) fn search(&self) -> Search<T> {
+        Search {
+            matches: NonOverlapping {
+                from: 0,
+                last_end: None,
+                tentative: VecDeque::new(),
+            },
+            scan: Scan::new(self.insts.len(), 0, true),
+        }
+    }
+
+    /// Run the program from the `start` of the `haystack` with the case sensitivity of this
+    /// program
+    ///
+    /// If `is_unanchored`, a match can start at the `start` or any later haystack index.
+    /// The closure `on_match` is called with the start and the end index of every match in
+    /// ascending order of the end. If it returns a haystack index, all matches which start
+    /// there or later are stopped.
+    #[inline]
+    fn run<H, M>(&self, haystack: &H, start: usize, is_unanchored: bool, on_match: M)
+    where
+        H: Haystack<T> + ?Sized,
+        M: FnMut(usize, usize) -> Option<usize>,
+    {
+        let mut scan = Scan::new(self.insts.len(), start, is_unanchored);
+        let mut starts = Limit {
+            limit: usize::MAX,
+            on_match,
+        };
+        if self.options.case_sensitive {
+            self.run_worker(
+                &mut scan,
+                haystack,
+                &mut starts,
+                T::match_one_case_sensitive,
+                T::match_range_case_sensitive,
+            );
+        } else {
+            self.run_worker(
+                &mut scan,
+                haystack,
+                &mut starts,
+                T::match_one_case_insensitive,
+                T::match_range_case_insensitive,
+            );
+        }
+    }
+
+    /// Advance the `scan` through the rest of the `haystack`
+    fn run_worker<F, G, H, S>(
+        &self,
+        scan: &mut Scan<T>,
+        haystack: &H,
+        starts: &mut S,
+        match_one: F,
+        match_range: G,
+    ) where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+        H: Haystack<T> + ?Sized,
+        S: Starts,
+    {
+        while scan.h_idx.is_some() {
+            self.step(scan, haystack, starts, match_one, match_range);
+        }
+    }
+
+    /// Advance all possible positions in the program in lockstep by one token or cluster of
+    /// the `haystack`
+    ///
+    /// Every instruction is active at most once per haystack index and start, and only the
+    /// earliest start of an instruction is kept. An unanchored scan adds a new start at every
+    /// haystack index, so it finds the leftmost match in a single pass. Without `!(...)`, a
+    /// step never takes more than the number of instructions steps unless a match stops
+    /// starts in front of the wanted ones. The step is repeated without them then.
+    fn step<F, G, H, S>(
+        &self,
+        scan: &mut Scan<T>,
+        haystack: &H,
+        starts: &mut S,
+        match_one: F,
+        match_range: G,
+    ) where
+        F: Fn(T, T) -> bool + Copy,
+        G: Fn(T, T, T) -> bool + Copy,
+        H: Haystack<T> + ?Sized,
+        S: Starts,
+    {
+        let h_idx = match scan.h_idx {
+            Some(h_idx) => h_idx,
+            None => return,
+        };
+        let is_start = scan.is_unanchored || h_idx == scan.start;
+        let Scan {
+            cluster,
+            current,
+            ends,
+            next,
+            pending,
+            stack,
+            ..
+        } = scan;
+
+        while let Some(Reverse((_, from, pc))) =
+            pending.peek().filter(|Reverse((at, ..))| *at <= h_idx)
+        {
+            let (from, pc) = (*from, *pc);
+            pending.pop();
+            if starts.is_wanted(from) {
+                self.add(current, stack, pc, from);
+            }
+        }
+        if is_start && starts.is_wanted(h_idx) {
+            self.add(current, stack, 0, h_idx);
+        }
+        if current.pcs.is_empty() && pending.is_empty() {
+            scan.h_idx = None;
+            return;
+        }
+
+        let next_c = haystack.next(h_idx);
+        let is_leading_dot = next_c.map_or(false, |(c, _)| self.is_leading_dot(c, haystack, h_idx));
+        let cluster_end = next_c
+            .filter(|(c, next)| cluster.read(haystack, h_idx, *c, *next))
+            .map(|_| cluster.span.end);
+        let mut index = 0;
+        while let Some(pc) = current.pcs.get(index).copied() {
+            index += 1;
+            let from = current.starts[pc];
+            if !starts.is_wanted(from) {
+                continue;
+            }
+            match &self.insts[pc] {
+                Inst::Match => {
+                    if starts.on_match(from, h_idx) {
+                        self.retain(current, stack, starts);
+                        if is_start && starts.is_wanted(h_idx) {
+                            self.add(current, stack, 0, h_idx);
+                        }
+                        next.clear();
+                        index = 0;
+                    }
+                }
+                Inst::Not(negation) => {
+                    self.match_negation(*negation, haystack, h_idx, ends);
+                    for end in ends.iter().copied() {
+                        if end == h_idx {
+                            self.add(current, stack, pc + 1, from);
+                        } else {
+                            pending.push(Reverse((end, from, pc + 1)));
+                        }
+                    }
+                }
+                inst @ (Inst::Any | Inst::Token(_)) => {
+                    if cluster_end.is_some() {
+                        self.match_cluster(pc, haystack, cluster, match_one, match_range, |pc| {
+                            self.add(next, stack, pc, from);
+                        });
+                        continue;
+                    }
+                    let c = match next_c {
+                        Some((c, _)) => c,
+                        None => continue,
+                    };
+                    if self.is_match_one(inst, c, is_leading_dot, match_one, match_range) {
+                        self.add(next, stack, pc + 1, from);
+                    }
+                    if let Some(len) = self.match_full_case_folding(pc, c) {
+                        self.add(next, stack, pc + len, from);
+                    }
+                }
+                Inst::Jump(_) | Inst::Save(_) | Inst::Split(..) => {}
+            }
+        }
+
+        scan.h_idx = cluster_end.or_else(|| next_c.map(|(_, next)| next));
+        if scan.h_idx.is_some() {
+            mem::swap(current, next);
+            next.clear();
+        }
+    }
+
+    /// Keep only the instructions of the `threads` whose starts are still wanted.
+    ///
+    /// An instruction of a stopped start could have kept a later start from reaching the
+    /// instructions following it, so the wanted ones are added again.
+    fn retain<S>(&self, threads: &mut Threads, stack: &mut Vec<usize>, starts: &S)
+    where
+        S: Starts,
+    {
+        let wanted = threads
+            .pcs
+            .iter()
+            .map(|pc| (*pc, threads.starts[*pc]))
+            .filter(|(_, start)| starts.is_wanted(*start))
+            .collect::<Vec<_>>();
+        threads.clear();
+        for (pc, start) in wanted {
+            self.add(threads, stack, pc, start);
+        }
+    }
+}
+
+impl<T> Cluster<T>
+where
+    T: Wildcard,
+{
+    const fn new() -> Self {
+        Self {
+            single: None,
+            span: 0..0,
+            stack: Vec::new(),
+            tokens: Vec::new(),
+        }
+    }
+
+    /// Reads the cluster at `h_idx` of the `haystack` where `c` is the token at `h_idx` and
+    /// `next` the position after it.
+    ///
+    /// Returns `true` if the cluster is not just the token `c`, so it has to be matched as a
+    /// whole with [`Program::match_cluster`].
+    fn read<H>(&mut self, haystack: &H, h_idx: usize, c: T, next: usize) -> bool
+    where
+        H: Haystack<T> + ?Sized,
+    {
+        if !H::HAS_CLUSTERS {
+            return false;
+        }
+
+        self.tokens.clear();
+        match haystack.next_cluster(h_idx, &mut self.tokens) {
+            Some((single, end)) => {
+                self.single = single;
+                self.span = h_idx..end;
+                end != next || self.tokens.as_slice() != [c]
+            }
+            None => false,
+        }
+    }
+}
+
+impl<M> Starts for Limit<M>
+where
+    M: FnMut(usize, usize) -> Option<usize>,
+{
+    #[inline]
+    fn is_wanted(&self, start: usize) -> bool {
+        start < self.limit
+    }
+
+    #[inline]
+    fn on_match(&mut self, start: usize, end: usize) -> bool {
+        if let Some(stop) = (self.on_match)(start, end) {
+            self.limit = self.limit.min(stop);
+        }
+        // Only starts after the wanted ones are stopped
+        false
+    }
+}
+
+impl NonOverlapping {
+    /// Returns the index of the gap between the tentative matches which contains the
+    /// haystack index `start`.
+    ///
+    /// The gap with the index `k` follows the `k`-th tentative match and precedes the next
+    /// one. A start between a tentative match and the following gap is stopped.
+    fn gap(&self, start: usize) -> usize {
+        self.tentative.partition_point(|(_, next)| *next <= start)
+    }
+
+    /// Removes the first tentative match and returns it if no wanted match starts in front
+    /// of it, which `earliest` is the earliest wanted start of.
+    fn pop_certain(&mut self, earliest: Option<usize>) -> Option<Range<usize>> {
+        let (span, _) = self.tentative.front()?;
+        if earliest.map_or(false, |earliest| earliest < span.start) {
+            return None;
+        }
+
+        let (span, next) = self.tentative.pop_front()?;
+        self.from = next;
+        self.last_end = Some(span.end);
+        Some(span)
+    }
+}
+
+impl Starts for NonOverlapping {
+    #[inline]
+    fn is_wanted(&self, start: usize) -> bool {
+        if start < self.from {
+            return false;
+        }
+        match self.tentative.back() {
+            Some((_, next)) if start < *next => start < self.tentative[self.gap(start)].0.start,
+            _ => true,
+        }
+    }
+
+    fn on_match(&mut self, start: usize, end: usize) -> bool {
+        if !self.is_wanted(start) {
+            return false;
+        }
+
+        let gap = self.gap(start);
+        let prev_end = match gap.checked_sub(1) {
+            Some(prev) => Some(self.tentative[prev].0.end),
+            None => self.last_end,
+        };
+        // An empty match directly after the previous match is not a new match, so its start
+        // is skipped
+        if start == end && prev_end == Some(end) {
+            match gap.checked_sub(1) {
+                Some(prev) => self.tentative[prev].1 = end + 1,
+                None => self.from = end + 1,
+            }
+            return false;
+        }
+
+        // After an empty match, the next match starts at the next index
+        let next = if start == end { end + 1 } else { end };
+        self.tentative.truncate(gap);
+        self.tentative.push_back((start..end, next));
+        true
+    }
+}
+
+impl<T> Scan<T>
+where
+    T: Wildcard,
+{
+    fn new(len: usize, start: usize, is_unanchored: bool) -> Self {
+        Self {
+            cluster: Cluster::new(),
+            current: Threads::new(len),
+            ends: Vec::new(),
+            h_idx: Some(start),
+            is_unanchored,
+            next: Threads::new(len),
+            pending: BinaryHeap::new(),
+            stack: Vec::new(),
+            start,
+        }
+    }
+
+    /// Returns the earliest start of the active and pending instructions which the `starts`
+    /// still want.
+    fn earliest_start<S>(&self, starts: &S) -> Option<usize>
+    where
+        S: Starts,
+    {
+        let active = self.current.pcs.iter().map(|pc| self.current.starts[*pc]);
+        let pending = self.pending.iter().map(|Reverse((_, from, _))| *from);
+        active
+            .chain(pending)
+            .filter(|start| starts.is_wanted(*start))
+            .min()
+    }
+}
+
+impl<T> Stream<T> {
+    /// Returns the verdict if it is certain already.
+    pub(crate) const fn verdict(&self) -> Option<bool> {
+        self.verdict
+    }
+}
+
+impl Threads {
+    fn new(len: usize) -> Self {
+        Self {
+            pcs: Vec::with_capacity(len),
+            seen: vec![false; len],
+            starts: vec![0; len],
+        }
+    }
+
+    fn clear(&mut self) {
+        for pc in self.pcs.drain(..) {
+            self.seen[pc] = false;
+        }
+    }
+}
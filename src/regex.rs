@@ -0,0 +1,588 @@
+//! The translation of a pattern to the regex syntax of [`to_regex_string`]
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::pattern::Token;
+use crate::program::{Extglob, Lexeme};
+#[cfg(doc)]
+use crate::to_regex_string;
+use crate::{
+    diagnostic, Class, ClassKind, NamedClass, Options, Pattern, SimpleMatchError, Wildcard,
+};
+
+/// The bit which distinguishes the upper- and lowercase ASCII letters
+const ASCII_CASE_MASK: u8 = 0b0010_0000;
+
+/// How a case-insensitive pattern is translated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaseFolding {
+    /// Only the ASCII letters are folded, so they are spelled out like `[aA]`
+    Ascii,
+    /// The flag `i` folds the characters like the [`Wildcard`] does
+    Flag,
+    /// The pattern is case-sensitive
+    None,
+}
+
+/// Whether the next character of the haystack is at the start of a path segment
+///
+/// A protected leading dot can only be matched by a literal dot at the start of a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Leading {
+    /// The next character is not at the start of a segment
+    No,
+    /// It depends on the haystack if the next character is at the start of a segment
+    Unknown,
+    /// The next character is at the start of the haystack or follows a path separator
+    Yes,
+}
+
+/// An open brace alternation or extended glob
+struct Group {
+    /// The leading state before the group
+    before: Leading,
+    /// The merged leading state at the end of the alternatives seen so far
+    end: Option<Leading>,
+    /// `None` for a brace alternation
+    extglob: Option<Extglob>,
+    /// The index of the opening lexeme
+    index: usize,
+    /// The length of the regex after the opening `(?:`
+    len: usize,
+    /// The leading state at the start of every alternative
+    start: Leading,
+}
+
+/// Translates the lexemes of a pattern to a regex from left to right
+struct Translator<T>
+where
+    T: Wildcard,
+{
+    case_folding: CaseFolding,
+    groups: Vec<Group>,
+    leading: Leading,
+    options: Options<T>,
+    regex: String,
+}
+
+impl Leading {
+    /// Returns the state if either this or the `other` state is possible.
+    const fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::No, Self::No) => Self::No,
+            (Self::Yes, Self::Yes) => Self::Yes,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl<T> Translator<T>
+where
+    T: Wildcard + Ord,
+{
+    /// Close the innermost group.
+    ///
+    /// A repeated group under the leading dot protection has to be translated again if an
+    /// alternative ends in another leading state than the one it was translated with. Returns
+    /// the index of the lexeme to continue with in this case.
+    fn close_group(&mut self) -> Option<usize> {
+        let leading = self.leading;
+        let group = self.groups.last_mut()?;
+        let end = group.end.map_or(leading, |end| end.merge(leading));
+        let is_repeated = matches!(
+            group.extglob,
+            Some(Extglob::OneOrMore | Extglob::ZeroOrMore)
+        );
+
+        if self.options.is_leading_dot_protected
+            && is_repeated
+            && end.merge(group.start) != group.start
+        {
+            group.start = end.merge(group.start);
+            group.end = None;
+            self.regex.truncate(group.len);
+            self.leading = group.start;
+            return Some(group.index + 1);
+        }
+
+        let before = group.before;
+        let extglob = group.extglob;
+        self.groups.pop();
+        self.regex.push(')');
+        self.leading = match extglob {
+            Some(Extglob::OneOrMore) => {
+                self.regex.push('+');
+                end
+            }
+            Some(Extglob::ZeroOrMore) => {
+                self.regex.push('*');
+                end.merge(before)
+            }
+            Some(Extglob::ZeroOrOne) => {
+                self.regex.push('?');
+                end.merge(before)
+            }
+            Some(Extglob::Not | Extglob::One) | None => end,
+        };
+
+        None
+    }
+
+    /// Returns `true` if the next character can't be a dot because of the leading dot
+    /// protection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the protection depends on the haystack.
+    const fn is_dot_excluded(&self) -> Result<bool, SimpleMatchError> {
+        if !self.options.is_leading_dot_protected {
+            return Ok(false);
+        }
+        match self.leading {
+            Leading::No => Ok(false),
+            Leading::Unknown => Err(SimpleMatchError::NoRegexEquivalent),
+            Leading::Yes => Ok(true),
+        }
+    }
+
+    fn open_group(&mut self, extglob: Option<Extglob>, index: usize) {
+        self.regex.push_str("(?:");
+        self.groups.push(Group {
+            before: self.leading,
+            end: None,
+            extglob,
+            index,
+            len: self.regex.len(),
+            start: self.leading,
+        });
+    }
+
+    /// Push a `*` which crosses path separators if `crosses_separator` is `true`.
+    fn push_any(&mut self, crosses_separator: bool) -> Result<(), SimpleMatchError> {
+        let is_dot_excluded = self.is_dot_excluded()?;
+        let separator = self.options.path_separator;
+
+        match (crosses_separator, separator) {
+            (true, _) if !self.options.is_leading_dot_protected => self.regex.push_str(".*"),
+            (false, _) | (true, None) if !is_dot_excluded => {
+                self.push_one(false, !crosses_separator);
+                self.regex.push('*');
+            }
+            (false, _) | (true, None) => {
+                self.regex.push_str("(?:");
+                self.push_one(true, !crosses_separator);
+                self.push_one(false, !crosses_separator);
+                self.regex.push_str("*)?");
+                self.leading = Leading::Unknown;
+            }
+            // A dot must not follow a separator, and if `is_dot_excluded`, must not be first
+            (true, Some(separator)) => {
+                self.regex.push_str("(?:");
+                if is_dot_excluded {
+                    self.push_segment_start(separator);
+                    self.regex.push_str("(?:");
+                }
+                self.push_one(false, true);
+                self.regex.push('|');
+                T::push_regex_literal(separator, &mut self.regex);
+                self.regex.push('+');
+                self.push_one(true, true);
+                self.regex
+                    .push_str(if is_dot_excluded { ")*)?" } else { ")*" });
+                T::push_regex_literal(separator, &mut self.regex);
+                self.regex.push('*');
+                self.leading = Leading::Unknown;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Push the `class`.
+    ///
+    /// The flag `i` would fold the named classes, too, so a class with a named class is
+    /// matched without it. For the bytes, the other members are folded by spelling out both
+    /// cases then. The Unicode members are matched in a separate class with the flag, which
+    /// can't be negated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a negated class mixes named classes and other Unicode members.
+    fn push_class(
+        &mut self,
+        class: &Class<T>,
+        is_dot_excluded: bool,
+    ) -> Result<(), SimpleMatchError> {
+        let (kinds, is_negative) = match class {
+            Class::Negative(kinds) => (kinds, true),
+            Class::Positive(kinds) => (kinds, false),
+        };
+        let is_named = |kind: &&ClassKind<T>| matches!(kind, ClassKind::Named(_));
+
+        if self.case_folding != CaseFolding::Flag || !kinds.iter().any(|kind| is_named(&kind)) {
+            self.push_class_members(kinds, is_negative, is_dot_excluded, self.case_folding);
+            return Ok(());
+        }
+
+        let (named, others) = kinds.iter().partition::<Vec<_>, _>(is_named);
+        if !T::IS_UNICODE {
+            self.regex.push_str("(?-i:");
+            self.push_class_members(kinds, is_negative, is_dot_excluded, CaseFolding::Ascii);
+        } else if others.is_empty() {
+            self.regex.push_str("(?-i:");
+            self.push_class_members(named, is_negative, is_dot_excluded, CaseFolding::None);
+        } else if !is_negative {
+            self.regex.push_str("(?:(?-i:");
+            self.push_class_members(named, false, is_dot_excluded, CaseFolding::None);
+            self.regex.push_str(")|");
+            self.push_class_members(others, false, is_dot_excluded, CaseFolding::Flag);
+        } else {
+            return Err(SimpleMatchError::NoRegexEquivalent);
+        }
+        self.regex.push(')');
+
+        Ok(())
+    }
+
+    /// Push a character class of the `kinds` with the `case_folding`.
+    fn push_class_members<'k, I>(
+        &mut self,
+        kinds: I,
+        is_negative: bool,
+        is_dot_excluded: bool,
+        case_folding: CaseFolding,
+    ) where
+        I: IntoIterator<Item = &'k ClassKind<T>>,
+        T: 'k,
+    {
+        // A class has at least one member and the ranges are ordered by the parser
+        let mut members = String::new();
+        for kind in kinds {
+            push_class_kind(kind, case_folding, &mut members);
+        }
+
+        self.regex.push('[');
+        if is_negative {
+            if let Some(separator) = self.options.path_separator {
+                T::push_regex_literal(separator, &mut members);
+            }
+            if is_dot_excluded {
+                T::push_regex_literal(T::DEFAULT_DOT, &mut members);
+            }
+            self.regex.push('^');
+            self.regex.push_str(&members);
+        } else {
+            self.regex.push_str(&members);
+            if is_dot_excluded {
+                self.regex.push_str("&&[^");
+                T::push_regex_literal(T::DEFAULT_DOT, &mut self.regex);
+                self.regex.push(']');
+            }
+        }
+        self.regex.push(']');
+    }
+
+    /// Push zero or more segments each followed by a separator.
+    fn push_globstar_segments(&mut self) -> Result<(), SimpleMatchError> {
+        let separator = match self.options.path_separator {
+            Some(separator) => separator,
+            None => return Ok(()),
+        };
+
+        if !self.options.is_leading_dot_protected {
+            self.regex.push_str("(?:");
+            self.push_one(false, true);
+            self.regex.push('*');
+            T::push_regex_literal(separator, &mut self.regex);
+            self.regex.push_str(")*");
+            return Ok(());
+        }
+
+        // After the first separator, every segment starts with a protected leading dot
+        let is_dot_excluded = self.is_dot_excluded()?;
+        if !is_dot_excluded {
+            self.regex.push_str("(?:");
+            self.push_one(false, true);
+            self.regex.push('*');
+            T::push_regex_literal(separator, &mut self.regex);
+        }
+        self.regex.push_str("(?:(?:");
+        self.push_one(true, true);
+        self.push_one(false, true);
+        self.regex.push_str("*)?");
+        T::push_regex_literal(separator, &mut self.regex);
+        self.regex.push_str(")*");
+        if is_dot_excluded {
+            self.leading = Leading::Yes;
+        } else {
+            self.regex.push_str(")?");
+            self.leading = Leading::Unknown;
+        }
+
+        Ok(())
+    }
+
+    /// Push a single character which is not a dot if `is_dot_excluded` and not the path
+    /// separator if `is_separator_excluded`.
+    fn push_one(&mut self, is_dot_excluded: bool, is_separator_excluded: bool) {
+        let separator = self
+            .options
+            .path_separator
+            .filter(|_| is_separator_excluded);
+        if !is_dot_excluded && separator.is_none() {
+            self.regex.push('.');
+            return;
+        }
+
+        self.regex.push_str("[^");
+        if is_dot_excluded {
+            T::push_regex_literal(T::DEFAULT_DOT, &mut self.regex);
+        }
+        if let Some(separator) = separator {
+            T::push_regex_literal(separator, &mut self.regex);
+        }
+        self.regex.push(']');
+    }
+
+    /// Push the first character of a non-empty sequence starting at a segment start, which is
+    /// either not a dot or a run of separators followed by something else than a dot.
+    fn push_segment_start(&mut self, separator: T) {
+        self.regex.push_str("(?:");
+        self.push_one(true, true);
+        self.regex.push('|');
+        T::push_regex_literal(separator, &mut self.regex);
+        self.regex.push('+');
+        self.push_one(true, true);
+        self.regex.push(')');
+    }
+
+    fn push_token(&mut self, token: &Token<T>) -> Result<(), SimpleMatchError> {
+        let separator = self.options.path_separator;
+        match token {
+            Token::Any => {
+                self.push_any(separator.is_none() || self.options.wildcard_any_segment.is_some())?;
+            }
+            Token::Class(class) => {
+                let is_dot_excluded = self.is_dot_excluded()?;
+                self.push_class(class, is_dot_excluded)?;
+                self.leading = match (class, separator) {
+                    (Class::Positive(_), Some(separator))
+                        if class.is_match(
+                            separator,
+                            T::match_one_case_sensitive,
+                            T::match_range_case_sensitive,
+                        ) =>
+                    {
+                        Leading::Unknown
+                    }
+                    _ => Leading::No,
+                };
+            }
+            Token::Escaped(c) | Token::Literal(c) => {
+                let is_folded = matches!(token, Token::Literal(_));
+                match self.case_folding {
+                    CaseFolding::Flag if !is_folded => {
+                        self.regex.push_str("(?-i:");
+                        T::push_regex_literal(*c, &mut self.regex);
+                        self.regex.push(')');
+                    }
+                    CaseFolding::Ascii if is_folded && is_ascii_letter(*c) => {
+                        self.regex.push('[');
+                        push_literal(*c, self.case_folding, &mut self.regex);
+                        self.regex.push(']');
+                    }
+                    _ => T::push_regex_literal(*c, &mut self.regex),
+                }
+                self.leading = if Some(*c) == separator {
+                    Leading::Yes
+                } else {
+                    Leading::No
+                };
+            }
+            Token::One => {
+                let is_dot_excluded = self.is_dot_excluded()?;
+                self.push_one(is_dot_excluded, true);
+                self.leading = Leading::No;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn separate_group(&mut self) {
+        let leading = self.leading;
+        if let Some(group) = self.groups.last_mut() {
+            group.end = Some(group.end.map_or(leading, |end| end.merge(leading)));
+            self.leading = group.start;
+            self.regex.push('|');
+        }
+    }
+
+    fn translate(&mut self, lexemes: &[Lexeme<T>]) -> Result<(), SimpleMatchError> {
+        let mut index = 0;
+        while let Some(lexeme) = lexemes.get(index) {
+            index += 1;
+            match lexeme {
+                Lexeme::AnySegment => self.push_any(false)?,
+                Lexeme::BraceClose | Lexeme::ExtglobClose => {
+                    if let Some(restart) = self.close_group() {
+                        index = restart;
+                    }
+                }
+                Lexeme::BraceOpen => self.open_group(None, index - 1),
+                Lexeme::BraceSeparator | Lexeme::ExtglobSeparator => self.separate_group(),
+                Lexeme::ExtglobOpen(Extglob::Not) => {
+                    return Err(SimpleMatchError::NoRegexEquivalent)
+                }
+                Lexeme::ExtglobOpen(extglob) => self.open_group(Some(*extglob), index - 1),
+                Lexeme::Globstar => self.push_any(true)?,
+                Lexeme::GlobstarSegments => self.push_globstar_segments()?,
+                Lexeme::Token(token) => self.push_token(token)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the token `c` is an ASCII letter.
+fn is_ascii_letter<T>(c: T) -> bool
+where
+    T: Wildcard,
+{
+    T::to_ascii(c).map_or(false, |ascii| ascii.is_ascii_alphabetic())
+}
+
+/// Push the member `kind` of a character class with the `case_folding`.
+fn push_class_kind<T>(kind: &ClassKind<T>, case_folding: CaseFolding, members: &mut String)
+where
+    T: Wildcard,
+{
+    match kind {
+        ClassKind::Named(class) => push_named_class::<T>(*class, members),
+        ClassKind::Escaped(c) | ClassKind::One(c) | ClassKind::RangeOne(c) => {
+            push_literal(*c, case_folding, members);
+        }
+        ClassKind::Range(low, high) => {
+            T::push_regex_literal(*low, members);
+            members.push('-');
+            T::push_regex_literal(*high, members);
+
+            if let (CaseFolding::Ascii, Some(low), Some(high)) =
+                (case_folding, T::to_ascii(*low), T::to_ascii(*high))
+            {
+                // The letters of the range in the other case
+                for (first, last) in [(b'a', b'z'), (b'A', b'Z')] {
+                    let (first, last) = (low.max(first), high.min(last));
+                    if first <= last {
+                        members.push(char::from(first ^ ASCII_CASE_MASK));
+                        members.push('-');
+                        members.push(char::from(last ^ ASCII_CASE_MASK));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Push the literal `c` for use inside of a character class.
+///
+/// With the ASCII case folding, a letter is pushed in both cases like `aA`.
+fn push_literal<T>(c: T, case_folding: CaseFolding, regex: &mut String)
+where
+    T: Wildcard,
+{
+    match T::to_ascii(c) {
+        Some(ascii) if case_folding == CaseFolding::Ascii && ascii.is_ascii_alphabetic() => {
+            regex.push(char::from(ascii.to_ascii_lowercase()));
+            regex.push(char::from(ascii.to_ascii_uppercase()));
+        }
+        _ => T::push_regex_literal(c, regex),
+    }
+}
+
+/// Push the members of the named `class` for use inside of a character class.
+///
+/// The bytes use the ASCII classes like `[:alpha:]`. The Unicode properties for `char`s
+/// are chosen to match the same characters as [`NamedClass::is_member`].
+fn push_named_class<T>(class: NamedClass, members: &mut String)
+where
+    T: Wildcard,
+{
+    if !T::IS_UNICODE {
+        members.push_str("[:");
+        members.push_str(class.name());
+        members.push_str(":]");
+        return;
+    }
+
+    members.push_str(match class {
+        NamedClass::Alnum => "\\p{Alphabetic}\\p{N}",
+        NamedClass::Alpha => "\\p{Alphabetic}",
+        NamedClass::Blank => "[:blank:][\\p{White_Space}&&[^\\x00-\\x7F\\x{85}\\x{2028}\\x{2029}]]",
+        NamedClass::Cntrl => "\\p{Cc}",
+        NamedClass::Digit => "[:digit:]",
+        NamedClass::Graph => "[^\\p{White_Space}\\p{Cc}]",
+        NamedClass::Lower => "\\p{Lowercase}",
+        NamedClass::Print => "[^\\p{Cc}]",
+        NamedClass::Punct => "[^\\p{White_Space}\\p{Cc}\\p{Alphabetic}\\p{N}]",
+        NamedClass::Space => "\\p{White_Space}",
+        NamedClass::Upper => "\\p{Uppercase}",
+        NamedClass::Xdigit => "[:xdigit:]",
+    });
+}
+
+/// Translate the `pattern` like described in [`to_regex_string`].
+pub fn translate<T>(pattern: &[T], options: Options<T>) -> Result<String, SimpleMatchError>
+where
+    T: Wildcard + Ord,
+{
+    options.verify()?;
+    diagnostic::validate_strict(pattern, options)?;
+    #[cfg(feature = "normalization")]
+    if options.normalization.is_some() {
+        return Err(SimpleMatchError::NoRegexEquivalent);
+    }
+
+    let mut lexemes = Pattern::lexemes(pattern, options);
+    if let (Some(separator), true) = (options.path_separator, options.is_globstar_enabled) {
+        lexemes = Lexeme::resolve_globstars(lexemes, separator);
+    }
+
+    let case_folding = if options.case_sensitive {
+        CaseFolding::None
+    } else if Lexeme::has_full_case_folding(&lexemes) {
+        return Err(SimpleMatchError::NoRegexEquivalent);
+    } else if T::IS_UNICODE && !cfg!(feature = "unicode") {
+        CaseFolding::Ascii
+    } else {
+        CaseFolding::Flag
+    };
+
+    let mut translator = Translator {
+        case_folding,
+        groups: Vec::new(),
+        leading: Leading::Yes,
+        options,
+        regex: String::with_capacity(pattern.len() * 2 + 10),
+    };
+
+    translator.regex.push_str("(?");
+    if case_folding == CaseFolding::Flag {
+        translator.regex.push('i');
+    }
+    translator
+        .regex
+        .push_str(if T::IS_UNICODE { "s)^" } else { "s-u)^" });
+    translator.translate(&lexemes)?;
+    translator.regex.push('$');
+
+    Ok(translator.regex)
+}
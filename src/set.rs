@@ -0,0 +1,280 @@
+//! The [`WildSet`] to match many patterns at once
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{diagnostic, fold_case_full, Options, Pattern, SimpleMatchError, Wildcard};
+
+/// Patterns grouped by a literal key of the same kind
+///
+/// The `lengths` are the distinct lengths of all keys in ascending order, so a lookup needs
+/// one search in the `map` per length instead of one comparison per pattern.
+#[derive(Debug, Clone)]
+struct Bucket<T> {
+    lengths: Vec<usize>,
+    map: BTreeMap<Vec<T>, Vec<usize>>,
+}
+
+/// A set of wildcard patterns which are matched against a haystack at once
+///
+/// A `WildSet` reports which of its patterns match a haystack or whether any of them
+/// matches. All patterns share the same [`Options`]. Matching is much faster than calling
+/// [`dowild_with`](crate::dowild_with) for each pattern: the patterns are grouped by their
+/// literal parts, so only the patterns whose literal parts occur at the right place in the
+/// haystack are actually run. Patterns without wildcards are looked up by the whole
+/// haystack, others by their literal prefix or suffix, whichever is longer. Extension
+/// patterns like `*.rs` are grouped by their suffix `.rs`. Only patterns without any literal
+/// prefix or suffix, like `*foo*`, are run for every haystack.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{Options, WildSet};
+///
+/// let set = WildSet::new(["*.rs", "src/*", "Cargo.toml", "*"], Options::default()).unwrap();
+///
+/// assert_eq!(set.matches("src/lib.rs"), vec![0, 1, 3]);
+/// assert_eq!(set.matches("Cargo.toml"), vec![2, 3]);
+/// assert_eq!(set.is_match("README.md"), true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WildSet<T>
+where
+    T: Wildcard + Ord,
+{
+    literals: BTreeMap<Vec<T>, Vec<usize>>,
+    options: Options<T>,
+    others: Vec<usize>,
+    patterns: Vec<Pattern<T>>,
+    prefixes: Bucket<T>,
+    suffixes: Bucket<T>,
+}
+
+impl<T> Bucket<T>
+where
+    T: Ord,
+{
+    fn new() -> Self {
+        Self {
+            lengths: Vec::new(),
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// Calls `f` with the indices of all patterns whose key is a prefix (or a suffix if
+    /// `is_suffix` is true) of the `haystack` until `f` returns `true`.
+    fn find<F>(&self, haystack: &[T], is_suffix: bool, mut f: F) -> bool
+    where
+        F: FnMut(usize) -> bool,
+    {
+        for length in self.lengths.iter().copied() {
+            if length > haystack.len() {
+                break;
+            }
+
+            let key = if is_suffix {
+                &haystack[haystack.len() - length..]
+            } else {
+                &haystack[..length]
+            };
+            if let Some(indices) = self.map.get(key) {
+                if indices.iter().copied().any(&mut f) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn insert(&mut self, key: Vec<T>, index: usize) {
+        if let Err(position) = self.lengths.binary_search(&key.len()) {
+            self.lengths.insert(position, key.len());
+        }
+        self.map.entry(key).or_default().push(index);
+    }
+}
+
+impl<T> WildSet<T>
+where
+    T: Wildcard + Ord,
+{
+    /// Create a new `WildSet` from the `patterns` which are all parsed with the same
+    /// [`Options`].
+    ///
+    /// The index of a pattern in the `WildSet` is the position in the `patterns` iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Options`] are invalid. See also [`Options::verify`]. If
+    /// [`Options::is_strict`] is set, returns an error if any of the `patterns` is malformed,
+    /// too. See also [`validate`](crate::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, WildSet};
+    ///
+    /// let set = WildSet::new(["*.c", "*.h"], Options::default()).unwrap();
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn new<I, P>(patterns: I, options: Options<T>) -> Result<Self, SimpleMatchError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[T]>,
+    {
+        options.verify()?;
+
+        let mut set = Self {
+            literals: BTreeMap::new(),
+            options,
+            others: Vec::new(),
+            patterns: Vec::new(),
+            prefixes: Bucket::new(),
+            suffixes: Bucket::new(),
+        };
+
+        for (index, pattern) in patterns.into_iter().enumerate() {
+            diagnostic::validate_strict(pattern.as_ref(), options)?;
+            let pattern = Pattern::parse(pattern.as_ref(), options);
+
+            if pattern.is_literal() {
+                let key = set.fold(&pattern.literal_prefix());
+                set.literals.entry(key).or_default().push(index);
+            } else {
+                let prefix = pattern.literal_prefix();
+                let suffix = pattern.literal_suffix();
+
+                if prefix.is_empty() && suffix.is_empty() {
+                    set.others.push(index);
+                } else if prefix.len() > suffix.len() {
+                    let key = set.fold(&prefix);
+                    set.prefixes.insert(key, index);
+                } else {
+                    let key = set.fold(&suffix);
+                    set.suffixes.insert(key, index);
+                }
+            }
+
+            set.patterns.push(pattern);
+        }
+
+        Ok(set)
+    }
+
+    /// Returns `true` if any pattern of this `WildSet` matches the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, WildSet};
+    ///
+    /// let set = WildSet::new(["*.c", "*.h"], Options::default()).unwrap();
+    ///
+    /// assert_eq!(set.is_match("main.c"), true);
+    /// assert_eq!(set.is_match("main.rs"), false);
+    /// ```
+    #[must_use]
+    pub fn is_match<H>(&self, haystack: H) -> bool
+    where
+        H: AsRef<[T]>,
+    {
+        self.find(haystack.as_ref(), |_| true)
+    }
+
+    /// Returns `true` if this `WildSet` contains no patterns.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns the number of patterns in this `WildSet`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns the indices of all patterns matching the `haystack` in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, WildSet};
+    ///
+    /// let set = WildSet::new(["*.tar.gz", "*.gz", "*.zip"], Options::default()).unwrap();
+    ///
+    /// assert_eq!(set.matches("backup.tar.gz"), vec![0, 1]);
+    /// assert_eq!(set.matches("backup.7z"), Vec::<usize>::new());
+    /// ```
+    #[must_use]
+    pub fn matches<H>(&self, haystack: H) -> Vec<usize>
+    where
+        H: AsRef<[T]>,
+    {
+        let mut indices = Vec::new();
+        self.find(haystack.as_ref(), |index| {
+            indices.push(index);
+            false
+        });
+
+        indices.sort_unstable();
+        indices
+    }
+
+    /// The [`Options`] shared by all patterns of this `WildSet`.
+    #[must_use]
+    pub const fn options(&self) -> &Options<T> {
+        &self.options
+    }
+
+    /// Calls `f` with the index of every matching pattern until `f` returns `true`.
+    fn find<F>(&self, haystack: &[T], mut f: F) -> bool
+    where
+        F: FnMut(usize) -> bool,
+    {
+        let folded;
+        let key = if self.options.case_sensitive {
+            haystack
+        } else {
+            folded = self.fold(haystack);
+            &folded
+        };
+
+        // The literal parts only select the candidates. Escaped characters or case-insensitive
+        // matching may still reject a candidate, so every candidate is run.
+        let mut check = |index: usize| self.patterns[index].is_match(haystack) && f(index);
+
+        if let Some(indices) = self.literals.get(key) {
+            if indices.iter().copied().any(&mut check) {
+                return true;
+            }
+        }
+
+        self.prefixes.find(key, false, &mut check)
+            || self.suffixes.find(key, true, &mut check)
+            || self.others.iter().copied().any(check)
+    }
+
+    /// Fold the `key` if this `WildSet` is case-insensitive.
+    ///
+    /// Characters like `ß` are replaced with their full case folding `ss`, so the keys of
+    /// patterns and haystacks agree no matter which of both spells it out.
+    fn fold(&self, key: &[T]) -> Vec<T> {
+        if self.options.case_sensitive {
+            return key.to_vec();
+        }
+
+        let mut folded = Vec::with_capacity(key.len());
+        for c in key.iter().copied() {
+            match fold_case_full(c) {
+                Some(full) => folded.extend_from_slice(full),
+                None => folded.push(T::fold_case(c)),
+            }
+        }
+        folded
+    }
+}
@@ -0,0 +1,374 @@
+//! The SQL `SIMILAR TO` predicate
+//!
+//! `SIMILAR TO` combines the `%` and `_` of `LIKE` with the operators of regular expressions.
+//! The pattern always has to match the whole string:
+//!
+//! * `%` matches any sequence of characters and `_` matches exactly one character
+//! * `a|b` matches either of the alternatives and `(...)` groups them
+//! * `*`, `+` and `?` repeat the preceding item zero or more times, one or more times or
+//!   optionally
+//! * `{m}`, `{m,}` and `{m,n}` repeat the preceding item exactly `m` times, at least `m` times
+//!   or between `m` and `n` times. The bounds can be at most 255.
+//! * A bracket expression `[...]` matches a single character like a character class of
+//!   [`dowild_with`](crate::dowild_with). It is negated with `^` and can contain ranges and
+//!   named classes like `[:digit:]`.
+//! * The escape character must precede one of the special characters `%_|*+?{}()[` or the
+//!   escape character itself, which then match literally. All other characters including `.`
+//!   match themselves.
+//!
+//! ```rust
+//! use simplematch::similar::{similar_to, SimilarError};
+//!
+//! assert_eq!(similar_to("(ab|cd)+", "abcdab", Some('\\')), Ok(true));
+//! assert_eq!(similar_to("[0-9]{3}-%", "555-1234", Some('\\')), Ok(true));
+//! assert_eq!(similar_to("a_", "abc", Some('\\')), Ok(false));
+//! assert_eq!(
+//!     similar_to("(ab", "ab", Some('\\')),
+//!     Err(SimilarError::UnmatchedParenthesis(0))
+//! );
+//! ```
+//!
+//! The pattern is translated for the matching engine of [`Pattern`], so no regex engine is
+//! involved and the time to match is linear in the length of the string.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::pattern::Token;
+use crate::program::{Extglob, Lexeme};
+use crate::{CharacterClass, Options, Pattern};
+
+/// The maximum bound of a repetition `{m,n}` like `RE_DUP_MAX` of POSIX
+const MAX_REPETITION: u32 = 255;
+/// The maximum number of lexemes the repetitions of a pattern can expand to
+const MAX_SIZE: usize = 1 << 16;
+
+/// The parser of a `SIMILAR TO` pattern
+struct Parser<'p> {
+    /// The characters of the pattern
+    chars: Vec<char>,
+    escape: Option<char>,
+    /// The byte offsets of the characters
+    offsets: Vec<usize>,
+    pattern: &'p str,
+    pos: usize,
+}
+
+/// A precompiled `SIMILAR TO` pattern
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::similar::Similar;
+///
+/// let predicate = Similar::new("%(.rs|.toml)", None).unwrap();
+///
+/// assert_eq!(predicate.is_match("Cargo.toml"), true);
+/// assert_eq!(predicate.is_match("README.md"), false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Similar(Pattern<char>);
+
+/// The error of a malformed `SIMILAR TO` pattern
+///
+/// The indices are byte offsets in the pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimilarError {
+    /// The escape character at the index precedes a character which isn't special
+    InvalidEscapeSequence(usize),
+    /// The repetition `{m,n}` starting at the index is malformed, its minimum is greater than
+    /// its maximum or a bound is greater than 255
+    InvalidRepetition(usize),
+    /// The quantifier at the index doesn't follow an item it could repeat
+    MisplacedQuantifier(usize),
+    /// The repetitions of the pattern expand to too many items
+    PatternTooLarge,
+    /// The pattern ends with the escape character
+    TrailingEscape,
+    /// The parenthesis at the index has no matching counterpart
+    UnmatchedParenthesis(usize),
+    /// The bracket expression starting at the index has no closing `]`
+    UnterminatedBracket(usize),
+}
+
+impl Parser<'_> {
+    /// Returns the byte offset of the character at `pos`
+    fn offset(&self, pos: usize) -> usize {
+        self.offsets.get(pos).copied().unwrap_or(self.pattern.len())
+    }
+
+    /// Parse the alternatives until the end of the pattern or until a `)` if `open` is the
+    /// position of the opening parenthesis
+    fn parse_alternatives(
+        &mut self,
+        open: Option<usize>,
+    ) -> Result<Vec<Lexeme<char>>, SimilarError> {
+        let mut lexemes = Vec::new();
+
+        while let Some(&c) = self.chars.get(self.pos) {
+            let start = self.pos;
+            self.pos += 1;
+            let atom = match c {
+                c if Some(c) == self.escape => {
+                    let next = match self.chars.get(self.pos) {
+                        Some(&next) => next,
+                        None => return Err(SimilarError::TrailingEscape),
+                    };
+                    if next != c && !is_special(next) {
+                        return Err(SimilarError::InvalidEscapeSequence(self.offset(start)));
+                    }
+                    self.pos += 1;
+                    vec![Lexeme::Token(Token::Literal(next))]
+                }
+                '(' => {
+                    let mut group = vec![Lexeme::ExtglobOpen(Extglob::One)];
+                    group.extend(self.parse_alternatives(Some(start))?);
+                    group.push(Lexeme::ExtglobClose);
+                    group
+                }
+                ')' => {
+                    return match open {
+                        Some(_) => Ok(lexemes),
+                        None => Err(SimilarError::UnmatchedParenthesis(self.offset(start))),
+                    }
+                }
+                '|' => {
+                    lexemes.push(Lexeme::ExtglobSeparator);
+                    continue;
+                }
+                '*' | '+' | '?' | '{' => {
+                    return Err(SimilarError::MisplacedQuantifier(self.offset(start)));
+                }
+                '%' => vec![Lexeme::Token(Token::Any)],
+                '_' => vec![Lexeme::Token(Token::One)],
+                '[' => {
+                    let class = CharacterClass::parse(
+                        start,
+                        &self.chars,
+                        &Options::new().enable_classes_with('^'),
+                    );
+                    let len = class.len();
+                    match class.class {
+                        Some(class) => {
+                            self.pos = start + len;
+                            vec![Lexeme::Token(Token::Class(class))]
+                        }
+                        None => return Err(SimilarError::UnterminatedBracket(self.offset(start))),
+                    }
+                }
+                c => vec![Lexeme::Token(Token::Literal(c))],
+            };
+
+            self.parse_quantifier(atom, &mut lexemes)?;
+            if lexemes.len() > MAX_SIZE {
+                return Err(SimilarError::PatternTooLarge);
+            }
+        }
+
+        match open {
+            Some(open) => Err(SimilarError::UnmatchedParenthesis(self.offset(open))),
+            None => Ok(lexemes),
+        }
+    }
+
+    /// Parse the bounds of a repetition `{m,n}` after the `{` at `start`
+    fn parse_bounds(&mut self, start: usize) -> Result<(u32, Option<u32>), SimilarError> {
+        let error = SimilarError::InvalidRepetition(self.offset(start));
+        let min = self.parse_number().ok_or(error)?;
+        let max = match self.chars.get(self.pos) {
+            Some(',') => {
+                self.pos += 1;
+                match self.chars.get(self.pos) {
+                    Some('}') => None,
+                    _ => Some(self.parse_number().ok_or(error)?),
+                }
+            }
+            _ => Some(min),
+        };
+        if self.chars.get(self.pos) != Some(&'}') || max.map_or(false, |max| min > max) {
+            return Err(error);
+        }
+        self.pos += 1;
+
+        Ok((min, max))
+    }
+
+    /// Parse a bound of a repetition
+    ///
+    /// Returns `None` if there is no number or it is greater than the maximum.
+    fn parse_number(&mut self) -> Option<u32> {
+        let digits = self.chars[self.pos..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        let number = self.chars[self.pos..self.pos + digits]
+            .iter()
+            .try_fold(0_u32, |number, c| {
+                let number = number * 10 + c.to_digit(10)?;
+                (number <= MAX_REPETITION).then_some(number)
+            });
+        self.pos += digits;
+
+        (digits > 0).then_some(number).flatten()
+    }
+
+    /// Push the `atom` to the `lexemes` repeated like the quantifier following it
+    fn parse_quantifier(
+        &mut self,
+        atom: Vec<Lexeme<char>>,
+        lexemes: &mut Vec<Lexeme<char>>,
+    ) -> Result<(), SimilarError> {
+        let start = self.pos;
+        let (min, max) = match self.chars.get(start) {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                self.parse_bounds(start)?
+            }
+            _ => {
+                lexemes.extend(atom);
+                return Ok(());
+            }
+        };
+        if self.pos == start {
+            self.pos += 1;
+        }
+        if matches!(self.chars.get(self.pos), Some('*' | '+' | '?' | '{')) {
+            return Err(SimilarError::MisplacedQuantifier(self.offset(self.pos)));
+        }
+
+        let wrap = |extglob, lexemes: &mut Vec<Lexeme<char>>| {
+            lexemes.push(Lexeme::ExtglobOpen(extglob));
+            lexemes.extend(atom.iter().cloned());
+            lexemes.push(Lexeme::ExtglobClose);
+        };
+        match (min, max) {
+            (1, None) => wrap(Extglob::OneOrMore, lexemes),
+            (min, max) => {
+                let optional = max.map_or(1, |max| max - min) as usize;
+                if (min as usize + optional) * (atom.len() + 2) > MAX_SIZE {
+                    return Err(SimilarError::PatternTooLarge);
+                }
+                for _ in 0..min {
+                    lexemes.extend(atom.iter().cloned());
+                }
+                for _ in 0..optional {
+                    wrap(
+                        if max.is_some() {
+                            Extglob::ZeroOrOne
+                        } else {
+                            Extglob::ZeroOrMore
+                        },
+                        lexemes,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Similar {
+    /// Returns `true` if the `string` matches this pattern.
+    #[must_use]
+    pub fn is_match(&self, string: &str) -> bool {
+        self.0.is_match_str(string)
+    }
+
+    /// Parse the `pattern` of a `SIMILAR TO` with the optional `escape` character.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SimilarError`] if the `pattern` is malformed.
+    pub fn new(pattern: &str, escape: Option<char>) -> Result<Self, SimilarError> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            escape,
+            offsets: pattern.char_indices().map(|(offset, _)| offset).collect(),
+            pattern,
+            pos: 0,
+        };
+
+        let mut lexemes = vec![Lexeme::ExtglobOpen(Extglob::One)];
+        lexemes.extend(parser.parse_alternatives(None)?);
+        lexemes.push(Lexeme::ExtglobClose);
+
+        Ok(Self(Pattern::from_lexemes(lexemes, Options::new())))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SimilarError {}
+
+impl Display for SimilarError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidEscapeSequence(index) => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The escape character at byte {index} must be followed \
+                 by a special character or the escape character."
+            ),
+            Self::InvalidRepetition(index) => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The repetition at byte {index} is malformed."
+            ),
+            Self::MisplacedQuantifier(index) => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The quantifier at byte {index} has nothing to repeat."
+            ),
+            Self::PatternTooLarge => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The repetitions of the pattern are too large."
+            ),
+            Self::TrailingEscape => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The pattern must not end with the escape character."
+            ),
+            Self::UnmatchedParenthesis(index) => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The parenthesis at byte {index} is unmatched."
+            ),
+            Self::UnterminatedBracket(index) => write!(
+                f,
+                "Invalid SIMILAR TO pattern: The bracket expression at byte {index} is not \
+                 terminated."
+            ),
+        }
+    }
+}
+
+/// Returns `true` if `c` has a special meaning in a `SIMILAR TO` pattern
+const fn is_special(c: char) -> bool {
+    matches!(
+        c,
+        '%' | '_' | '|' | '*' | '+' | '?' | '{' | '}' | '(' | ')' | '['
+    )
+}
+
+/// Returns `true` if the `string` matches the `pattern` of a `SIMILAR TO`.
+///
+/// # Errors
+///
+/// Returns a [`SimilarError`] if the `pattern` is malformed.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::similar::similar_to;
+///
+/// assert_eq!(similar_to("%(b|d)%", "abc", None), Ok(true));
+/// assert_eq!(similar_to("(b|d)%", "abc", None), Ok(false));
+/// ```
+pub fn similar_to(pattern: &str, string: &str, escape: Option<char>) -> Result<bool, SimilarError> {
+    Similar::new(pattern, escape).map(|similar| similar.is_match(string))
+}
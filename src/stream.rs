@@ -0,0 +1,168 @@
+//! The [`StreamMatcher`] to match a haystack which arrives in chunks
+
+#[cfg(feature = "std")]
+use std::io::{self, ErrorKind, Read};
+
+use crate::program::{Lexeme, Program, Stream};
+use crate::{diagnostic, is_normalized, Options, Pattern, SimpleMatchError, Wildcard};
+
+/// Matches a wildcard pattern against a haystack which arrives in chunks
+///
+/// The chunks are passed to [`StreamMatcher::feed`] one after another and
+/// [`StreamMatcher::finish`] returns whether the whole haystack matched like
+/// [`dowild_with`](crate::dowild_with). Instead of the haystack, the `StreamMatcher` only
+/// keeps the positions in the pattern which are still possible, so its memory doesn't grow
+/// with the haystack. As soon as the verdict is certain, for example because the pattern ends
+/// with a `*` whose prefix already matched or because nothing can match anymore, `feed`
+/// returns it and the rest of the haystack is skipped.
+///
+/// Patterns with a `!(...)` extglob need to look ahead in the haystack and Unicode
+/// normalization needs to see the whole haystack, so these can't be matched in chunks.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{Options, StreamMatcher};
+///
+/// let mut matcher = StreamMatcher::new(b"GET /*", Options::default()).unwrap();
+///
+/// assert_eq!(matcher.feed(b"GET "), None);
+/// assert_eq!(matcher.feed(b"/index.html"), Some(true));
+/// assert_eq!(matcher.finish(), true);
+///
+/// assert_eq!(matcher.feed(b"POST /"), Some(false));
+/// assert_eq!(matcher.finish(), false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamMatcher<T>
+where
+    T: Wildcard,
+{
+    program: Program<T>,
+    stream: Stream<T>,
+}
+
+impl<T> StreamMatcher<T>
+where
+    T: Wildcard + Ord,
+{
+    /// Compile the `pattern` with the given [`Options`] into a new `StreamMatcher`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Options`] are invalid. See also [`Options::verify`]. If
+    /// [`Options::is_strict`] is set, returns an error if the `pattern` is malformed, too. See
+    /// also [`validate`](crate::validate). Returns [`SimpleMatchError::NotStreamable`] if the
+    /// `pattern` contains a `!(...)` extglob or the [`Options`] normalize the haystack.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, SimpleMatchError, StreamMatcher};
+    ///
+    /// let options = Options::default().enable_extglob(true);
+    ///
+    /// assert!(StreamMatcher::new(b"@(a|b)*", options).is_ok());
+    /// assert_eq!(
+    ///     StreamMatcher::new(b"!(a|b)*", options).unwrap_err(),
+    ///     SimpleMatchError::NotStreamable
+    /// );
+    /// ```
+    pub fn new(pattern: &[T], options: Options<T>) -> Result<Self, SimpleMatchError> {
+        options.verify()?;
+        diagnostic::validate_strict(pattern, options)?;
+        if is_normalized(&options) {
+            return Err(SimpleMatchError::NotStreamable);
+        }
+
+        let mut lexemes = Pattern::lexemes(pattern, options);
+        if let (Some(separator), true) = (options.path_separator, options.is_globstar_enabled) {
+            lexemes = Lexeme::resolve_globstars(lexemes, separator);
+        }
+        let program = Program::new(lexemes, options);
+        if !program.is_streamable() {
+            return Err(SimpleMatchError::NotStreamable);
+        }
+
+        let stream = program.stream();
+        Ok(Self { program, stream })
+    }
+
+    /// Match the next `chunk` of the haystack.
+    ///
+    /// Returns the verdict as soon as it is certain whatever the rest of the haystack is.
+    /// Further chunks don't change it then and are skipped until [`StreamMatcher::finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, StreamMatcher};
+    ///
+    /// let mut matcher = StreamMatcher::new(b"*.log", Options::default()).unwrap();
+    ///
+    /// assert_eq!(matcher.feed(b"debug.l"), None);
+    /// assert_eq!(matcher.feed(b"og"), None);
+    /// assert_eq!(matcher.finish(), true);
+    /// ```
+    pub fn feed(&mut self, chunk: &[T]) -> Option<bool> {
+        self.program.feed(&mut self.stream, chunk)
+    }
+
+    /// Returns the verdict if it is already certain whatever the rest of the haystack is.
+    #[must_use]
+    pub const fn verdict(&self) -> Option<bool> {
+        self.stream.verdict()
+    }
+
+    /// Returns `true` if the pattern matches the haystack fed so far.
+    ///
+    /// The `StreamMatcher` is reset afterwards, so it can match the next haystack.
+    pub fn finish(&mut self) -> bool {
+        let is_match = self.program.is_stream_match(&self.stream);
+        self.program.restart(&mut self.stream);
+        is_match
+    }
+}
+
+#[cfg(feature = "std")]
+impl StreamMatcher<u8> {
+    /// Match all bytes from the `reader` until its end or until the verdict is certain.
+    ///
+    /// Returns the verdict if it is certain like [`StreamMatcher::feed`]. The haystack is not
+    /// finished, so more bytes can be fed before calling [`StreamMatcher::finish`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the `reader` except for [`ErrorKind::Interrupted`], in which
+    /// case reading is retried.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, StreamMatcher};
+    ///
+    /// let mut matcher = StreamMatcher::new(b"*ERROR*", Options::default()).unwrap();
+    ///
+    /// let log = "INFO started\nERROR failed\nINFO stopped\n";
+    /// assert_eq!(matcher.feed_reader(log.as_bytes()).unwrap(), Some(true));
+    /// assert_eq!(matcher.finish(), true);
+    /// ```
+    pub fn feed_reader<R>(&mut self, mut reader: R) -> io::Result<Option<bool>>
+    where
+        R: Read,
+    {
+        let mut buffer = [0; 8192];
+        while self.verdict().is_none() {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => {
+                    self.feed(&buffer[..len]);
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(self.verdict())
+    }
+}
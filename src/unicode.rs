@@ -0,0 +1,43 @@
+//! The Unicode case folding of the `unicode` feature
+//!
+//! The tables are embedded in the crate, so the case folding works in `no_std` environments,
+//! too. They are generated from the Unicode Character Database with `cargo xtask
+//! generate-case-folding`. The language specific turkic mappings are not included.
+
+mod tables;
+
+use core::iter;
+
+pub use tables::FULL_CASE_FOLDING;
+use tables::{CASE_ORBITS, SIMPLE_CASE_FOLDING};
+
+/// Returns all characters except `c` itself which have the same simple case folding as `c`
+///
+/// For example, the orbit of `k` consists of `K` and the Kelvin sign `K`.
+pub fn case_orbit(c: char) -> impl Iterator<Item = char> {
+    let mut next = next_in_orbit(c);
+    iter::from_fn(move || {
+        let current = next.filter(|n| *n != c)?;
+        next = next_in_orbit(current);
+        Some(current)
+    })
+}
+
+/// Returns the simple case folding of `c` which is a single character
+///
+/// For example, `Σ` and `ς` fold to `σ`.
+pub fn fold_simple(c: char) -> char {
+    if c.is_ascii() {
+        return c.to_ascii_lowercase();
+    }
+    SIMPLE_CASE_FOLDING
+        .binary_search_by_key(&c, |(from, _)| *from)
+        .map_or(c, |index| SIMPLE_CASE_FOLDING[index].1)
+}
+
+fn next_in_orbit(c: char) -> Option<char> {
+    CASE_ORBITS
+        .binary_search_by_key(&c, |(from, _)| *from)
+        .ok()
+        .map(|index| CASE_ORBITS[index].1)
+}
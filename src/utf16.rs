@@ -0,0 +1,298 @@
+//! Surrogate aware matching on the code units of UTF-16
+//!
+//! The [`DoWild`](crate::DoWild) implementation of `[u16]` matches the code units, so a `?`
+//! consumes a single surrogate of a character outside of the Basic Multilingual Plane like an
+//! emoji. The functions of this module match the code points instead. The haystack is decoded
+//! on the fly without allocating, so `?`, classes and ranges match a whole surrogate pair and
+//! every reported offset is the index of a code unit on a code point boundary of the haystack:
+//!
+//! ```rust
+//! use simplematch::{utf16, DoWild, Options};
+//!
+//! let pattern = "?".encode_utf16().collect::<Vec<u16>>();
+//! let haystack = "🦀".encode_utf16().collect::<Vec<u16>>();
+//!
+//! assert_eq!(pattern.as_slice().dowild(&haystack), false);
+//! assert_eq!(utf16::dowild(&pattern, &haystack), true);
+//! ```
+//!
+//! A surrogate without its counterpart is decoded as the replacement character `U+FFFD` in
+//! the pattern and in the haystack, so a `?` matches it, too. The pattern is decoded into
+//! characters on every call. If the same pattern is used multiple times, it is more efficient
+//! to create a [`Pattern`] of characters once and use [`Pattern::is_match_utf16`] and its
+//! siblings.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::haystack::Haystack;
+use crate::{Options, Pattern};
+
+/// The code units of UTF-16 which are matched code point by code point
+struct Utf16<'h>(&'h [u16]);
+
+impl Haystack<char> for Utf16<'_> {
+    const IS_INDEXED: bool = false;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        let first = *self.0.get(pos)?;
+        if let Some(c) = char::from_u32(u32::from(first)) {
+            return Some((c, pos + 1));
+        }
+
+        match self
+            .0
+            .get(pos + 1)
+            .and_then(|second| decode_pair(first, *second))
+        {
+            Some(c) => Some((c, pos + 2)),
+            None => Some((char::REPLACEMENT_CHARACTER, pos + 1)),
+        }
+    }
+
+    #[inline]
+    fn prev(&self, pos: usize) -> Option<(char, usize)> {
+        let last = *self.0.get(pos.checked_sub(1)?)?;
+        if let Some(c) = char::from_u32(u32::from(last)) {
+            return Some((c, pos - 1));
+        }
+
+        match pos
+            .checked_sub(2)
+            .and_then(|first| decode_pair(self.0[first], last))
+        {
+            Some(c) => Some((c, pos - 2)),
+            None => Some((char::REPLACEMENT_CHARACTER, pos - 1)),
+        }
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Pattern<char> {
+    /// Returns the spans of the `haystack` matched by each `*`, `?` and character class like
+    /// [`Pattern::captures`] but on the code points of the UTF-16 `haystack`.
+    ///
+    /// The spans are code unit indices on code point boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['*', '?'], Options::default()).unwrap();
+    /// let haystack = "a🦀".encode_utf16().collect::<Vec<u16>>();
+    ///
+    /// assert_eq!(pattern.captures_utf16(&haystack), Some(vec![0..1, 1..3]));
+    /// ```
+    #[must_use]
+    pub fn captures_utf16(&self, haystack: &[u16]) -> Option<Vec<Range<usize>>> {
+        self.captures_in(&Utf16(haystack))
+    }
+
+    /// Returns the leftmost and shortest span of the `haystack` matched by this `Pattern` like
+    /// [`Pattern::find`] but on the code points of the UTF-16 `haystack`.
+    ///
+    /// The span is a range of code units on code point boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['?', '!'], Options::default()).unwrap();
+    /// let haystack = "a🦀!".encode_utf16().collect::<Vec<u16>>();
+    ///
+    /// assert_eq!(pattern.find_utf16(&haystack), Some(1..4));
+    /// ```
+    #[must_use]
+    pub fn find_utf16(&self, haystack: &[u16]) -> Option<Range<usize>> {
+        self.find_leftmost(&Utf16(haystack), false)
+    }
+
+    /// Returns the leftmost and longest span of the `haystack` matched by this `Pattern` like
+    /// [`Pattern::find_longest`] but on the code points of the UTF-16 `haystack`.
+    ///
+    /// The span is a range of code units on code point boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['b', '*'], Options::default()).unwrap();
+    /// let haystack = "ab🦀".encode_utf16().collect::<Vec<u16>>();
+    ///
+    /// assert_eq!(pattern.find_longest_utf16(&haystack), Some(1..4));
+    /// ```
+    #[must_use]
+    pub fn find_longest_utf16(&self, haystack: &[u16]) -> Option<Range<usize>> {
+        self.find_leftmost(&Utf16(haystack), true)
+    }
+
+    /// Returns `true` if this `Pattern` matches the code points of the UTF-16 `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['?', '?'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.is_match_utf16(&[0x61, 0xd83e, 0xdd80]), true);
+    /// assert_eq!(pattern.is_match_utf16(&[0xd83e, 0xdd80]), false);
+    /// ```
+    #[must_use]
+    pub fn is_match_utf16(&self, haystack: &[u16]) -> bool {
+        self.is_match_in(&Utf16(haystack))
+    }
+}
+
+/// Returns the spans of the `haystack` matched by each `*`, `?` and character class of the
+/// `pattern` or `None` if the `pattern` doesn't match.
+///
+/// Like [`captures`](crate::captures) but on the code points of UTF-16. The spans are code
+/// unit indices on code point boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf16, Options};
+///
+/// let pattern = "*.?".encode_utf16().collect::<Vec<u16>>();
+/// let haystack = "a.🦀".encode_utf16().collect::<Vec<u16>>();
+///
+/// assert_eq!(
+///     utf16::captures(&pattern, &haystack, Options::default()),
+///     Some(vec![0..1, 2..4])
+/// );
+/// ```
+#[must_use]
+pub fn captures(
+    pattern: &[u16],
+    haystack: &[u16],
+    options: Options<char>,
+) -> Option<Vec<Range<usize>>> {
+    parse(pattern, options).captures_utf16(haystack)
+}
+
+/// Returns `true` if the wildcard `pattern` matches the code points of the `haystack`.
+///
+/// Like [`dowild`](crate::dowild) but a `?` matches a whole surrogate pair instead of a single
+/// code unit.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::utf16;
+///
+/// let haystack = "🦀".encode_utf16().collect::<Vec<u16>>();
+///
+/// assert_eq!(utf16::dowild(&[0x3f], &haystack), true);
+/// assert_eq!(utf16::dowild(&[0x3f, 0x3f], &haystack), false);
+/// ```
+#[must_use]
+pub fn dowild(pattern: &[u16], haystack: &[u16]) -> bool {
+    dowild_with(pattern, haystack, Options::default())
+}
+
+/// Returns `true` if the wildcard `pattern` matches the code points of the `haystack`. This
+/// function can be customized with [`Options`].
+///
+/// Like [`dowild_with`](crate::dowild_with) but a `?`, a class and a range match a whole
+/// surrogate pair instead of a single code unit.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf16, Options};
+///
+/// let options = Options::default().enable_classes(true);
+/// let pattern = "[🦀-🦅]*".encode_utf16().collect::<Vec<u16>>();
+/// let haystack = "🦀 crab".encode_utf16().collect::<Vec<u16>>();
+///
+/// assert_eq!(utf16::dowild_with(&pattern, &haystack, options), true);
+/// ```
+#[must_use]
+pub fn dowild_with(pattern: &[u16], haystack: &[u16], options: Options<char>) -> bool {
+    crate::dowild_with_in(&decode(pattern), &Utf16(haystack), options)
+}
+
+/// Returns the leftmost and shortest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`find`](crate::find) but on the code points of UTF-16. The span is a range of code
+/// units on code point boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf16, Options};
+///
+/// let haystack = "🦀b".encode_utf16().collect::<Vec<u16>>();
+///
+/// assert_eq!(
+///     utf16::find(&[0x3f, 0x62], &haystack, Options::default()),
+///     Some(0..3)
+/// );
+/// ```
+#[must_use]
+pub fn find(pattern: &[u16], haystack: &[u16], options: Options<char>) -> Option<Range<usize>> {
+    parse(pattern, options).find_utf16(haystack)
+}
+
+/// Returns the leftmost and longest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`find_longest`](crate::find_longest) but on the code points of UTF-16. The span is a
+/// range of code units on code point boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf16, Options};
+///
+/// let haystack = "a🦀!".encode_utf16().collect::<Vec<u16>>();
+///
+/// assert_eq!(
+///     utf16::find_longest(&[0x3f, 0x2a], &haystack, Options::default()),
+///     Some(0..4)
+/// );
+/// ```
+#[must_use]
+pub fn find_longest(
+    pattern: &[u16],
+    haystack: &[u16],
+    options: Options<char>,
+) -> Option<Range<usize>> {
+    parse(pattern, options).find_longest_utf16(haystack)
+}
+
+/// Returns the characters of the UTF-16 `pattern` with unpaired surrogates replaced
+fn decode(pattern: &[u16]) -> Vec<char> {
+    char::decode_utf16(pattern.iter().copied())
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Returns the character of the surrogate pair `first` and `second` or `None` if they are no
+/// valid pair
+fn decode_pair(first: u16, second: u16) -> Option<char> {
+    if (0xd800..0xdc00).contains(&first) && (0xdc00..0xe000).contains(&second) {
+        let high = u32::from(first - 0xd800);
+        let low = u32::from(second - 0xdc00);
+        char::from_u32(0x1_0000 + (high << 10) + low)
+    } else {
+        None
+    }
+}
+
+fn parse(pattern: &[u16], options: Options<char>) -> Pattern<char> {
+    Pattern::parse(&decode(pattern), options)
+}
@@ -0,0 +1,308 @@
+//! UTF-8 aware matching directly on a `str`
+//!
+//! The [`DoWild`](crate::DoWild) implementation of `str` matches the bytes of the strings, so
+//! a `?` consumes a single byte and a class like `[äöü]` consists of the bytes of its
+//! characters. The functions of this module match the characters of the strings instead. The
+//! haystack is decoded on the fly without allocating, so `?`, classes and ranges match a
+//! single code point and every reported offset is a byte offset on a character boundary of
+//! the haystack:
+//!
+//! ```rust
+//! use simplematch::{utf8, DoWild, Options};
+//!
+//! assert_eq!("?".dowild("é"), false);
+//! assert_eq!(utf8::dowild("?", "é"), true);
+//!
+//! let options = Options::default().enable_classes(true);
+//! assert_eq!(utf8::dowild_with("gr[äöü]n", "grün", options), true);
+//! assert_eq!(
+//!     utf8::captures("*?", "café", Options::default()),
+//!     Some(vec![0..3, 3..5])
+//! );
+//! ```
+//!
+//! The pattern is collected into characters on every call. If the same pattern is used
+//! multiple times, it is more efficient to create a [`Pattern`] of characters once and use
+//! [`Pattern::is_match_str`] and its siblings.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::{Options, Pattern, SimpleMatchError};
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Pattern<char> {
+    /// Returns the spans of the `haystack` matched by each `*`, `?` and character class like
+    /// [`Pattern::captures`] but on the characters of the `haystack`.
+    ///
+    /// The spans are byte offsets on character boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['*', '?'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.captures_str("café"), Some(vec![0..3, 3..5]));
+    /// ```
+    #[must_use]
+    pub fn captures_str(&self, haystack: &str) -> Option<Vec<Range<usize>>> {
+        self.captures_in(haystack)
+    }
+
+    /// Returns the leftmost and shortest span of the `haystack` matched by this `Pattern` like
+    /// [`Pattern::find`] but on the characters of the `haystack`.
+    ///
+    /// The span is a byte range on character boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['f', '?', 'o'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.find_str("¡fão!"), Some(2..6));
+    /// ```
+    #[must_use]
+    pub fn find_str(&self, haystack: &str) -> Option<Range<usize>> {
+        self.find_leftmost(haystack, false)
+    }
+
+    /// Returns the leftmost and longest span of the `haystack` matched by this `Pattern` like
+    /// [`Pattern::find_longest`] but on the characters of the `haystack`.
+    ///
+    /// The span is a byte range on character boundaries of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['é', '*'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.find_longest_str("café!"), Some(3..6));
+    /// ```
+    #[must_use]
+    pub fn find_longest_str(&self, haystack: &str) -> Option<Range<usize>> {
+        self.find_leftmost(haystack, true)
+    }
+
+    /// Returns `true` if this `Pattern` matches the characters of the `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simplematch::{Options, Pattern};
+    ///
+    /// let pattern = Pattern::new(&['?', '?'], Options::default()).unwrap();
+    ///
+    /// assert_eq!(pattern.is_match_str("äö"), true);
+    /// assert_eq!(pattern.is_match_str("ä"), false);
+    /// ```
+    #[must_use]
+    pub fn is_match_str(&self, haystack: &str) -> bool {
+        self.is_match_in(haystack)
+    }
+}
+
+/// Returns the spans of the `haystack` matched by each `*`, `?` and character class of the
+/// `pattern` or `None` if the `pattern` doesn't match.
+///
+/// Like [`captures`](crate::captures) but on the characters of the strings. The spans are
+/// byte offsets on character boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options};
+///
+/// assert_eq!(
+///     utf8::captures("*.?", "résumé.ü", Options::default()),
+///     Some(vec![0..8, 9..11])
+/// );
+/// ```
+#[must_use]
+pub fn captures(pattern: &str, haystack: &str, options: Options<char>) -> Option<Vec<Range<usize>>> {
+    parse(pattern, options).captures_str(haystack)
+}
+
+/// Returns `true` if the wildcard `pattern` matches the characters of the `haystack`.
+///
+/// Like [`dowild`](crate::dowild) but a `?` matches a single character instead of a single
+/// byte.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::utf8;
+///
+/// assert_eq!(utf8::dowild("caf?", "café"), true);
+/// assert_eq!(utf8::dowild("caf??", "café"), false);
+/// ```
+#[must_use]
+pub fn dowild(pattern: &str, haystack: &str) -> bool {
+    dowild_with(pattern, haystack, Options::default())
+}
+
+/// Returns `true` if the wildcard `pattern` matches the characters of the `haystack`. This
+/// function can be customized with [`Options`].
+///
+/// Like [`dowild_with`](crate::dowild_with) but a `?`, a class and a range match a single
+/// character instead of a single byte.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options};
+///
+/// let options = Options::default().enable_classes(true);
+///
+/// assert_eq!(utf8::dowild_with("[α-ω]*", "λ-calculus", options), true);
+/// assert_eq!(utf8::dowild_with("[α-ω]*", "lambda", options), false);
+/// ```
+#[must_use]
+pub fn dowild_with(pattern: &str, haystack: &str, options: Options<char>) -> bool {
+    crate::dowild_with_in(&pattern.chars().collect::<Vec<_>>(), haystack, options)
+}
+
+/// Like [`try_dowild_with`](crate::try_dowild_with) but on the characters of the strings.
+///
+/// The span of a [`PatternError`](crate::PatternError) consists of byte offsets on character
+/// boundaries of the `pattern`.
+///
+/// # Errors
+///
+/// See [`try_dowild_with`](crate::try_dowild_with).
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options};
+///
+/// let options = Options::default().enable_classes(true).strict(true);
+///
+/// assert_eq!(
+///     utf8::try_dowild_with("[α-ω]*", "λ-calculus", options),
+///     Ok(true)
+/// );
+/// assert!(utf8::try_dowild_with("[α-ω*", "λ-calculus", options).is_err());
+/// ```
+pub fn try_dowild_with(
+    pattern: &str,
+    haystack: &str,
+    options: Options<char>,
+) -> Result<bool, SimpleMatchError> {
+    crate::try_dowild_with_in(&pattern.chars().collect::<Vec<_>>(), haystack, options)
+        .map_err(|error| into_byte_span(error, pattern))
+}
+
+/// Returns the leftmost and shortest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`find`](crate::find) but on the characters of the strings. The span is a byte range
+/// on character boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options};
+///
+/// assert_eq!(utf8::find("?b", "äb", Options::default()), Some(0..3));
+/// ```
+#[must_use]
+pub fn find(pattern: &str, haystack: &str, options: Options<char>) -> Option<Range<usize>> {
+    parse(pattern, options).find_str(haystack)
+}
+
+/// Returns the leftmost and longest span of the `haystack` matched by the `pattern`.
+///
+/// Like [`find_longest`](crate::find_longest) but on the characters of the strings. The span
+/// is a byte range on character boundaries of the `haystack`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options};
+///
+/// assert_eq!(
+///     utf8::find_longest("ü*", "grün!", Options::default()),
+///     Some(2..6)
+/// );
+/// ```
+#[must_use]
+pub fn find_longest(pattern: &str, haystack: &str, options: Options<char>) -> Option<Range<usize>> {
+    parse(pattern, options).find_longest_str(haystack)
+}
+
+/// Translate the `pattern` to a regular expression which matches the same strings.
+///
+/// Like [`to_regex_string`](crate::to_regex_string) but for the characters of the `pattern`.
+/// The regex matches a `str` with the Unicode mode of the `regex` crate.
+///
+/// # Errors
+///
+/// See [`to_regex_string`](crate::to_regex_string).
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options};
+///
+/// assert_eq!(
+///     utf8::to_regex_string("gr?n*", Options::default()).unwrap(),
+///     "(?s)^gr.n.*$"
+/// );
+/// ```
+pub fn to_regex_string(pattern: &str, options: Options<char>) -> Result<String, SimpleMatchError> {
+    crate::to_regex_string(&pattern.chars().collect::<Vec<_>>(), options)
+}
+
+/// Like [`validate`](crate::validate) but for the characters of the `pattern`.
+///
+/// The span of the [`PatternError`](crate::PatternError) consists of byte offsets on character
+/// boundaries of the `pattern`.
+///
+/// # Errors
+///
+/// See [`validate`](crate::validate).
+///
+/// # Examples
+///
+/// ```rust
+/// use simplematch::{utf8, Options, SimpleMatchError};
+///
+/// let options = Options::default().enable_classes(true);
+///
+/// let error = match utf8::validate("ä[bc", options) {
+///     Err(SimpleMatchError::InvalidPattern(error)) => error,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(error.span(), 2..5);
+/// ```
+pub fn validate(pattern: &str, options: Options<char>) -> Result<(), SimpleMatchError> {
+    crate::validate(&pattern.chars().collect::<Vec<_>>(), options)
+        .map_err(|error| into_byte_span(error, pattern))
+}
+
+fn parse(pattern: &str, options: Options<char>) -> Pattern<char> {
+    Pattern::parse(&pattern.chars().collect::<Vec<_>>(), options)
+}
+
+/// Convert the span of an [`SimpleMatchError::InvalidPattern`] to byte offsets of the
+/// `pattern`
+fn into_byte_span(error: SimpleMatchError, pattern: &str) -> SimpleMatchError {
+    match error {
+        SimpleMatchError::InvalidPattern(error) => {
+            SimpleMatchError::InvalidPattern(error.into_byte_span(pattern))
+        }
+        error => error,
+    }
+}
@@ -0,0 +1,190 @@
+use rstest::rstest;
+use simplematch::{captures, dowild_with, find, find_longest, Options, Pattern, SimpleMatchError};
+
+// spell-checker: disable
+#[rstest]
+#[case::first("*.{rs,toml}", "main.rs", true)]
+#[case::second("*.{rs,toml}", "Cargo.toml", true)]
+#[case::none("*.{rs,toml}", "Cargo.lock", false)]
+#[case::prefix_of_alternative("*.{rs,toml}", "main.r", false)]
+#[case::middle("src/{lib,main}.rs", "src/main.rs", true)]
+#[case::single("{a}", "a", true)]
+#[case::empty_group("a{}b", "ab", true)]
+#[case::empty_alternative_first("a{,b}", "a", true)]
+#[case::empty_alternative_second("a{,b}", "ab", true)]
+#[case::empty_alternative_last("a{b,}", "a", true)]
+#[case::nested("{src/{lib,main},build}.rs", "src/lib.rs", true)]
+#[case::nested_outer("{src/{lib,main},build}.rs", "build.rs", true)]
+#[case::nested_mismatch("{src/{lib,main},build}.rs", "src/build.rs", false)]
+#[case::wildcards_inside("{*.rs,?.md}", "a.md", true)]
+#[case::wildcards_inside_fail("{*.rs,?.md}", "ab.md", false)]
+#[case::many_groups("{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}", "abbaba", true)]
+#[case::many_groups_fail("{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}", "abbabc", false)]
+#[case::backtrack_into_group("{a,ab}c", "abc", true)]
+#[case::star_after_group("{a,ab}*b", "ab", true)]
+#[case::unclosed_open("{a,b", "{a,b", true)]
+#[case::unclosed_open_no_alternative("{a,b", "a", false)]
+#[case::unmatched_close("a}", "a}", true)]
+#[case::separator_outside("a,b", "a,b", true)]
+#[case::unclosed_outer("{{a,b}", "{b", true)]
+#[case::unclosed_inner_separator_literal("{a,{b}", "{a,b", true)]
+#[case::class_with_separator("{[,]x,y}", ",x", true)]
+#[case::class_with_close("{[}]x,y}", "}x", true)]
+#[case::escaped_open("\\{a,b}", "{a,b}", true)]
+#[case::escaped_separator("{a\\,b,c}", "a,b", true)]
+#[case::escaped_separator_no_split("{a\\,b,c}", "b", false)]
+#[case::escaped_close("{a\\},b}", "a}", true)]
+// spell-checker: enable
+fn dowild_with_braces(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new()
+        .enable_braces(true)
+        .enable_classes(true)
+        .enable_escape(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+
+    let chars = pattern.chars().collect::<Vec<char>>();
+    let char_options = Options::new()
+        .enable_braces(true)
+        .enable_classes(true)
+        .enable_escape(true);
+    let compiled = Pattern::new(&chars, char_options).unwrap();
+
+    assert_eq!(
+        compiled.is_match(&haystack.chars().collect::<Vec<char>>()),
+        expected
+    );
+}
+
+#[rstest]
+#[case::disabled("*.{rs,toml}", "main.rs", false)]
+#[case::disabled_literal("{a,b}", "{a,b}", true)]
+fn dowild_with_braces_disabled(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), Options::new()),
+        expected
+    );
+}
+
+#[rstest]
+#[case::same_case("{foo,bar}*", "BARbaz", true)]
+#[case::escaped_and_literal("{\\*A,b}", "*a", true)]
+fn dowild_with_braces_when_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .case_insensitive(true)
+        .enable_braces(true)
+        .enable_escape(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::group("*.{rs,toml}", "Cargo.toml", Some(vec![(0, 5), (6, 10)]))]
+#[case::first_alternative_wins("{a*,*}", "abc", Some(vec![(0, 3)]))]
+#[case::wildcards_inside_dont_capture("?{*.rs,?.md}*", "xa.md", Some(vec![(0, 1), (1, 5), (5, 5)]))]
+#[case::empty_alternative("a{,b}?", "ab", Some(vec![(1, 1), (1, 2)]))]
+#[case::no_match("*.{rs,toml}", "Cargo.lock", None)]
+// spell-checker: enable
+fn captures_with_braces(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: Option<Vec<(usize, usize)>>,
+) {
+    let expected = expected.map(|spans| {
+        spans
+            .into_iter()
+            .map(|(start, end)| start..end)
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(
+        captures(
+            pattern.as_bytes(),
+            haystack.as_bytes(),
+            Options::new().enable_braces(true)
+        ),
+        expected
+    );
+}
+
+#[test]
+fn captures_with_braces_in_large_haystack() {
+    let mut haystack = vec![b'a'; 200_000];
+    haystack.extend(b"bxc");
+    let options = Options::new().enable_braces(true);
+
+    assert_eq!(
+        captures(b"*{ab,b}*{c,d}", &haystack, options),
+        Some(vec![
+            0..199_999,
+            199_999..200_001,
+            200_001..200_002,
+            200_002..200_003
+        ])
+    );
+    haystack.push(b'x');
+    assert_eq!(captures(b"*{ab,b}*{c,d}", &haystack, options), None);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::group("{ERROR,WARN}:*;", "INFO: a; WARN: b; ERROR: c;", Some((9, 17)), Some((9, 27)))]
+#[case::alternatives_of_different_length("{ab,a}", "xab", Some((1, 2)), Some((1, 3)))]
+#[case::no_match("{x,y}", "abc", None, None)]
+// spell-checker: enable
+fn find_with_braces(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] shortest: Option<(usize, usize)>,
+    #[case] longest: Option<(usize, usize)>,
+) {
+    let options = Options::new().enable_braces(true);
+
+    assert_eq!(
+        find(pattern.as_bytes(), haystack.as_bytes(), options),
+        shortest.map(|(start, end)| start..end)
+    );
+    assert_eq!(
+        find_longest(pattern.as_bytes(), haystack.as_bytes(), options),
+        longest.map(|(start, end)| start..end)
+    );
+}
+
+#[test]
+fn pattern_with_braces_find_iter() {
+    let pattern = Pattern::new(b"{cat,dog}", Options::new().enable_braces(true)).unwrap();
+
+    assert_eq!(
+        pattern
+            .find_iter(b"a cat, a dog, a cow")
+            .collect::<Vec<_>>(),
+        [2..5, 9..12]
+    );
+}
+
+#[rstest]
+#[case::any(Options::new().wildcard_any_with(b'{'))]
+#[case::one(Options::new().wildcard_one_with(b','))]
+#[case::escape(Options::new().enable_escape_with(b'}'))]
+fn options_verify_when_braces_enabled(#[case] options: Options<u8>) {
+    assert_eq!(options.verify(), Ok(()));
+    assert_eq!(
+        options.enable_braces(true).verify(),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
@@ -0,0 +1,78 @@
+use rstest::rstest;
+use simplematch::{captures, Options, Pattern};
+
+// spell-checker: disable
+#[rstest]
+#[case::no_wildcards("abc", "abc", &[])]
+#[case::star_empty("*", "", &[(0, 0)])]
+#[case::star_all("*", "abc", &[(0, 3)])]
+#[case::extension("*.tar.gz", "backup.tar.gz", &[(0, 6)])]
+#[case::star_and_one("*.tar.?z", "backup.tar.gz", &[(0, 6), (11, 12)])]
+#[case::first_star_is_lazy("*.*", "a.b.c", &[(0, 1), (2, 5)])]
+#[case::consecutive_stars("a**c", "abbc", &[(1, 1), (1, 3)])]
+#[case::star_then_ones("*??", "abcd", &[(0, 2), (2, 3), (3, 4)])]
+#[case::star_then_ones_then_literal("*??b", "xxyb", &[(0, 1), (1, 2), (2, 3)])]
+#[case::star_then_one_then_star("*?*", "abc", &[(0, 0), (0, 1), (1, 3)])]
+#[case::backtracking("*ab", "aabab", &[(0, 3)])]
+#[case::multiple_stars("*issip*ss*", "mississipissippi", &[(0, 4), (9, 10), (12, 16)])]
+#[case::ones("a?c?", "abcd", &[(1, 2), (3, 4)])]
+// spell-checker: enable
+fn captures_with_default_options(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: &[(usize, usize)],
+) {
+    assert_eq!(
+        captures(pattern.as_bytes(), haystack.as_bytes(), Options::new()),
+        Some(to_ranges(expected))
+    );
+    assert_eq!(
+        Pattern::new(pattern.as_bytes(), Options::new())
+            .unwrap()
+            .captures(haystack.as_bytes()),
+        Some(to_ranges(expected))
+    );
+}
+
+#[rstest]
+#[case::class("[a-z]*", "abc", &[(0, 1), (1, 3)])]
+#[case::class_after_star("*[0-9]", "ab1", &[(0, 2), (2, 3)])]
+#[case::invalid_class_is_literal("*[a", "x[a", &[(0, 1)])]
+#[case::escaped_star("\\**", "*ab", &[(1, 3)])]
+#[case::escaped_one("?\\?", "a?", &[(0, 1)])]
+#[case::case_insensitive("*.TXT", "notes.txt", &[(0, 5)])]
+fn captures_with_options(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: &[(usize, usize)],
+) {
+    let options = Options::new()
+        .case_insensitive(true)
+        .enable_classes(true)
+        .enable_escape(true);
+
+    assert_eq!(
+        captures(pattern.as_bytes(), haystack.as_bytes(), options),
+        Some(to_ranges(expected))
+    );
+}
+
+#[test]
+fn captures_with_char() {
+    let pattern = "*ö?".chars().collect::<Vec<char>>();
+    let haystack = "äöü".chars().collect::<Vec<char>>();
+
+    assert_eq!(
+        captures(&pattern, &haystack, Options::new()),
+        Some(to_ranges(&[(0, 1), (2, 3)]))
+    );
+}
+
+#[test]
+fn captures_when_no_match() {
+    assert_eq!(captures(b"a*c", b"abd", Options::new()), None);
+}
+
+fn to_ranges(spans: &[(usize, usize)]) -> Vec<core::ops::Range<usize>> {
+    spans.iter().map(|(start, end)| *start..*end).collect()
+}
@@ -1,7 +1,7 @@
 //! The tests
 
 use rstest::rstest;
-use simplematch::{dowild_with, DoWild, Options};
+use simplematch::{dowild_with, DoWild, NamedClass, Options, Pattern};
 
 #[test]
 fn impl_for_str() {
@@ -260,6 +260,230 @@ fn dowild_with_range_and_wildcards(
     }
 }
 
+// spell-checker: disable
+#[rstest]
+#[case::alnum("[[:alnum:]]", &["a", "Z", "0"], &[" ", "-", "_"])]
+#[case::alpha("[[:alpha:]]", &["a", "Z"], &["0", " ", "-"])]
+#[case::blank("[[:blank:]]", &[" ", "\t"], &["\n", "a"])]
+#[case::cntrl("[[:cntrl:]]", &["\0", "\n", "\x7f"], &[" ", "a"])]
+#[case::digit("[[:digit:]]", &["0", "5", "9"], &["a", "x"])]
+#[case::graph("[[:graph:]]", &["a", "!", "~"], &[" ", "\n"])]
+#[case::lower("[[:lower:]]", &["a", "z"], &["A", "0"])]
+#[case::print("[[:print:]]", &["a", " ", "~"], &["\n", "\x7f"])]
+#[case::punct("[[:punct:]]", &["!", "-", "_", "~"], &["a", "0", " "])]
+#[case::space("[[:space:]]", &[" ", "\t", "\n", "\r", "\x0b", "\x0c"], &["a", "_"])]
+#[case::upper("[[:upper:]]", &["A", "Z"], &["a", "0"])]
+#[case::xdigit("[[:xdigit:]]", &["0", "a", "F"], &["g", "G"])]
+#[case::negated("[![:alnum:]_]", &["-", " "], &["a", "0", "_"])]
+#[case::with_range("[[:digit:]a-c]", &["1", "b"], &["d"])]
+#[case::multiple("[[:upper:][:digit:]]", &["A", "1"], &["a"])]
+#[case::not_a_range_start("[[:digit:]-z]", &["1", "-", "z"], &["a"])]
+#[case::unknown_name("[[:foo:]]", &[":]"], &["f", ":"])]
+#[case::uppercase_name("[[:ALPHA:]]", &["A]", ":]"], &["a"])]
+#[case::unterminated_name("[[:alpha]", &["a", ":"], &["b"])]
+#[case::unterminated_class("[[:alpha:]", &["[[:alpha:]"], &["a"])]
+#[case::name_without_brackets("[:alpha:]", &["a", ":"], &["b"])]
+// spell-checker: enable
+fn dowild_with_named_class(
+    #[case] pattern: &str,
+    #[case] matching: &[&str],
+    #[case] not_matching: &[&str],
+) {
+    let options = Options::new().enable_classes(true);
+    let char_options = Options::new().enable_classes(true);
+    let compiled = Pattern::new(pattern.as_bytes(), options).unwrap();
+
+    for (haystacks, expected) in [(matching, true), (not_matching, false)] {
+        for haystack in haystacks {
+            assert_eq!(
+                dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+                expected,
+                "haystack was: {haystack:?}",
+            );
+            assert_eq!(
+                compiled.is_match(haystack.as_bytes()),
+                expected,
+                "compiled haystack was: {haystack:?}",
+            );
+            assert_eq!(
+                dowild_with(
+                    &pattern.chars().collect::<Vec<char>>(),
+                    &haystack.chars().collect::<Vec<char>>(),
+                    char_options
+                ),
+                expected,
+                "char haystack was: {haystack:?}",
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case::default_negate("[!a-c]", &["d", "^", "!"], &["a"])]
+#[case::alternative_negate("[^a-c]", &["d", "!", "^"], &["a"])]
+#[case::close_after_alternative("[^]a]", &["b"], &["]", "a"])]
+#[case::not_at_start("[a^]", &["a", "^"], &["b"])]
+#[case::both("[!^]", &["a"], &["^"])]
+fn dowild_with_class_negate_alternative(
+    #[case] pattern: &str,
+    #[case] matching: &[&str],
+    #[case] not_matching: &[&str],
+) {
+    let options = Options::new()
+        .enable_classes(true)
+        .class_negate_alternative_with(b'^');
+    let compiled = Pattern::new(pattern.as_bytes(), options).unwrap();
+
+    for (haystacks, expected) in [(matching, true), (not_matching, false)] {
+        for haystack in haystacks {
+            assert_eq!(
+                dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+                expected,
+                "haystack was: {haystack:?}",
+            );
+            assert_eq!(
+                compiled.is_match(haystack.as_bytes()),
+                expected,
+                "compiled haystack was: {haystack:?}",
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case::escaped_close("[\\]a]", &["]", "a"], &["\\", "b"])]
+#[case::escaped_hyphen("[a\\-z]", &["a", "-", "z"], &["x", "\\"])]
+#[case::escaped_escape("[\\\\a]", &["\\", "a"], &["b"])]
+#[case::escaped_literal("[\\a]", &["a"], &["\\", "b"])]
+#[case::escaped_first_is_no_range_start("[\\a-c]", &["a", "-", "c"], &["b"])]
+#[case::negated("[!\\]]", &["a"], &["]"])]
+#[case::named("[\\[:digit:]]", &["[]", ":]", "d]"], &["1", "1]"])]
+#[case::unclosed("[\\]", &["[\\]"], &["]", "\\"])]
+#[case::trailing_escape("[a\\", &["[a\\"], &["a"])]
+#[case::with_wildcards("*[\\*\\?]", &["a*", "b?"], &["a", "\\"])]
+fn dowild_with_class_escape(
+    #[case] pattern: &str,
+    #[case] matching: &[&str],
+    #[case] not_matching: &[&str],
+) {
+    let options = Options::new()
+        .enable_classes(true)
+        .enable_escape(true)
+        .enable_class_escape(true);
+    let compiled = Pattern::new(pattern.as_bytes(), options).unwrap();
+
+    for (haystacks, expected) in [(matching, true), (not_matching, false)] {
+        for haystack in haystacks {
+            assert_eq!(
+                dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+                expected,
+                "haystack was: {haystack:?}",
+            );
+            assert_eq!(
+                compiled.is_match(haystack.as_bytes()),
+                expected,
+                "compiled haystack was: {haystack:?}",
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case::without_escape(Options::new().enable_classes(true).enable_class_escape(true))]
+#[case::without_class_escape(Options::new().enable_classes(true).enable_escape(true))]
+fn dowild_with_class_escape_when_disabled(#[case] options: Options<u8>) {
+    assert_eq!(dowild_with(b"[\\a]", b"\\", options), true);
+    assert_eq!(dowild_with(b"[a\\-z]", b"]", options), true);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::upper("[[:upper:]]", "a", false)]
+#[case::upper_matches_upper("[[:upper:]]", "A", true)]
+#[case::lower("[[:lower:]]", "A", false)]
+#[case::negated_upper("[![:upper:]]", "a", true)]
+#[case::negated_lower("[![:lower:]]", "A", true)]
+#[case::negated_upper_upper("[![:upper:]]", "A", false)]
+#[case::mixed_with_literal("[[:upper:]b]", "B", true)]
+#[case::mixed_with_range("[[:lower:]A-C]", "b", true)]
+#[case::upper_not_digit("[[:upper:]]", "1", false)]
+#[case::digit("[[:digit:]]", "a", false)]
+// spell-checker: enable
+fn dowild_with_named_class_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new().enable_classes(true).case_insensitive(true);
+    let compiled = Pattern::new(pattern.as_bytes(), options).unwrap();
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+    assert_eq!(compiled.is_match(haystack.as_bytes()), expected);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::upper("[[:upper:]]", 'ä', false)]
+#[case::lower("[[:lower:]]", 'Ä', false)]
+#[case::negated_upper("[![:upper:]]", 'ä', true)]
+#[case::negated_lower("[![:lower:]]", 'Ä', true)]
+#[case::mixed_with_literal("[[:upper:]ö]", 'Ö', true)]
+// spell-checker: enable
+fn dowild_with_named_class_char_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: char,
+    #[case] expected: bool,
+) {
+    let pattern = pattern.chars().collect::<Vec<char>>();
+    let options = Options::new().enable_classes(true).case_insensitive(true);
+    let compiled = Pattern::new(&pattern, options).unwrap();
+
+    assert_eq!(dowild_with(&pattern, &[haystack], options), expected);
+    assert_eq!(compiled.is_match(&[haystack]), expected);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::alpha("[[:alpha:]]", 'é', true)]
+#[case::alpha_cjk("[[:alpha:]]", '語', true)]
+#[case::upper("[[:upper:]]", 'Ä', true)]
+#[case::lower("[[:lower:]]", 'ß', true)]
+#[case::space("[[:space:]]", '\u{2003}', true)]
+#[case::blank("[[:blank:]]", '\u{a0}', true)]
+#[case::blank_line_separator("[[:blank:]]", '\u{2028}', false)]
+#[case::digit_arabic_indic("[[:digit:]]", '٣', false)]
+#[case::alnum_arabic_indic("[[:alnum:]]", '٣', true)]
+#[case::punct("[[:punct:]]", '€', true)]
+#[case::punct_letter("[[:punct:]]", 'é', false)]
+// spell-checker: enable
+fn dowild_with_named_class_unicode(
+    #[case] pattern: &str,
+    #[case] haystack: char,
+    #[case] expected: bool,
+) {
+    let pattern = pattern.chars().collect::<Vec<char>>();
+
+    assert_eq!(
+        dowild_with(&pattern, &[haystack], Options::new().enable_classes(true)),
+        expected
+    );
+}
+
+#[test]
+fn named_class_names() {
+    for name in [
+        "alnum", "alpha", "blank", "cntrl", "digit", "graph", "lower", "print", "punct", "space",
+        "upper", "xdigit",
+    ] {
+        let class = NamedClass::from_name(name.as_bytes()).unwrap();
+        assert_eq!(class.name(), name);
+    }
+    assert_eq!(NamedClass::from_name(b"word"), None);
+}
+
 // cspell: disable
 #[rstest]
 #[case::fuzz_0("*[a]", &["cba"], true, true)]
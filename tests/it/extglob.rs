@@ -0,0 +1,232 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use rstest::rstest;
+use simplematch::{
+    captures, dowild_with, find, find_longest, utf8, Options, Pattern, SimpleMatchError, Wildcard,
+};
+
+const fn options<T>() -> Options<T>
+where
+    T: Wildcard,
+{
+    Options::new()
+        .enable_extglob(true)
+        .enable_braces(true)
+        .enable_classes(true)
+        .enable_escape(true)
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::at_first("@(a|b)c", "ac", true)]
+#[case::at_second("@(a|b)c", "bc", true)]
+#[case::at_none("@(a|b)c", "cc", false)]
+#[case::at_empty("@(a|b)c", "c", false)]
+#[case::at_twice("@(a|b)c", "abc", false)]
+#[case::zero_or_one_zero("?(a|b)c", "c", true)]
+#[case::zero_or_one_one("?(a|b)c", "ac", true)]
+#[case::zero_or_one_two("?(a|b)c", "abc", false)]
+#[case::zero_or_more_zero("*(a|b)c", "c", true)]
+#[case::zero_or_more_many("*(a|b)c", "ababbac", true)]
+#[case::zero_or_more_other("*(a|b)c", "abxc", false)]
+#[case::zero_or_more_empty_haystack("*(a|b)", "", true)]
+#[case::one_or_more_zero("+(a|b)c", "c", false)]
+#[case::one_or_more_many("+(a|b)c", "abbc", true)]
+#[case::one_or_more_multi_char("+(ab|cd)", "abcdab", true)]
+#[case::one_or_more_multi_char_partial("+(ab|cd)", "abcda", false)]
+#[case::digits("+([0-9]).log", "2024.log", true)]
+#[case::digits_empty("+([0-9]).log", ".log", false)]
+#[case::digits_other("+([0-9]).log", "20a4.log", false)]
+#[case::not("!(*.bak|*.tmp)", "notes.txt", true)]
+#[case::not_first("!(*.bak|*.tmp)", "notes.bak", false)]
+#[case::not_second("!(*.bak|*.tmp)", "notes.tmp", false)]
+#[case::not_empty("!(*.bak|*.tmp)", "", true)]
+#[case::not_with_suffix("!(foo)bar", "foobar", false)]
+#[case::not_with_suffix_other("!(foo)bar", "fobar", true)]
+#[case::not_with_suffix_empty("!(foo)bar", "bar", true)]
+#[case::not_prefix("foo!(bar)", "foobaz", true)]
+#[case::not_prefix_excluded("foo!(bar)", "foobar", false)]
+#[case::not_longer("foo!(bar)", "foobarbar", true)]
+#[case::nested("@(a|+(b|c))d", "bcbd", true)]
+#[case::nested_mismatch("@(a|+(b|c))d", "abd", false)]
+#[case::nested_not("!(@(a|b)*)", "cat", true)]
+#[case::nested_not_mismatch("!(@(a|b)*)", "bat", false)]
+#[case::not_inside("@(x|!(y))", "z", true)]
+#[case::not_inside_mismatch("@(x|!(y))", "y", false)]
+#[case::wildcards_inside("@(*.rs|?.md)", "a.md", true)]
+#[case::empty_alternative("a@(|b)c", "ac", true)]
+#[case::star_of_empty("*()a", "a", true)]
+#[case::star_of_star("*(*)", "abc", true)]
+#[case::braces_inside("@({a,b}c|d)", "bc", true)]
+#[case::extglob_inside_braces("{a,@(b|c)}d", "cd", true)]
+#[case::pipe_in_braces_is_literal("{a|b,c}", "a|b", true)]
+#[case::comma_in_extglob_is_literal("@(a,b|c)", "a,b", true)]
+#[case::class_with_pipe("@([|]|x)", "|", true)]
+#[case::unclosed("@(a|b", "@(a|b", true)]
+#[case::unclosed_one("?(a", "x(a", true)]
+#[case::unclosed_any("*(a", "xyz(a", true)]
+#[case::unmatched_close("a)", "a)", true)]
+#[case::separator_outside("a|b", "a|b", true)]
+#[case::no_operator("(a|b)", "(a|b)", true)]
+#[case::escaped_operator("\\@(a)", "@(a)", true)]
+#[case::escaped_open("@\\(a)", "@(a)", true)]
+#[case::escaped_separator("@(a\\|b)", "a|b", true)]
+#[case::escaped_close("@(a\\)|b)", "a)", true)]
+// spell-checker: enable
+fn dowild_with_extglob(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options()),
+        expected
+    );
+    assert_eq!(utf8::dowild_with(pattern, haystack, options()), expected);
+    assert_eq!(
+        Pattern::new(pattern.as_bytes(), options())
+            .unwrap()
+            .is_match(haystack.as_bytes()),
+        expected
+    );
+}
+
+#[rstest]
+#[case::disabled("@(a|b)", "a", false)]
+#[case::disabled_literal("@(a|b)", "@(a|b)", true)]
+#[case::disabled_wildcards("*(a)", "x(a)", true)]
+fn dowild_with_extglob_disabled(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), Options::new()),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::segment("@(src|lib)/*.rs", "lib/main.rs", true)]
+#[case::not_segment("!(target)/*.rs", "src/main.rs", true)]
+#[case::not_segment_excluded("!(target)/*.rs", "target/main.rs", false)]
+#[case::not_does_not_cross_separator("!(a)", "b/c", false)]
+#[case::star_does_not_cross_separator("*(a|b|/)", "a/b", true)]
+#[case::not_leading_dot("!(a)", ".b", false)]
+#[case::at_leading_dot("@(.b|c)", ".b", true)]
+// spell-checker: enable
+fn dowild_with_extglob_and_path(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .enable_extglob(true)
+        .path_separator_with(b'/')
+        .protect_leading_dot(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+}
+
+#[test]
+fn dowild_with_extglob_custom_wildcards() {
+    let options = Options::new()
+        .enable_extglob(true)
+        .wildcard_any_with(b'%')
+        .wildcard_one_with(b'_');
+
+    assert_eq!(dowild_with(b"%(ab)c", b"ababc", options), true);
+    assert_eq!(dowild_with(b"_(ab)c", b"abc", options), true);
+    assert_eq!(dowild_with(b"*(ab)c", b"*(ab)c", options), true);
+}
+
+#[test]
+fn dowild_with_extglob_case_insensitive() {
+    let options = Options::new().enable_extglob(true).case_insensitive(true);
+
+    assert_eq!(dowild_with(b"+(AB)", b"abAb", options), true);
+    assert_eq!(dowild_with(b"!(*.BAK)", b"notes.bak", options), false);
+}
+
+#[test]
+fn dowild_with_extglob_nested_repetitions_are_fast() {
+    let options = Options::new().enable_extglob(true);
+    let haystack = vec![b'a'; 2000];
+    let start = Instant::now();
+
+    assert_eq!(dowild_with(b"*(*(*(a)))b", &haystack, options), false);
+    assert_eq!(dowild_with(b"+(a|aa|+(a))", &haystack, options), true);
+    assert_eq!(captures(b"*(a|aa)*(a|aa)b", &haystack, options), None);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::group("@(a|b)*", "ab", Some(vec![(0, 1), (1, 2)]))]
+#[case::repetition("+(ab)*", "ababx", Some(vec![(0, 2), (2, 5)]))]
+#[case::zero_or_more_is_lazy("*(a)*", "aa", Some(vec![(0, 0), (0, 2)]))]
+#[case::not("!(a)", "b", Some(vec![(0, 1)]))]
+#[case::not_is_lazy("!(x)*", "ab", Some(vec![(0, 0), (0, 2)]))]
+#[case::wildcards_inside_dont_capture("?@(*.rs|?.md)", "xa.md", Some(vec![(0, 1), (1, 5)]))]
+#[case::no_match("@(a|b)", "c", None)]
+// spell-checker: enable
+fn captures_with_extglob(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: Option<Vec<(usize, usize)>>,
+) {
+    let expected = expected.map(|spans| {
+        spans
+            .into_iter()
+            .map(|(start, end)| start..end)
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(
+        captures(
+            pattern.as_bytes(),
+            haystack.as_bytes(),
+            Options::new().enable_extglob(true)
+        ),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::repetition("+(ab)", "xababy", Some((1, 3)), Some((1, 5)))]
+#[case::not("x!(a)y", "xay xby", Some((0, 7)), Some((0, 7)))]
+#[case::not_excluded("x!(a)y", "xay", None, None)]
+#[case::no_match("@(c|d)", "ab", None, None)]
+// spell-checker: enable
+fn find_with_extglob(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] shortest: Option<(usize, usize)>,
+    #[case] longest: Option<(usize, usize)>,
+) {
+    let options = Options::new().enable_extglob(true);
+
+    assert_eq!(
+        find(pattern.as_bytes(), haystack.as_bytes(), options),
+        shortest.map(|(start, end)| start..end)
+    );
+    assert_eq!(
+        find_longest(pattern.as_bytes(), haystack.as_bytes(), options),
+        longest.map(|(start, end)| start..end)
+    );
+}
+
+#[rstest]
+#[case::any(Options::new().wildcard_any_with(b'('))]
+#[case::one(Options::new().wildcard_one_with(b'|'))]
+#[case::escape(Options::new().enable_escape_with(b')'))]
+#[case::separator(Options::new().path_separator_with(b'|'))]
+fn options_verify_when_extglob_enabled(#[case] options: Options<u8>) {
+    assert_eq!(options.verify(), Ok(()));
+    assert_eq!(
+        options.enable_extglob(true).verify(),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
@@ -0,0 +1,253 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use rstest::rstest;
+use simplematch::{count, find, find_iter, find_longest, Options, Pattern};
+
+// spell-checker: disable
+#[rstest]
+#[case::empty_pattern("", "abc", Some((0, 0)), Some((0, 0)))]
+#[case::empty_haystack("a", "", None, None)]
+#[case::no_match("x", "abc", None, None)]
+#[case::literal("b", "abcb", Some((1, 2)), Some((1, 2)))]
+#[case::star_only("*", "abc", Some((0, 0)), Some((0, 3)))]
+#[case::leading_star("*c", "abcbc", Some((0, 3)), Some((0, 5)))]
+#[case::trailing_star("b*", "abcbc", Some((1, 2)), Some((1, 5)))]
+#[case::one("a?c", "xxabcabc", Some((2, 5)), Some((2, 5)))]
+#[case::inner_star("ERR?R*timeout", "x ERROR timeout ERROR timeout", Some((2, 15)), Some((2, 29)))]
+#[case::leftmost_wins("b*b", "abbab", Some((1, 3)), Some((1, 5)))]
+#[case::star_then_ones("a*??", "abcd", Some((0, 3)), Some((0, 4)))]
+#[case::longest_tail("a*bc", "abcbcxbc", Some((0, 3)), Some((0, 8)))]
+#[case::longest_tail_with_one("a*b?", "abxbyz", Some((0, 3)), Some((0, 5)))]
+// spell-checker: enable
+fn find_with_default_options(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] shortest: Option<(usize, usize)>,
+    #[case] longest: Option<(usize, usize)>,
+) {
+    let shortest = shortest.map(|(start, end)| start..end);
+    let longest = longest.map(|(start, end)| start..end);
+
+    assert_eq!(
+        find(pattern.as_bytes(), haystack.as_bytes(), Options::new()),
+        shortest
+    );
+    assert_eq!(
+        find_longest(pattern.as_bytes(), haystack.as_bytes(), Options::new()),
+        longest
+    );
+
+    let compiled = Pattern::new(pattern.as_bytes(), Options::new()).unwrap();
+    assert_eq!(compiled.find(haystack.as_bytes()), shortest);
+    assert_eq!(compiled.find_longest(haystack.as_bytes()), longest);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::case_insensitive("timeout", "A TIMEOUT", Some((2, 9)))]
+#[case::class("[0-9][0-9]", "at 12:00", Some((3, 5)))]
+#[case::negative_class("[!a-z ]*", "ab 12", Some((3, 4)))]
+#[case::escape("\\*x", "a*x", Some((1, 3)))]
+#[case::invalid_class("[ab", "x[ab", Some((1, 4)))]
+// spell-checker: enable
+fn find_with_options(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: Option<(usize, usize)>,
+) {
+    let options = Options::new()
+        .case_insensitive(true)
+        .enable_classes(true)
+        .enable_escape(true);
+
+    assert_eq!(
+        find(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected.map(|(start, end)| start..end)
+    );
+}
+
+#[test]
+fn find_with_char() {
+    let pattern = "ö?".chars().collect::<Vec<char>>();
+    let haystack = "aäöü".chars().collect::<Vec<char>>();
+
+    assert_eq!(find(&pattern, &haystack, Options::new()), Some(2..4));
+}
+
+#[test]
+fn find_in_large_haystack() {
+    let mut haystack = vec![b'a'; 200_000];
+
+    assert_eq!(find(b"a*b", &haystack, Options::new()), None);
+    assert_eq!(find_longest(b"a*b", &haystack, Options::new()), None);
+
+    haystack.extend(b"bab");
+    assert_eq!(find(b"a*b", &haystack, Options::new()), Some(0..200_001));
+    assert_eq!(
+        find_longest(b"a*b", &haystack, Options::new()),
+        Some(0..200_003)
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::no_match("x", "abc", &[])]
+#[case::empty_haystack("a", "", &[])]
+#[case::literal("ab", "abxabab", &[(0, 2), (3, 5), (5, 7)])]
+#[case::non_overlapping("aa", "aaaaa", &[(0, 2), (2, 4)])]
+#[case::star_is_lazy("a*b", "aabab", &[(0, 3), (3, 5)])]
+#[case::empty_matches("*", "abc", &[(0, 0), (1, 1), (2, 2), (3, 3)])]
+#[case::empty_match_in_empty_haystack("*", "", &[(0, 0)])]
+#[case::empty_pattern("", "ab", &[(0, 0), (1, 1), (2, 2)])]
+#[case::trailing_star("a*", "aab", &[(0, 1), (1, 2)])]
+#[case::one("?", "abc", &[(0, 1), (1, 2), (2, 3)])]
+#[case::log_lines(
+    "ERR*timeout",
+    "ERROR timeout, ERROR: timeout, WARN",
+    &[(0, 13), (15, 29)]
+)]
+// spell-checker: enable
+fn find_iter_with_default_options(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: &[(usize, usize)],
+) {
+    let expected = expected
+        .iter()
+        .map(|(start, end)| *start..*end)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        find_iter(pattern.as_bytes(), haystack.as_bytes(), Options::new()).collect::<Vec<_>>(),
+        expected
+    );
+    assert_eq!(
+        count(pattern.as_bytes(), haystack.as_bytes(), Options::new()),
+        expected.len()
+    );
+
+    let compiled = Pattern::new(pattern.as_bytes(), Options::new()).unwrap();
+    assert_eq!(
+        compiled.find_iter(haystack.as_bytes()).collect::<Vec<_>>(),
+        expected
+    );
+    assert_eq!(compiled.count(haystack.as_bytes()), expected.len());
+}
+
+#[test]
+fn find_iter_is_fused() {
+    let pattern = Pattern::new(b"a", Options::new()).unwrap();
+    let mut iter = pattern.find_iter(b"a");
+
+    assert_eq!(iter.next(), Some(0..1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn find_iter_with_options() {
+    let options = Options::new().case_insensitive(true).enable_classes(true);
+
+    assert_eq!(
+        find_iter(b"[a-z][0-9]", b"A1-b2-c", options).collect::<Vec<_>>(),
+        [0..2, 3..5]
+    );
+}
+
+#[test]
+fn find_iter_in_large_haystack() {
+    let mut haystack = vec![b'a'; 200_000];
+
+    assert_eq!(count(b"a*b", &haystack, Options::new()), 0);
+
+    haystack.extend(b"ab".repeat(50_000));
+    assert_eq!(count(b"a*b", &haystack, Options::new()), 50_000);
+    assert_eq!(
+        find_iter(b"a*b", &haystack, Options::new()).nth(1),
+        Some(200_002..200_004)
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::tentative_matches("{b*y,a}", "baba", &[(1, 2), (3, 4)])]
+#[case::replaced_by_earlier_start("{b*y,a}", "babay", &[(0, 5)])]
+#[case::replaced_in_between("{b*y,a}", "abayba", &[(0, 1), (1, 4), (5, 6)])]
+#[case::empty_alternative("{a,}", "ab", &[(0, 0), (1, 1), (2, 2)])]
+// spell-checker: enable
+fn find_iter_with_braces(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: &[(usize, usize)],
+) {
+    let expected = expected
+        .iter()
+        .map(|(start, end)| *start..*end)
+        .collect::<Vec<_>>();
+    let options = Options::new().enable_braces(true);
+
+    assert_eq!(
+        find_iter(pattern.as_bytes(), haystack.as_bytes(), options).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+fn find_iter_is_linear() {
+    let pattern = Pattern::new(b"{b*y,a}", Options::new().enable_braces(true)).unwrap();
+    let elapsed = |len: usize| {
+        let haystack = b"ba".repeat(len);
+        let start = Instant::now();
+        assert_eq!(pattern.find_iter(&haystack).count(), len);
+        start.elapsed()
+    };
+
+    // The `b*y` from the first `b` is possible until the end, so every `a` is only certain at
+    // the end of the haystack
+    let small = [elapsed(2_000), elapsed(2_000), elapsed(2_000)]
+        .into_iter()
+        .min()
+        .unwrap();
+    let large = elapsed(20_000);
+    assert!(
+        large < small * 30 + Duration::from_millis(100),
+        "10 times the haystack took {large:?} instead of about 10 times {small:?}"
+    );
+}
+
+#[rstest]
+#[case::leading_dot(
+    "?",
+    "a.b",
+    Options::new().protect_leading_dot(true),
+    &[(0, 1), (1, 2), (2, 3)]
+)]
+#[case::leading_dot_after_separator(
+    "?",
+    "a/.b",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    &[(0, 1), (3, 4)]
+)]
+#[case::dot_inside_of_segment(
+    "?",
+    "a/b.c",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    &[(0, 1), (2, 3), (3, 4), (4, 5)]
+)]
+fn find_iter_sees_the_haystack_in_front_of_a_match(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] options: Options<u8>,
+    #[case] expected: &[(usize, usize)],
+) {
+    let expected = expected
+        .iter()
+        .map(|(start, end)| *start..*end)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        find_iter(pattern.as_bytes(), haystack.as_bytes(), options).collect::<Vec<_>>(),
+        expected
+    );
+}
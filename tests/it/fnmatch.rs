@@ -0,0 +1,227 @@
+//! The conformance tests are the examples of the POSIX specification of `fnmatch` and the
+//! pattern matching notation (XCU 2.13). The expected results were verified against glibc.
+use rstest::rstest;
+use simplematch::fnmatch::{
+    fnmatch, Flags, FNM_CASEFOLD, FNM_FILE_NAME, FNM_LEADING_DIR, FNM_NOESCAPE, FNM_PATHNAME,
+    FNM_PERIOD,
+};
+use simplematch::Options;
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("abc", "abc", true)]
+#[case::literal_mismatch("abc", "abd", false)]
+#[case::one("a?c", "abc", true)]
+#[case::one_needs_a_character("a?c", "ac", false)]
+#[case::star("a*c", "abbbc", true)]
+#[case::star_empty("a*c", "ac", true)]
+#[case::star_only("*", "", true)]
+#[case::class("[abc]", "b", true)]
+#[case::class_mismatch("[abc]", "d", false)]
+#[case::range("[a-c]x", "bx", true)]
+#[case::range_reversed("[c-a]", "b", false)]
+#[case::range_with_hyphen_at_end("[a-]", "-", true)]
+#[case::range_with_hyphen_at_start("[-a]", "-", true)]
+#[case::negated_with_exclamation_mark("[!a]", "b", true)]
+#[case::negated_with_exclamation_mark_mismatch("[!a]", "a", false)]
+#[case::negated_with_caret("[^a]", "b", true)]
+#[case::negated_with_caret_mismatch("[^a]", "a", false)]
+#[case::close_bracket_first("[]a]", "]", true)]
+#[case::close_bracket_after_negation("[!]a]", "]", false)]
+#[case::close_bracket_after_negation_other("[!]a]", "b", true)]
+#[case::exclamation_mark_not_first("[a!]", "!", true)]
+#[case::unterminated_bracket("[ab", "[ab", true)]
+#[case::unterminated_bracket_empty("[]", "[]", true)]
+#[case::escaped_star("\\*", "*", true)]
+#[case::escaped_star_no_wildcard("\\*", "a", false)]
+#[case::escaped_letter("\\a", "a", true)]
+#[case::escaped_in_bracket("[\\]]", "]", true)]
+#[case::escaped_range_end("[a-\\z]", "m", true)]
+#[case::trailing_backslash("a\\", "a\\", false)]
+#[case::collating_symbol("[[.a.]]", "a", true)]
+#[case::collating_symbol_close_bracket("[[.].]]", "]", true)]
+#[case::collating_symbol_range("[a-[.c.]]", "b", true)]
+#[case::collating_symbol_invalid("[[.ab.]]", "a", false)]
+#[case::collating_symbol_unterminated("x[[.a", "x[[.a", false)]
+#[case::equivalence_class("[[=a=]]", "a", true)]
+#[case::equivalence_class_no_range_start("[[=a=]-c]", "-", true)]
+#[case::equivalence_class_no_range_end("[a-[=c=]]", "b", false)]
+#[case::equivalence_class_malformed("[[=a]", "a", true)]
+#[case::equivalence_class_malformed_after_match("[a[=a]", "a", false)]
+#[case::named_class("[[:alpha:]]", "a", true)]
+#[case::named_class_mismatch("[[:alpha:]]", "1", false)]
+#[case::named_class_negated("[![:alnum:]_]", "-", true)]
+#[case::named_class_negated_mismatch("[![:alnum:]_]", "_", false)]
+#[case::named_class_no_range_start("[[:alpha:]-z]", "-", true)]
+#[case::named_class_unknown("[[:foo:]]", "f", false)]
+#[case::named_class_unknown_after_match("[a[:foo:]]", "a", true)]
+#[case::named_class_not_a_name("[[:a]", ":", true)]
+#[case::named_class_unterminated_bracket("[[:alpha:]", "[a", true)]
+#[case::star_matches_slash("*", "a/b", true)]
+#[case::one_matches_slash("a?b", "a/b", true)]
+#[case::bracket_matches_slash("a[/]b", "a/b", true)]
+#[case::star_matches_period("*", ".profile", true)]
+// spell-checker: enable
+fn fnmatch_without_flags(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(
+        fnmatch(pattern.as_bytes(), string.as_bytes(), Flags::empty()),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::escaped_star("\\*", "\\x", true)]
+#[case::escaped_star_literal("\\*", "*", false)]
+#[case::backslash("a\\b", "a\\b", true)]
+#[case::trailing_backslash("a\\", "a\\", true)]
+#[case::backslash_in_bracket("[\\]]", "\\]", true)]
+#[case::backslash_in_bracket_member("[\\]", "\\", true)]
+// spell-checker: enable
+fn fnmatch_with_noescape(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(
+        fnmatch(pattern.as_bytes(), string.as_bytes(), FNM_NOESCAPE),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::star_does_not_match_slash("*", "a/b", false)]
+#[case::star_in_segment("a/*", "a/b", true)]
+#[case::one_does_not_match_slash("a?b", "a/b", false)]
+#[case::bracket_does_not_match_slash("a[/]b", "a/b", false)]
+#[case::negated_bracket_does_not_match_slash("a[!x]b", "a/b", false)]
+#[case::literal_slash("a/b", "a/b", true)]
+#[case::escaped_slash("a\\/b", "a/b", true)]
+#[case::star_before_escaped_slash("a*\\/b", "a/b", false)]
+#[case::double_star_is_no_globstar("**/b", "a/x/b", false)]
+#[case::star_matches_period("*", ".profile", true)]
+// spell-checker: enable
+fn fnmatch_with_pathname(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(
+        fnmatch(pattern.as_bytes(), string.as_bytes(), FNM_PATHNAME),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::star(Flags::empty(), "*", ".profile", false)]
+#[case::star_before_literal_period(Flags::empty(), "*.", ".", false)]
+#[case::explicit_period(Flags::empty(), ".*", ".profile", true)]
+#[case::one(Flags::empty(), "?profile", ".profile", false)]
+#[case::bracket(Flags::empty(), "[.]profile", ".profile", false)]
+#[case::negated_bracket(Flags::empty(), "[!a]profile", ".profile", false)]
+#[case::not_leading(Flags::empty(), "a*", "a.b", true)]
+#[case::after_slash_without_pathname(Flags::empty(), "a/*", "a/.b", true)]
+#[case::after_slash(FNM_PATHNAME, "a/*", "a/.b", false)]
+#[case::after_slash_explicit(FNM_PATHNAME, "a/.*", "a/.b", true)]
+#[case::after_slash_one(FNM_PATHNAME, "a/?b", "a/.b", false)]
+#[case::first_segment(FNM_PATHNAME, "*/b", ".a/b", false)]
+#[case::not_leading_in_segment(FNM_PATHNAME, "a/b*", "a/b.c", true)]
+// spell-checker: enable
+fn fnmatch_with_period(
+    #[case] flags: Flags,
+    #[case] pattern: &str,
+    #[case] string: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(
+        fnmatch(pattern.as_bytes(), string.as_bytes(), flags | FNM_PERIOD),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("abc", "ABC", true)]
+#[case::bracket("[a-c]", "B", true)]
+#[case::bracket_upper("[A-C]", "b", true)]
+#[case::negated_bracket("[!a]", "A", false)]
+#[case::range_with_folded_bounds("[Z-a]", "_", false)]
+#[case::named_class_upper("[[:upper:]]", "a", false)]
+#[case::named_class_lower("[[:lower:]]", "A", false)]
+#[case::collating_symbol("[[.a.]]", "A", false)]
+#[case::collating_symbol_range("[[.A.]-C]", "b", true)]
+// spell-checker: enable
+fn fnmatch_with_casefold(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(
+        fnmatch(pattern.as_bytes(), string.as_bytes(), FNM_CASEFOLD),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::whole(Flags::empty(), "src", "src", true)]
+#[case::leading_directory(Flags::empty(), "src", "src/lib.rs", true)]
+#[case::leading_directories(Flags::empty(), "a/b", "a/b/c/d", true)]
+#[case::not_a_directory(Flags::empty(), "src", "srcs/lib.rs", false)]
+#[case::star_crosses_slash(Flags::empty(), "a*b", "a/b/c", true)]
+#[case::star_with_pathname(FNM_PATHNAME, "*", "src/lib.rs", true)]
+#[case::star_does_not_cross_with_pathname(FNM_PATHNAME, "a*c", "ab/c/d", false)]
+#[case::trailing_slash(FNM_PATHNAME, "src/", "src/", true)]
+#[case::hidden_leading_directory(FNM_PATHNAME | FNM_PERIOD, "*", ".git/config", false)]
+// spell-checker: enable
+fn fnmatch_with_leading_dir(
+    #[case] flags: Flags,
+    #[case] pattern: &str,
+    #[case] string: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(
+        fnmatch(
+            pattern.as_bytes(),
+            string.as_bytes(),
+            flags | FNM_LEADING_DIR
+        ),
+        expected
+    );
+}
+
+#[test]
+fn fnmatch_with_all_flags() {
+    let flags = FNM_PATHNAME | FNM_PERIOD | FNM_CASEFOLD | FNM_LEADING_DIR;
+
+    assert_eq!(fnmatch(b"SRC/*.rs", b"src/lib.RS", flags), true);
+    assert_eq!(fnmatch(b"src/*", b"src/bin/main.rs", flags), true);
+    assert_eq!(fnmatch(b"src/*", b"src/.git/config", flags), false);
+    assert_eq!(fnmatch(b"*.rs", b"src/lib.rs", flags), false);
+}
+
+#[test]
+fn flags() {
+    assert_eq!(FNM_FILE_NAME, FNM_PATHNAME);
+    assert_eq!(FNM_PATHNAME.bits(), 1);
+    assert_eq!(FNM_NOESCAPE.bits(), 2);
+    assert_eq!(FNM_PERIOD.bits(), 4);
+    assert_eq!(FNM_LEADING_DIR.bits(), 8);
+    assert_eq!(FNM_CASEFOLD.bits(), 16);
+    assert_eq!(
+        Flags::from_bits_truncate(0xff),
+        Flags::from_bits_truncate(0x1f)
+    );
+    assert!((FNM_PATHNAME | FNM_PERIOD).contains(FNM_PERIOD));
+    assert!(!FNM_PATHNAME.contains(FNM_PERIOD));
+
+    let mut flags = Flags::empty();
+    flags |= FNM_CASEFOLD;
+    assert_eq!(flags, FNM_CASEFOLD);
+}
+
+#[test]
+fn options_from_flags() {
+    assert_eq!(
+        Options::from(Flags::empty()),
+        Options::new().enable_classes(true).enable_escape(true)
+    );
+    assert_eq!(
+        Options::from(FNM_PATHNAME | FNM_NOESCAPE | FNM_PERIOD | FNM_CASEFOLD),
+        Options::new()
+            .enable_classes(true)
+            .path_separator_with(b'/')
+            .protect_leading_dot(true)
+            .case_insensitive(true)
+    );
+}
@@ -0,0 +1,113 @@
+//! Tests for the grapheme cluster matching of the `graphemes` feature
+
+use rstest::rstest;
+use simplematch::{graphemes, utf8, Options, Pattern};
+
+fn chars(string: &str) -> Vec<char> {
+    string.chars().collect()
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::ascii("a?c", "abc", true)]
+#[case::combining_accent("caf?", "cafe\u{301}", true)]
+#[case::multiple_combining_marks("?", "e\u{301}\u{323}", true)]
+#[case::flag("?", "🇩🇪", true)]
+#[case::two_flags("??", "🇩🇪🇫🇷", true)]
+#[case::family("?", "👨\u{200d}👩\u{200d}👧", true)]
+#[case::skin_tone("?", "👍🏽", true)]
+#[case::hangul_syllable("?", "\u{1100}\u{1161}\u{11a8}", true)]
+#[case::crlf("a?b", "a\r\nb", true)]
+#[case::too_many_ones("???", "e\u{301}🇩🇪", false)]
+#[case::too_few_ones("?", "e\u{301}🇩🇪", false)]
+#[case::star("*?", "e\u{301}", true)]
+#[case::star_then_literal("*\u{301}", "e\u{301}", false)]
+#[case::literal_cluster("e\u{301}", "e\u{301}", true)]
+#[case::literal_base_only("e", "e\u{301}", false)]
+#[case::literal_base_then_star("e*", "e\u{301}", false)]
+#[case::precomposed_is_not_decomposed("é", "e\u{301}", false)]
+#[case::empty_haystack("?", "", false)]
+// spell-checker: enable
+fn dowild(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    assert_eq!(graphemes::dowild(pattern, haystack), expected);
+    assert_eq!(
+        Pattern::new(&chars(pattern), Options::new())
+            .unwrap()
+            .is_match_graphemes(haystack),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::class_single_char("[äöü]", "ö", true)]
+#[case::class_cluster("[aeo]", "e\u{301}", false)]
+#[case::negated_class_cluster("[!aeo]", "e\u{301}", true)]
+#[case::negated_class_single_char("[!aeo]", "e", false)]
+#[case::range_cluster("[a-z]", "e\u{301}", false)]
+#[case::case_insensitive("CAFE\u{301}", "cafe\u{301}", true)]
+#[case::braces("{na,fa}?ve", "nai\u{308}ve", true)]
+#[case::path_one("?/?", "e\u{301}/🇩🇪", true)]
+#[case::path_separator_is_not_one("a?b", "a/b", false)]
+#[case::leading_dot(".?", ".e\u{301}", true)]
+#[case::leading_dot_protected("?e", ".\u{301}e", false)]
+// spell-checker: enable
+fn dowild_with(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new()
+        .enable_classes(true)
+        .enable_braces(true)
+        .case_insensitive(true);
+    let options = if pattern.contains('/') || haystack.contains(['/', '.']) {
+        options.path_separator_with('/').protect_leading_dot(true)
+    } else {
+        options
+    };
+
+    assert_eq!(graphemes::dowild_with(pattern, haystack, options), expected);
+    assert_eq!(
+        Pattern::new(&chars(pattern), options)
+            .unwrap()
+            .is_match_graphemes(haystack),
+        expected
+    );
+}
+
+#[test]
+fn find_and_captures() {
+    let options = Options::new();
+    let haystack = "x👍🏽e\u{301}y🇩🇪";
+
+    assert_eq!(graphemes::find("??", haystack, options), Some(0..9));
+    assert_eq!(graphemes::find("e*", haystack, options), None);
+    assert_eq!(graphemes::find("y?", haystack, options), Some(12..21));
+    assert_eq!(
+        graphemes::find_longest("?*", haystack, options),
+        Some(0..21)
+    );
+    assert_eq!(
+        graphemes::captures("?*?", haystack, options),
+        Some(vec![0..1, 1..13, 13..21])
+    );
+
+    let pattern = Pattern::new(&chars("*?y"), options).unwrap();
+    assert_eq!(pattern.find_graphemes(haystack), Some(0..13));
+    assert_eq!(pattern.find_longest_graphemes(haystack), Some(0..13));
+    assert_eq!(
+        pattern.captures_graphemes("e\u{301}y"),
+        Some(vec![0..0, 0..3])
+    );
+}
+
+#[test]
+fn spans_are_grapheme_boundaries() {
+    let haystack = "🇩🇪e\u{301}👨\u{200d}👩\u{200d}👧";
+
+    assert_eq!(
+        graphemes::captures("*?", haystack, Options::new()),
+        Some(vec![0..11, 11..29])
+    );
+    assert_eq!(
+        utf8::captures("*?", haystack, Options::new()),
+        Some(vec![0..25, 25..29])
+    );
+}
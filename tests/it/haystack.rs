@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use rstest::rstest;
+#[cfg(feature = "ropey")]
+use simplematch::{dowild_with, utf8};
+use simplematch::{dowild_with_in, try_dowild_with_in, Chunks, Options, Pattern, SimpleMatchError};
+
+/// Returns the `haystack` in a `VecDeque` whose buffer wraps around in front of `split`
+fn ring(haystack: &[u8], split: usize) -> VecDeque<u8> {
+    let mut ring = VecDeque::with_capacity(haystack.len());
+    let (first, second) = haystack.split_at(split);
+    ring.extend(second);
+    for c in first.iter().rev() {
+        ring.push_front(*c);
+    }
+    ring
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("abc*", "abcdef", Options::new(), true)]
+#[case::no_match("a*b", "aaaa", Options::new(), false)]
+#[case::empty("*", "", Options::new(), true)]
+#[case::case_insensitive("*.RS", "lib.rs", Options::new().case_insensitive(true), true)]
+#[case::class("[a-c]?[!x]", "bxy", Options::new().enable_classes(true), true)]
+#[case::separator("src/?", "src//", Options::new().path_separator_with(b'/'), false)]
+#[case::leading_dot(
+    "src/*",
+    "src/.git",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    false
+)]
+#[case::globstar(
+    "src/**/*.rs",
+    "src/a/b/lib.rs",
+    Options::new().path_separator_with(b'/').enable_globstar(true),
+    true
+)]
+#[case::braces("{src,tests}/*.rs", "tests/a.rs", Options::new().enable_braces(true), true)]
+#[case::negation("!(*.rs)", "lib.rs", Options::new().enable_extglob(true), false)]
+// spell-checker: enable
+fn haystack_split_at_every_position(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] options: Options<u8>,
+    #[case] expected: bool,
+) {
+    let (pattern, haystack) = (pattern.as_bytes(), haystack.as_bytes());
+    let compiled = Pattern::new(pattern, options).unwrap();
+
+    for split in 0..=haystack.len() {
+        let ring = ring(haystack, split);
+        assert_eq!(
+            dowild_with_in(pattern, &ring, options),
+            expected,
+            "split: {split}"
+        );
+        assert_eq!(compiled.is_match_in(&ring), expected, "split: {split}");
+
+        let (first, second) = haystack.split_at(split);
+        let chunks = [first, &[], second];
+        let chunks = Chunks::new(&chunks);
+        assert_eq!(
+            dowild_with_in(pattern, &chunks, options),
+            expected,
+            "split: {split}"
+        );
+        assert_eq!(compiled.is_match_in(&chunks), expected, "split: {split}");
+    }
+}
+
+#[test]
+fn haystack_of_path_segments() {
+    let segments = [&b"src"[..], b"/", b"a", b"/", b"lib.rs"];
+    let segments = Chunks::new(&segments);
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .enable_globstar(true);
+
+    assert_eq!(dowild_with_in(b"src/**/*.rs", &segments, options), true);
+    assert_eq!(dowild_with_in(b"src/*.rs", &segments, options), false);
+    assert_eq!(dowild_with_in(b"", &Chunks::new(&[]), options), true);
+    assert_eq!(
+        dowild_with_in(b"", &Chunks::new(&[&[], &[]]), options),
+        true
+    );
+}
+
+#[rstest]
+#[case::leading_empty_chunks(&["", "", "src/", "lib.rs"], true)]
+#[case::trailing_empty_chunks(&["src/", "lib.rs", "", ""], true)]
+#[case::empty_chunks_between(&["src", "", "", "/lib", "", ".rs"], true)]
+#[case::dot_after_separator_in_previous_chunk(&["src/", ".lib.rs"], false)]
+#[case::dot_after_separator_in_same_chunk(&["src", "/.lib.rs"], false)]
+#[case::dot_after_empty_chunk(&["src/", "", ".lib.rs"], false)]
+fn haystack_of_chunks(#[case] chunks: &[&str], #[case] expected: bool) {
+    let chunks = chunks
+        .iter()
+        .map(|chunk| chunk.as_bytes())
+        .collect::<Vec<_>>();
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .protect_leading_dot(true);
+
+    assert_eq!(
+        dowild_with_in(b"src/*.rs", &Chunks::new(&chunks), options),
+        expected
+    );
+}
+
+#[test]
+fn haystack_of_many_chunks() {
+    let haystack = b"ab".repeat(10_000);
+    let mut chunks = haystack.chunks(1).collect::<Vec<_>>();
+    chunks.insert(5_000, &[]);
+    let chunks = Chunks::new(&chunks);
+    let pattern = Pattern::new(b"*b*bb*", Options::new()).unwrap();
+
+    assert_eq!(dowild_with_in(b"*ab", &chunks, Options::new()), true);
+    assert_eq!(dowild_with_in(b"ab*ba", &chunks, Options::new()), false);
+    assert_eq!(pattern.is_match_in(&chunks), false);
+}
+
+#[test]
+fn try_dowild_with_in_errors() {
+    let haystack = VecDeque::from(b"[a".to_vec());
+    let options = Options::new().enable_classes(true);
+
+    assert_eq!(try_dowild_with_in(b"[a", &haystack, options), Ok(true));
+    assert!(matches!(
+        try_dowild_with_in(b"[a", &haystack, options.strict(true)),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+    assert_eq!(
+        try_dowild_with_in(b"a", &haystack, options.wildcard_one_with(b'*')),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
+
+#[cfg(feature = "ropey")]
+#[test]
+fn haystack_of_rope() {
+    let mut rope = ropey::Rope::from_str("src/lib.rs");
+    rope.insert(4, "grün/");
+    let options = Options::new().path_separator_with(b'/');
+    let char_options = Options::new().path_separator_with('/');
+
+    for pattern in ["src/*/*.rs", "src/gr??n/*", "src/*.rs", "*/gr?n/*"] {
+        let text = rope.to_string();
+        let chars = pattern.chars().collect::<Vec<char>>();
+
+        assert_eq!(
+            dowild_with_in(pattern.as_bytes(), &rope, options),
+            dowild_with(pattern.as_bytes(), text.as_bytes(), options),
+            "pattern: {pattern:?}"
+        );
+        assert_eq!(
+            dowild_with_in(&chars, &rope, char_options),
+            utf8::dowild_with(pattern, &text, char_options),
+            "pattern: {pattern:?}"
+        );
+    }
+
+    let pattern = Pattern::new(&['*', 'ü', '*'], Options::new()).unwrap();
+    assert_eq!(pattern.is_match_in(&rope), true);
+}
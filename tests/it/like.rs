@@ -0,0 +1,122 @@
+use rstest::rstest;
+use simplematch::like::{ilike, like, parse_escape, Like, LikeError, DEFAULT_ESCAPE};
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("abc", "abc", true)]
+#[case::literal_mismatch("abc", "abd", false)]
+#[case::literal_is_case_sensitive("abc", "ABC", false)]
+#[case::percent("a%c", "abbbc", true)]
+#[case::percent_empty("a%c", "ac", true)]
+#[case::percent_only("%", "", true)]
+#[case::percent_newline("a%", "a\nb", true)]
+#[case::underscore("a_c", "abc", true)]
+#[case::underscore_needs_a_character("a_c", "ac", false)]
+#[case::underscore_multibyte("_", "ä", true)]
+#[case::underscore_two_characters("_", "ab", false)]
+#[case::glob_characters_are_literal("*?[a]", "*?[a]", true)]
+#[case::glob_star_is_literal("*", "abc", false)]
+#[case::escaped_percent("100\\%", "100%", true)]
+#[case::escaped_percent_literal("100\\%", "1000", false)]
+#[case::escaped_underscore("a\\_b", "a_b", true)]
+#[case::escaped_underscore_literal("a\\_b", "axb", false)]
+#[case::escaped_escape("a\\\\b", "a\\b", true)]
+#[case::escape_then_wildcard("\\%%", "%abc", true)]
+// spell-checker: enable
+fn like_with_default_escape(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(like(pattern, string, Some(DEFAULT_ESCAPE)), Ok(expected));
+    assert_eq!(
+        Like::new(pattern, Some(DEFAULT_ESCAPE))
+            .unwrap()
+            .is_match(string),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::backslash_is_literal("a\\%", "a\\bc", true)]
+#[case::trailing_backslash("a\\", "a\\", true)]
+#[case::backslash_before_letter("a\\b", "a\\b", true)]
+// spell-checker: enable
+fn like_without_escape(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(like(pattern, string, None), Ok(expected));
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::escaped_percent("100#%", '#', "100%", true)]
+#[case::escaped_escape("a##", '#', "a#", true)]
+#[case::backslash_is_literal("a\\_", '#', "a\\x", true)]
+#[case::percent_as_escape("a%%", '%', "a%", true)]
+#[case::percent_as_escape_mismatch("a%%", '%', "ab", false)]
+#[case::underscore_as_escape("a__", '_', "a_", true)]
+#[case::multibyte_escape("aä%", 'ä', "a%", true)]
+// spell-checker: enable
+fn like_with_custom_escape(
+    #[case] pattern: &str,
+    #[case] escape: char,
+    #[case] string: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(like(pattern, string, Some(escape)), Ok(expected));
+}
+
+#[rstest]
+#[case::trailing("abc\\", Some('\\'), LikeError::TrailingEscape)]
+#[case::trailing_escaped_escape("\\\\\\", Some('\\'), LikeError::TrailingEscape)]
+#[case::only_escape("#", Some('#'), LikeError::TrailingEscape)]
+#[case::letter("a\\b", Some('\\'), LikeError::InvalidEscapeSequence(1))]
+#[case::byte_offset("ää#b", Some('#'), LikeError::InvalidEscapeSequence(4))]
+#[case::percent_escape("%a", Some('%'), LikeError::InvalidEscapeSequence(0))]
+fn like_errors(#[case] pattern: &str, #[case] escape: Option<char>, #[case] expected: LikeError) {
+    assert_eq!(like(pattern, "", escape), Err(expected));
+    assert_eq!(ilike(pattern, "", escape), Err(expected));
+    assert_eq!(Like::new(pattern, escape).unwrap_err(), expected);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("ABC", "abc", true)]
+#[case::wildcards("%.JPG", "holiday.jpg", true)]
+#[case::escaped("A\\_%", "a_B", true)]
+#[case::mismatch("A_C", "abd", false)]
+// spell-checker: enable
+fn ilike_with_default_escape(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(ilike(pattern, string, Some(DEFAULT_ESCAPE)), Ok(expected));
+    assert_eq!(
+        Like::ilike(pattern, Some(DEFAULT_ESCAPE))
+            .unwrap()
+            .is_match(string),
+        expected
+    );
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn ilike_with_unicode() {
+    assert_eq!(ilike("STRA_E", "straße", None), Ok(true));
+    assert_eq!(ilike("ÄRGER%", "ärgerlich", None), Ok(true));
+}
+
+#[rstest]
+#[case::empty("", Ok(None))]
+#[case::one("#", Ok(Some('#')))]
+#[case::multibyte("ä", Ok(Some('ä')))]
+#[case::two("##", Err(LikeError::InvalidEscapeString))]
+fn parse_escape_clause(#[case] escape: &str, #[case] expected: Result<Option<char>, LikeError>) {
+    assert_eq!(parse_escape(escape), expected);
+}
+
+#[test]
+fn like_error_display() {
+    assert_eq!(
+        LikeError::InvalidEscapeSequence(3).to_string(),
+        "Invalid LIKE pattern: The escape character at byte 3 must be followed by '%', '_' or the \
+         escape character."
+    );
+    assert_eq!(
+        LikeError::TrailingEscape.to_string(),
+        "Invalid LIKE pattern: The pattern must not end with the escape character."
+    );
+}
@@ -84,9 +84,47 @@
 #![warn(clippy::useless_let_if_seq)]
 #![warn(clippy::verbose_file_reads)]
 
+#[cfg(feature = "std")]
+mod braces;
+#[cfg(feature = "std")]
+mod captures;
 #[cfg(feature = "std")]
 mod dowild_with;
 #[cfg(feature = "std")]
+mod extglob;
+#[cfg(feature = "std")]
+mod find;
+#[cfg(feature = "std")]
+mod fnmatch;
+#[cfg(all(feature = "std", feature = "graphemes"))]
+mod graphemes;
+#[cfg(feature = "std")]
+mod haystack;
+#[cfg(feature = "std")]
 mod import;
 #[cfg(feature = "std")]
+mod like;
+#[cfg(all(feature = "std", feature = "normalization"))]
+mod normalization;
+#[cfg(feature = "std")]
 mod options;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod pattern;
+#[cfg(feature = "std")]
+mod set;
+#[cfg(feature = "std")]
+mod similar;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod to_regex;
+#[cfg(all(feature = "std", feature = "unicode"))]
+mod unicode;
+#[cfg(feature = "std")]
+mod utf16;
+#[cfg(feature = "std")]
+mod utf8;
+#[cfg(feature = "std")]
+mod validate;
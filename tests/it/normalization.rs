@@ -0,0 +1,144 @@
+//! Tests for the normalization-insensitive matching of the `normalization` feature
+
+use rstest::rstest;
+use simplematch::{dowild_with, utf8, Normalization, Options, Pattern, WildSet};
+
+fn chars(string: &str) -> Vec<char> {
+    string.chars().collect()
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::nfc_pattern_nfd_haystack("café*", "cafe\u{301} au lait", true)]
+#[case::nfd_pattern_nfc_haystack("cafe\u{301}*", "café au lait", true)]
+#[case::nfc_both("café", "café", true)]
+#[case::base_only("cafe", "cafe\u{301}", false)]
+#[case::one_matches_decomposed("caf?", "cafe\u{301}", true)]
+#[case::two_ones_decomposed("caf??", "cafe\u{301}", false)]
+#[case::star_then_one("*?", "e\u{301}", true)]
+#[case::mark_order("q\u{307}\u{323}", "q\u{323}\u{307}", true)]
+#[case::mark_order_precomposed("ḍ\u{307}", "d\u{307}\u{323}", true)]
+#[case::different_mark("é", "e\u{300}", false)]
+#[case::singleton("\u{212b}", "Å", true)]
+#[case::hangul_syllable("한", "\u{1112}\u{1161}\u{11ab}", true)]
+#[case::hangul_one("?", "\u{1112}\u{1161}\u{11ab}", true)]
+#[case::full_width_is_not_canonical("ABC", "ＡＢＣ", false)]
+#[case::mark_after_star("*\u{301}", "e\u{301}", false)]
+// spell-checker: enable
+fn canonical(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new().normalize_with(Normalization::Canonical);
+
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+    assert_eq!(
+        dowild_with(&chars(pattern), &chars(haystack), options),
+        expected
+    );
+    assert_eq!(
+        Pattern::new(&chars(pattern), options)
+            .unwrap()
+            .is_match_str(haystack),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::full_width("ABC", "ＡＢＣ", true)]
+#[case::full_width_pattern("ＡＢＣ*", "ABCD", true)]
+#[case::ligature("fi*", "ﬁle", true)]
+#[case::superscript("x2", "x²", true)]
+#[case::canonical_too("café", "cafe\u{301}", true)]
+#[case::one_matches_ligature("?le", "ﬁle", true)]
+#[case::different("ABC", "ＡＢＤ", false)]
+// spell-checker: enable
+fn compatible(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new().normalize_with(Normalization::Compatible);
+
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+    assert_eq!(
+        dowild_with(&chars(pattern), &chars(haystack), options),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::class_nfc_member("[äöü]", "u\u{308}", true)]
+#[case::class_nfd_haystack_mismatch("[äö]", "u\u{308}", false)]
+#[case::class_singleton_member("[\u{212b}]", "A\u{30a}", true)]
+#[case::negated_class("[!äö]", "u\u{308}", true)]
+#[case::negated_class_mismatch("[!äöü]", "u\u{308}", false)]
+#[case::negated_class_uncomposable("[!e]", "e\u{301}\u{301}", true)]
+#[case::range("[à-ÿ]", "u\u{308}", true)]
+#[case::case_insensitive("CAFÉ", "cafe\u{301}", true)]
+#[case::braces("{café,thé}!", "the\u{301}!", true)]
+#[case::path("ü/*", "u\u{308}/x", true)]
+#[case::path_one_does_not_match_separator("ü?x", "u\u{308}/x", false)]
+// spell-checker: enable
+fn with_options(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new()
+        .normalize_with(Normalization::Canonical)
+        .enable_classes(true)
+        .enable_braces(true)
+        .case_insensitive(true);
+    let options = if haystack.contains('/') {
+        options.path_separator_with('/')
+    } else {
+        options
+    };
+
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+    assert_eq!(
+        dowild_with(&chars(pattern), &chars(haystack), options),
+        expected
+    );
+}
+
+#[test]
+fn find_and_captures() {
+    let options = Options::new().normalize_with(Normalization::Canonical);
+    let haystack = "xcafe\u{301}!";
+
+    assert_eq!(utf8::find("café", haystack, options), Some(1..7));
+    assert_eq!(utf8::find("?!", haystack, options), Some(4..8));
+    assert_eq!(utf8::find_longest("c*", haystack, options), Some(1..8));
+    assert_eq!(
+        utf8::captures("*?!", haystack, options),
+        Some(vec![0..4, 4..7])
+    );
+
+    let pattern = Pattern::new(&chars("*é*"), options).unwrap();
+    assert_eq!(pattern.find_str(haystack), Some(0..7));
+    assert_eq!(pattern.captures_str(haystack), Some(vec![0..4, 7..8]));
+    assert_eq!(pattern.captures(&chars(haystack)), Some(vec![0..4, 6..7]));
+}
+
+#[test]
+fn without_normalization() {
+    assert_eq!(utf8::dowild("café", "cafe\u{301}"), false);
+    assert_eq!(utf8::dowild("caf?", "cafe\u{301}"), false);
+}
+
+#[test]
+fn wild_set() {
+    let options = Options::new().normalize_with(Normalization::Canonical);
+    let patterns = [chars("café"), chars("thé*"), chars("*.crème")];
+    let set = WildSet::new(patterns, options).unwrap();
+
+    assert_eq!(set.matches(chars("cafe\u{301}")), vec![0]);
+    assert_eq!(set.matches(chars("the\u{301} vert")), vec![1]);
+    assert_eq!(set.matches(chars("creme.cre\u{300}me")), vec![2]);
+    assert_eq!(set.is_match(chars("cafe")), false);
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes() {
+    use simplematch::graphemes;
+
+    let options = Options::new().normalize_with(Normalization::Canonical);
+
+    assert_eq!(graphemes::dowild_with("caf?", "cafe\u{301}", options), true);
+    assert_eq!(graphemes::dowild_with("café", "cafe\u{301}", options), true);
+    assert_eq!(graphemes::find("?", "🇩🇪e\u{301}", options), Some(0..8));
+}
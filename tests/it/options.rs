@@ -1,5 +1,5 @@
 use rstest::rstest;
-use simplematch::{Options, SimpleMatchError, Wildcard};
+use simplematch::{dowild_with, utf8, Options, SimpleMatchError, Wildcard};
 
 #[rstest]
 #[case::default(None, None, None, None, true)]
@@ -60,6 +60,77 @@ fn options_verify(
     }
 }
 
+#[rstest]
+#[case::default(b'[', b']', b'-', true)]
+#[case::custom(b'<', b'>', b'~', true)]
+#[case::open_is_close(b'|', b'|', b'-', false)]
+#[case::open_is_hyphen(b'<', b'>', b'<', false)]
+#[case::close_is_hyphen(b'<', b'>', b'>', false)]
+#[case::open_is_default_any(b'*', b']', b'-', false)]
+#[case::close_is_default_one(b'[', b'?', b'-', false)]
+#[case::hyphen_is_default_escape(b'[', b']', b'\\', false)]
+#[case::hyphen_is_default_negate(b'[', b']', b'!', false)]
+#[case::open_is_default_close(b']', b'>', b'-', true)]
+fn options_verify_class_tokens(
+    #[case] open: u8,
+    #[case] close: u8,
+    #[case] hyphen: u8,
+    #[case] expected: bool,
+) {
+    let options = Options::default()
+        .class_open_with(open)
+        .class_close_with(close)
+        .class_hyphen_with(hyphen);
+
+    if expected {
+        options.verify().unwrap();
+    } else {
+        assert_eq!(
+            options.verify(),
+            Err(SimpleMatchError::DuplicateCharacterAssignment)
+        );
+    }
+}
+
+#[rstest]
+#[case::custom(b'^', true)]
+#[case::same_as_negate(b'!', true)]
+#[case::default_any(b'*', false)]
+#[case::default_escape(b'\\', false)]
+#[case::default_open(b'[', false)]
+#[case::default_hyphen(b'-', false)]
+fn options_verify_class_negate_alternative(#[case] token: u8, #[case] expected: bool) {
+    let options = Options::default().class_negate_alternative_with(token);
+
+    if expected {
+        options.verify().unwrap();
+    } else {
+        assert_eq!(
+            options.verify(),
+            Err(SimpleMatchError::DuplicateCharacterAssignment)
+        );
+    }
+}
+
+#[rstest]
+#[case::brace_open(b'{', true, false)]
+#[case::extglob_close(b')', false, true)]
+fn options_verify_class_tokens_with_groups(
+    #[case] open: u8,
+    #[case] is_braces_enabled: bool,
+    #[case] is_extglob_enabled: bool,
+) {
+    let options = Options::default()
+        .class_open_with(open)
+        .enable_braces(is_braces_enabled)
+        .enable_extglob(is_extglob_enabled);
+
+    assert_eq!(
+        options.verify(),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
+
 #[test]
 fn options_case_insensitive() {
     let actual = Options::<u8>::default().case_insensitive(true);
@@ -121,6 +192,58 @@ fn options_with_custom_negate() {
     assert_eq!(actual, expected);
 }
 
+#[test]
+fn options_with_custom_class_tokens() {
+    let actual = Options::<u8>::default()
+        .class_open_with(b'<')
+        .class_close_with(b'>')
+        .class_hyphen_with(b'~');
+    let mut expected = Options::default();
+    expected.class_open = b'<';
+    expected.class_close = b'>';
+    expected.class_hyphen = b'~';
+    assert_eq!(actual, expected);
+}
+
+#[rstest]
+#[case::range("<a~z>", "x", true)]
+#[case::range_no_match("<a~z>", "X", false)]
+#[case::negated("<!a~z>", "X", true)]
+#[case::close_first("<>a>", ">", true)]
+#[case::default_hyphen_is_literal("<a-z>", "-", true)]
+#[case::default_hyphen_is_literal_no_match("<a-z>", "x", false)]
+#[case::default_brackets_are_literal("[a]", "[a]", true)]
+#[case::named("<<:digit:>>", "7", true)]
+#[case::named_with_default_brackets("<[:digit:]>", "7", false)]
+#[case::invalid("<a", "<a", true)]
+#[case::wildcards("*<0~9>?", "v1.2b", true)]
+#[case::escaped("\\<a>", "<a>", true)]
+fn options_with_custom_class_tokens_matching(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::default()
+        .enable_classes(true)
+        .enable_escape(true)
+        .class_open_with(b'<')
+        .class_close_with(b'>')
+        .class_hyphen_with(b'~');
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+
+    let options = Options::default()
+        .enable_classes(true)
+        .enable_escape(true)
+        .enable_braces(true)
+        .class_open_with('<')
+        .class_close_with('>')
+        .class_hyphen_with('~');
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+}
+
 #[test]
 fn options_verified() {
     let options = Options::<u8>::default().verified().unwrap();
@@ -0,0 +1,237 @@
+use rstest::rstest;
+use simplematch::{captures, dowild_with, Options, Pattern, SimpleMatchError, WildSet};
+
+// spell-checker: disable
+#[rstest]
+#[case::star_in_segment("src/*.rs", "src/lib.rs", true)]
+#[case::star_does_not_cross("src/*.rs", "src/a/b.rs", false)]
+#[case::star_empty_segment("src/*", "src/", true)]
+#[case::star_only("*", "a/b", false)]
+#[case::one_does_not_match_separator("a?b", "a/b", false)]
+#[case::one_in_segment("a?b", "a-b", true)]
+#[case::negated_class_does_not_match_separator("a[!x]b", "a/b", false)]
+#[case::negated_class_in_segment("a[!x]b", "a-b", true)]
+#[case::class_with_separator("a[/]b", "a/b", true)]
+#[case::literal_separator("a/b", "a/b", true)]
+#[case::double_star_without_globstar("src/**/*.rs", "src/a/lib.rs", true)]
+#[case::double_star_without_globstar_no_segments("src/**/*.rs", "src/lib.rs", false)]
+#[case::double_star_without_globstar_two_segments("src/**/*.rs", "src/a/b/lib.rs", false)]
+// spell-checker: enable
+fn dowild_with_path_separator(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .enable_classes(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+
+    let chars = pattern.chars().collect::<Vec<char>>();
+    let compiled = Pattern::new(
+        &chars,
+        Options::new().path_separator_with('/').enable_classes(true),
+    )
+    .unwrap();
+
+    assert_eq!(
+        compiled.is_match(&haystack.chars().collect::<Vec<char>>()),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::zero_segments("src/**/*.rs", "src/lib.rs", true)]
+#[case::one_segment("src/**/*.rs", "src/a/lib.rs", true)]
+#[case::many_segments("src/**/*.rs", "src/a/b/c/lib.rs", true)]
+#[case::wrong_root("src/**/*.rs", "tests/lib.rs", false)]
+#[case::leading("**/*.rs", "lib.rs", true)]
+#[case::leading_many_segments("**/*.rs", "a/b/lib.rs", true)]
+#[case::leading_absolute("**/*.rs", "/a/lib.rs", true)]
+#[case::trailing("src/**", "src/a/b", true)]
+#[case::trailing_empty("src/**", "src/", true)]
+#[case::trailing_needs_separator("src/**", "src", false)]
+#[case::only("**", "a/b/c", true)]
+#[case::consecutive("a/**/**/b", "a/b", true)]
+#[case::consecutive_many_segments("a/**/**/b", "a/x/y/b", true)]
+#[case::not_a_whole_segment("a**/b", "ax/y/b", false)]
+#[case::not_a_whole_segment_in_segment("a**/b", "axy/b", true)]
+#[case::not_at_end_of_segment("**a/b", "x/a/b", false)]
+#[case::triple_star("a/***/b", "a/x/y/b", true)]
+#[case::in_braces("src/{**/*.rs,Cargo.toml}", "src/a/b.rs", true)]
+#[case::in_braces_other("src/{**/*.rs,Cargo.toml}", "src/Cargo.toml", true)]
+#[case::in_braces_at_end("{a,**}", "x/y", true)]
+#[case::in_nested_braces("{a,{b,**}}/c", "x/y/c", true)]
+#[case::in_braces_not_at_segment_end("{**,x}.rs", "a/b.rs", false)]
+#[case::in_braces_not_at_segment_end_in_segment("{**,x}.rs", "ab.rs", true)]
+#[case::in_braces_not_at_segment_start("x{**,y}", "xa/b", false)]
+#[case::in_braces_not_at_segment_start_in_segment("x{**,y}", "xab", true)]
+#[case::in_nested_braces_not_at_segment_start("x{a,{b,**}}", "xa/b", false)]
+// spell-checker: enable
+fn dowild_with_globstar(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .enable_globstar(true)
+        .enable_braces(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+}
+
+#[test]
+fn dowild_with_globstar_without_path_separator() {
+    let options = Options::new().enable_globstar(true);
+
+    assert_eq!(
+        dowild_with(b"src/**/*.rs", b"src/a/b/lib.rs", options),
+        true
+    );
+    assert_eq!(dowild_with(b"src/**/*.rs", b"src/lib.rs", options), false);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::segment_any("INBOX/%", "INBOX/Sent", true)]
+#[case::segment_any_does_not_cross("INBOX/%", "INBOX/Sent/2024", false)]
+#[case::any_crosses("INBOX/*", "INBOX/Sent/2024", true)]
+#[case::mixed("%/*/2024", "INBOX/Sent/Old/2024", true)]
+#[case::mixed_fail("%/*/2024", "INBOX/2024", false)]
+#[case::escaped("a\\%", "a%", true)]
+#[case::one_does_not_cross("a?b", "a/b", false)]
+// spell-checker: enable
+fn dowild_with_wildcard_any_segment(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .wildcard_any_segment_with(b'%')
+        .enable_escape(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+}
+
+#[test]
+fn dowild_with_wildcard_any_segment_without_path_separator() {
+    let options = Options::new().wildcard_any_segment_with(b'%');
+
+    assert_eq!(dowild_with(b"a%c", b"ab/c", options), true);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::star("*", ".git", false)]
+#[case::star_with_dot(".*", ".git", true)]
+#[case::one("?git", ".git", false)]
+#[case::class("[.]git", ".git", false)]
+#[case::negated_class("[!a]git", ".git", false)]
+#[case::star_extension("*.rs", ".hidden.rs", false)]
+#[case::not_leading("a*", "a.b", true)]
+#[case::not_leading_one("a?b", "a.b", true)]
+#[case::alternative_with_dot("{.*,*.rs}", ".env", true)]
+#[case::alternative_without_dot("{*,?env}", ".env", false)]
+#[case::no_separator("a*", "a/.b", true)]
+// spell-checker: enable
+fn dowild_with_protected_leading_dot(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .protect_leading_dot(true)
+        .enable_classes(true)
+        .enable_braces(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::after_separator("src/*", "src/.env", false)]
+#[case::after_separator_with_dot("src/.*", "src/.env", true)]
+#[case::first_segment("*/*", ".a/b", false)]
+#[case::last_segment("*/*", "a/.b", false)]
+#[case::one_after_separator("a/?b", "a/.b", false)]
+#[case::globstar_hidden_directory("**/*.rs", ".git/a.rs", false)]
+#[case::globstar_hidden_directory_inside("a/**/b", "a/.x/b", false)]
+#[case::globstar_at_end("a/**", "a/.x", false)]
+#[case::globstar_with_dot("**/.*", "a/b/.env", true)]
+#[case::not_leading("src/*", "src/a.b", true)]
+// spell-checker: enable
+fn dowild_with_protected_leading_dot_and_path_separator(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .protect_leading_dot(true)
+        .path_separator_with(b'/')
+        .enable_globstar(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+}
+
+#[test]
+fn captures_with_globstar() {
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .enable_globstar(true);
+
+    assert_eq!(
+        captures(b"src/**/*.rs", b"src/a/b/lib.rs", options),
+        Some(vec![4..8, 8..11])
+    );
+    assert_eq!(
+        captures(b"src/**/*.rs", b"src/lib.rs", options),
+        Some(vec![4..4, 4..7])
+    );
+}
+
+#[test]
+fn pattern_with_path_separator_find() {
+    let pattern = Pattern::new(b"b/*", Options::new().path_separator_with(b'/')).unwrap();
+
+    assert_eq!(pattern.find_longest(b"a/b/cd/e"), Some(2..6));
+}
+
+#[test]
+fn wild_set_with_path_patterns() {
+    let options = Options::new()
+        .path_separator_with(b'/')
+        .enable_globstar(true);
+    let set = WildSet::new(["src/**/*.rs", "src/*.rs", "*.rs", "**"], options).unwrap();
+
+    assert_eq!(set.matches("src/lib.rs"), [0, 1, 3]);
+    assert_eq!(set.matches("src/a/lib.rs"), [0, 3]);
+    assert_eq!(set.matches("lib.rs"), [2, 3]);
+}
+
+#[rstest]
+#[case::separator_is_any(Options::new().path_separator_with(b'*'))]
+#[case::separator_is_escape(Options::new().path_separator_with(b'\\'))]
+#[case::segment_is_one(Options::new().wildcard_any_segment_with(b'?'))]
+#[case::same_as_separator(Options::new().path_separator_with(b'/').wildcard_any_segment_with(b'/'))]
+#[case::segment_is_brace(Options::new().enable_braces(true).wildcard_any_segment_with(b','))]
+fn options_verify_with_path_options(#[case] options: Options<u8>) {
+    assert_eq!(
+        options.verify(),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
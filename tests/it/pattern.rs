@@ -0,0 +1,138 @@
+use rstest::rstest;
+use simplematch::{dowild, dowild_with, Options, Pattern, SimpleMatchError};
+
+// spell-checker: disable
+#[rstest]
+#[case::empty("", "", true)]
+#[case::empty_pattern("", "a", false)]
+#[case::empty_haystack("a", "", false)]
+#[case::star_matches_empty("*", "", true)]
+#[case::star_at_end("Hi*", "Hi", true)]
+#[case::mismatch_after_star("ab*d", "abc", false)]
+#[case::multiple_stars("**a**", "bab", true)]
+#[case::star_one("*?", "", false)]
+#[case::star_ones_at_end("a*???", "abcd", true)]
+#[case::star_ones_too_short("a*???", "abc", false)]
+#[case::star_one_middle("*?b*", "aabc", true)]
+#[case::repeat("*issip*ss*", "mississipissippi", true)]
+#[case::repeat_fail("xxxx*zzy*fffff", "xxxxzzzzzzzzyf", false)]
+#[case::backtrack("a*a*a*a*b", "aaaaaaaaaaaaaaaaaaaaab", true)]
+#[case::backtrack_fail("a*a*a*a*b", "aaaaaaaaaaaaaaaaaaaaaa", false)]
+#[case::one_exhausts("??", "a", false)]
+// spell-checker: enable
+fn pattern_is_match_same_as_dowild(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let compiled = Pattern::new(pattern.as_bytes(), Options::default()).unwrap();
+
+    assert_eq!(dowild(pattern.as_bytes(), haystack.as_bytes()), expected);
+    assert_eq!(compiled.is_match(haystack.as_bytes()), expected);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::escape_star("a\\*", "a*", true)]
+#[case::escape_star_no_wildcard("a\\*", "ab", false)]
+#[case::escape_non_special("\\a", "\\a", true)]
+#[case::escape_at_end("a\\", "a\\", true)]
+#[case::escape_escape("\\\\", "\\", true)]
+#[case::escape_after_star("*\\?", "ab?", true)]
+#[case::escape_open_bracket("\\[a]", "[a]", true)]
+#[case::class("[a-c]x", "bx", true)]
+#[case::negative_class("[!a-c]x", "bx", false)]
+#[case::class_after_star("*[0-9]", "abc1", true)]
+#[case::class_after_star_fail("*[0-9]", "abc1a", false)]
+#[case::invalid_class("[abc", "[abc", true)]
+#[case::invalid_class_followed_by_invalid("*[a[b", "xx[a[b", true)]
+#[case::invalid_class_at_end("a[", "a[", true)]
+#[case::close_bracket_first("[]]", "]", true)]
+#[case::empty_class("[]", "[]", true)]
+#[case::fuzz_0("*[![?qd][zU*", "\0\0][zU", true)]
+#[case::fuzz_1("[]--]G", "GG", true)]
+#[case::fuzz_2("[!]a]", ",", true)]
+// spell-checker: enable
+fn pattern_is_match_same_as_dowild_with(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+    #[values(true, false)] case_sensitive: bool,
+) {
+    let options = Options::new()
+        .case_insensitive(!case_sensitive)
+        .enable_escape(true)
+        .enable_classes(true);
+    let compiled = Pattern::new(pattern.as_bytes(), options).unwrap();
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+    assert_eq!(compiled.is_match(haystack.as_bytes()), expected);
+
+    let chars = pattern.chars().collect::<Vec<char>>();
+    let char_options = Options::new()
+        .case_insensitive(!case_sensitive)
+        .enable_escape(true)
+        .enable_classes(true);
+    let compiled = Pattern::new(&chars, char_options).unwrap();
+
+    assert_eq!(
+        compiled.is_match(&haystack.chars().collect::<Vec<char>>()),
+        expected
+    );
+}
+
+#[rstest]
+#[case::same_case("foo*", "foobar", true)]
+#[case::different_case("foo*", "FOOBAR", true)]
+#[case::class("[a-f]OO", "Foo", true)]
+#[case::escaped_is_case_sensitive("a\\*", "A*", true)]
+fn pattern_is_match_when_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new()
+        .case_insensitive(true)
+        .enable_escape(true)
+        .enable_classes(true);
+    let compiled = Pattern::new(pattern.as_bytes(), options).unwrap();
+
+    assert_eq!(compiled.is_match(haystack.as_bytes()), expected);
+}
+
+#[test]
+fn pattern_when_invalid_options() {
+    assert_eq!(
+        Pattern::new(b"*", Options::new().wildcard_any_with(b'?')).unwrap_err(),
+        SimpleMatchError::DuplicateCharacterAssignment
+    );
+}
+
+#[test]
+fn pattern_is_clone_send_and_sync() {
+    fn assert_traits<T: Clone + Send + Sync>(value: &T) -> T {
+        value.clone()
+    }
+
+    let pattern = Pattern::new(b"a*", Options::new()).unwrap();
+    let cloned = assert_traits(&pattern);
+
+    assert_eq!(cloned.is_match(b"abc"), true);
+    assert_eq!(cloned.options(), pattern.options());
+}
+
+#[test]
+fn pattern_matches_many_haystacks() {
+    let pattern = Pattern::new(b"*.[ch]", Options::new().enable_classes(true)).unwrap();
+    let haystacks: [&[u8]; 4] = [b"main.c", b"lib.h", b"lib.rs", b"c"];
+
+    let actual = haystacks
+        .iter()
+        .map(|haystack| pattern.is_match(haystack))
+        .collect::<Vec<bool>>();
+
+    assert_eq!(actual, [true, true, false, false]);
+}
@@ -0,0 +1,104 @@
+use rstest::rstest;
+use simplematch::{dowild_with, Options, SimpleMatchError, WildSet};
+
+const PATTERNS: [&str; 10] = [
+    "Cargo.toml",
+    "*.rs",
+    "src/*",
+    "*.tar.gz",
+    "*test*",
+    "README.?d",
+    "[a-c]*",
+    "\\*.md",
+    "*",
+    "tests/*/main.rs",
+];
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("Cargo.toml", &[0, 8])]
+#[case::extension("main.rs", &[1, 8])]
+#[case::prefix_and_extension("src/lib.rs", &[1, 2, 8])]
+#[case::prefix_and_suffix("tests/it/main.rs", &[1, 4, 8, 9])]
+#[case::double_extension("backup.tar.gz", &[3, 6, 8])]
+#[case::infix("latest", &[4, 8])]
+#[case::one("README.md", &[5, 8])]
+#[case::escaped("*.md", &[7, 8])]
+#[case::only_any("x", &[8])]
+#[case::empty("", &[8])]
+// spell-checker: enable
+fn wild_set_matches(#[case] haystack: &str, #[case] expected: &[usize]) {
+    let options = Options::new().enable_classes(true).enable_escape(true);
+    let set = WildSet::new(PATTERNS, options).unwrap();
+
+    let naive = (0..PATTERNS.len())
+        .filter(|index| dowild_with(PATTERNS[*index].as_bytes(), haystack.as_bytes(), options))
+        .collect::<Vec<usize>>();
+
+    assert_eq!(set.matches(haystack), expected);
+    assert_eq!(naive, expected);
+    assert_eq!(set.is_match(haystack), true);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("cargo.TOML", &[0])]
+#[case::extension("MAIN.RS", &[1])]
+#[case::prefix("SRC/lib.rs", &[1, 2])]
+#[case::escaped_is_case_sensitive("\\A", &[])]
+#[case::no_match("Cargo.lock", &[])]
+// spell-checker: enable
+fn wild_set_matches_when_case_insensitive(#[case] haystack: &str, #[case] expected: &[usize]) {
+    let options = Options::new().case_insensitive(true).enable_escape(true);
+    let set = WildSet::new(["Cargo.toml", "*.rs", "src/*", "\\\\\\a"], options).unwrap();
+
+    assert_eq!(set.matches(haystack), expected);
+    assert_eq!(set.is_match(haystack), !expected.is_empty());
+}
+
+#[test]
+fn wild_set_when_no_pattern_matches() {
+    let set = WildSet::new(["*.c", "*.h", "Makefile"], Options::default()).unwrap();
+
+    assert_eq!(set.matches("main.rs"), Vec::<usize>::new());
+    assert_eq!(set.is_match("main.rs"), false);
+}
+
+#[test]
+fn wild_set_when_empty() {
+    let set = WildSet::new(Vec::<&[u8]>::new(), Options::default()).unwrap();
+
+    assert_eq!(set.is_empty(), true);
+    assert_eq!(set.len(), 0);
+    assert_eq!(set.is_match(b""), false);
+}
+
+#[test]
+fn wild_set_when_duplicate_patterns() {
+    let set = WildSet::new(["*.rs", "*.rs", "lib.rs", "lib.rs"], Options::default()).unwrap();
+
+    assert_eq!(set.len(), 4);
+    assert_eq!(set.matches("lib.rs"), [0, 1, 2, 3]);
+}
+
+#[test]
+fn wild_set_with_chars() {
+    let patterns = ["*.rs", "src/*"]
+        .iter()
+        .map(|pattern| pattern.chars().collect::<Vec<char>>())
+        .collect::<Vec<Vec<char>>>();
+    let set = WildSet::new(&patterns, Options::default()).unwrap();
+
+    assert_eq!(
+        set.matches("src/lib.rs".chars().collect::<Vec<char>>()),
+        [0, 1]
+    );
+}
+
+#[test]
+fn wild_set_when_invalid_options() {
+    assert_eq!(
+        WildSet::new(["*"], Options::new().wildcard_any_with(b'?')).unwrap_err(),
+        SimpleMatchError::DuplicateCharacterAssignment
+    );
+}
@@ -0,0 +1,134 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use rstest::rstest;
+use simplematch::similar::{similar_to, Similar, SimilarError};
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("abc", "abc", true)]
+#[case::literal_is_anchored("abc", "xabcx", false)]
+#[case::literal_is_case_sensitive("abc", "ABC", false)]
+#[case::dot_is_literal("a.c", "abc", false)]
+#[case::dot_is_literal_match("a.c", "a.c", true)]
+#[case::percent("a%", "abc", true)]
+#[case::percent_empty("%", "", true)]
+#[case::underscore("a_c", "abc", true)]
+#[case::underscore_multibyte("_", "ä", true)]
+#[case::alternation("abc|xyz", "xyz", true)]
+#[case::alternation_is_anchored("abc|xyz", "abcxyz", false)]
+#[case::alternation_empty("a|", "", true)]
+#[case::group("a(b|c)d", "acd", true)]
+#[case::group_mismatch("a(b|c)d", "ad", false)]
+#[case::group_empty("a()b", "ab", true)]
+#[case::star("ab*", "abbb", true)]
+#[case::star_zero("ab*", "a", true)]
+#[case::star_applies_to_last("ab*", "abab", false)]
+#[case::plus("ab+", "a", false)]
+#[case::plus_many("ab+", "abb", true)]
+#[case::question("ab?c", "ac", true)]
+#[case::question_two("ab?c", "abbc", false)]
+#[case::group_star("(ab)*", "ababab", true)]
+#[case::group_plus("(ab|cd)+", "abcdab", true)]
+#[case::group_plus_partial("(ab|cd)+", "abcda", false)]
+#[case::exact("a{3}", "aaa", true)]
+#[case::exact_fewer("a{3}", "aa", false)]
+#[case::exact_more("a{3}", "aaaa", false)]
+#[case::at_least("a{2,}", "aaaaa", true)]
+#[case::at_least_fewer("a{2,}", "a", false)]
+#[case::between("a{2,3}", "aaa", true)]
+#[case::between_more("a{2,3}", "aaaa", false)]
+#[case::between_zero("xa{0,1}", "x", true)]
+#[case::zero_times("xa{0}", "x", true)]
+#[case::zero_times_mismatch("xa{0}", "xa", false)]
+#[case::group_repetition("(ab){2}", "abab", true)]
+#[case::class_repetition("[0-9]{3}-[0-9]{4}", "555-1234", true)]
+#[case::class_repetition_mismatch("[0-9]{3}-[0-9]{4}", "555-123", false)]
+#[case::class("[abc]", "b", true)]
+#[case::class_negated("[^abc]", "d", true)]
+#[case::class_negated_mismatch("[^abc]", "a", false)]
+#[case::class_named("[[:digit:]]+", "2024", true)]
+#[case::class_with_special("[|*]", "*", true)]
+#[case::close_bracket_is_literal("a]", "a]", true)]
+#[case::close_brace_is_literal("a}", "a}", true)]
+#[case::nested("((a|b)c)+", "acbc", true)]
+#[case::nested_repetition("((a*)*)*b", "aaab", true)]
+#[case::percent_in_group("(%.rs|%.toml)", "Cargo.toml", true)]
+#[case::escaped_percent("100\\%", "100%", true)]
+#[case::escaped_percent_mismatch("100\\%", "1000", false)]
+#[case::escaped_pipe("a\\|b", "a|b", true)]
+#[case::escaped_parenthesis("\\(a\\)", "(a)", true)]
+#[case::escaped_star("a\\*", "a*", true)]
+#[case::escaped_bracket("\\[a]", "[a]", true)]
+#[case::escaped_escape("a\\\\", "a\\", true)]
+// spell-checker: enable
+fn similar_to_with_escape(#[case] pattern: &str, #[case] string: &str, #[case] expected: bool) {
+    assert_eq!(similar_to(pattern, string, Some('\\')), Ok(expected));
+    assert_eq!(
+        Similar::new(pattern, Some('\\')).unwrap().is_match(string),
+        expected
+    );
+}
+
+#[rstest]
+#[case::backslash_is_literal("a\\b", None, "a\\b", true)]
+#[case::trailing_backslash("a\\", None, "a\\", true)]
+#[case::custom_escape("a#%", Some('#'), "a%", true)]
+#[case::custom_escape_backslash_is_literal("a\\#_", Some('#'), "a\\_", true)]
+fn similar_to_with_other_escape(
+    #[case] pattern: &str,
+    #[case] escape: Option<char>,
+    #[case] string: &str,
+    #[case] expected: bool,
+) {
+    assert_eq!(similar_to(pattern, string, escape), Ok(expected));
+}
+
+#[rstest]
+#[case::trailing_escape("abc\\", SimilarError::TrailingEscape)]
+#[case::escaped_letter("a\\b", SimilarError::InvalidEscapeSequence(1))]
+#[case::escaped_letter_byte_offset("ä\\b", SimilarError::InvalidEscapeSequence(2))]
+#[case::unclosed_parenthesis("a(b|c", SimilarError::UnmatchedParenthesis(1))]
+#[case::nested_unclosed_parenthesis("((a)", SimilarError::UnmatchedParenthesis(0))]
+#[case::unmatched_close("a)b", SimilarError::UnmatchedParenthesis(1))]
+#[case::unterminated_bracket("a[bc", SimilarError::UnterminatedBracket(1))]
+#[case::leading_star("*a", SimilarError::MisplacedQuantifier(0))]
+#[case::star_after_pipe("a|+b", SimilarError::MisplacedQuantifier(2))]
+#[case::star_after_open("(?a)", SimilarError::MisplacedQuantifier(1))]
+#[case::double_quantifier("a**", SimilarError::MisplacedQuantifier(2))]
+#[case::repetition_after_quantifier("a+{2}", SimilarError::MisplacedQuantifier(2))]
+#[case::leading_brace("{2}", SimilarError::MisplacedQuantifier(0))]
+#[case::repetition_empty("a{}", SimilarError::InvalidRepetition(1))]
+#[case::repetition_unclosed("a{2", SimilarError::InvalidRepetition(1))]
+#[case::repetition_no_number("a{x}", SimilarError::InvalidRepetition(1))]
+#[case::repetition_no_min("a{,2}", SimilarError::InvalidRepetition(1))]
+#[case::repetition_reversed("a{3,2}", SimilarError::InvalidRepetition(1))]
+#[case::repetition_too_large("a{256}", SimilarError::InvalidRepetition(1))]
+#[case::repetition_overflow("a{99999999999}", SimilarError::InvalidRepetition(1))]
+#[case::expansion_too_large("((a{255}){255}){255}", SimilarError::PatternTooLarge)]
+fn similar_to_errors(#[case] pattern: &str, #[case] expected: SimilarError) {
+    assert_eq!(similar_to(pattern, "", Some('\\')), Err(expected));
+}
+
+#[test]
+fn similar_to_is_linear() {
+    let string = "a".repeat(5000);
+    let start = Instant::now();
+
+    assert_eq!(similar_to("(a|aa)*(a|aa)*b", &string, None), Ok(false));
+    assert_eq!(similar_to("((a*)*)*", &string, None), Ok(true));
+    assert_eq!(similar_to("(a?){50}a{50}", &"a".repeat(50), None), Ok(true));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn similar_error_display() {
+    assert_eq!(
+        SimilarError::UnmatchedParenthesis(4).to_string(),
+        "Invalid SIMILAR TO pattern: The parenthesis at byte 4 is unmatched."
+    );
+    assert_eq!(
+        SimilarError::MisplacedQuantifier(0).to_string(),
+        "Invalid SIMILAR TO pattern: The quantifier at byte 0 has nothing to repeat."
+    );
+}
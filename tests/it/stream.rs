@@ -0,0 +1,287 @@
+use std::io::{self, ErrorKind, Read};
+
+use rstest::rstest;
+use simplematch::{dowild_with, utf8, Options, SimpleMatchError, StreamMatcher, Wildcard};
+
+/// A reader which returns the bytes one at a time and is interrupted before each of them
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    is_interrupted: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.is_interrupted = !self.is_interrupted;
+        if self.is_interrupted {
+            return Err(ErrorKind::Interrupted.into());
+        }
+        match self.bytes.split_first() {
+            Some((first, rest)) => {
+                buf[0] = *first;
+                self.bytes = rest;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+/// Feeds the `haystack` in all splits into two chunks and one token at a time and checks the
+/// verdicts against the `expected` result.
+fn assert_stream<T>(matcher: &mut StreamMatcher<T>, haystack: &[T], expected: bool, info: &str)
+where
+    T: Wildcard + Ord,
+{
+    for split in 0..=haystack.len() {
+        let (first, second) = haystack.split_at(split);
+        for chunk in [first, second] {
+            let verdict = matcher.feed(chunk);
+            assert!(
+                verdict.map_or(true, |verdict| verdict == expected),
+                "{info}"
+            );
+            assert_eq!(verdict, matcher.verdict(), "{info}");
+        }
+        assert_eq!(matcher.finish(), expected, "split: {split}, {info}");
+    }
+
+    for token in haystack {
+        matcher.feed(core::slice::from_ref(token));
+    }
+    assert_eq!(matcher.finish(), expected, "one at a time, {info}");
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::empty("", "", Options::new(), true)]
+#[case::star_of_empty("*", "", Options::new(), true)]
+#[case::literal("abc", "abc", Options::new(), true)]
+#[case::literal_too_long("abc", "abcd", Options::new(), false)]
+#[case::star("a*c", "abbc", Options::new(), true)]
+#[case::trailing_star("ab*", "abx", Options::new(), true)]
+#[case::case_insensitive("*.RS", "lib.rs", Options::new().case_insensitive(true), true)]
+#[case::class("[!a]?", "ba", Options::new().enable_classes(true), true)]
+#[case::escape("\\*a*", "*ab", Options::new().enable_escape(true), true)]
+#[case::braces("{a,b}*.rs", "b/x.rs", Options::new().enable_braces(true), true)]
+#[case::repetition("+(ab)", "ababab", Options::new().enable_extglob(true), true)]
+#[case::repetition_incomplete("+(ab)", "ababa", Options::new().enable_extglob(true), false)]
+#[case::separator("src/*", "src/a/b", Options::new().path_separator_with(b'/'), false)]
+#[case::leading_dot(
+    "src/*",
+    "src/.git",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    false
+)]
+#[case::leading_dot_without_separator("*", ".a", Options::new().protect_leading_dot(true), false)]
+#[case::globstar(
+    "src/**/*.rs",
+    "src/a/b/lib.rs",
+    Options::new().path_separator_with(b'/').enable_globstar(true),
+    true
+)]
+#[case::any_segment(
+    "%/*",
+    "a/b/c",
+    Options::new().path_separator_with(b'/').wildcard_any_segment_with(b'%'),
+    true
+)]
+#[case::any_segment_leading_dot(
+    "*",
+    "a/.b",
+    Options::new()
+        .path_separator_with(b'/')
+        .wildcard_any_segment_with(b'%')
+        .protect_leading_dot(true),
+    false
+)]
+// spell-checker: enable
+fn stream_matcher_in_chunks(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] options: Options<u8>,
+    #[case] expected: bool,
+) {
+    let mut matcher = StreamMatcher::new(pattern.as_bytes(), options).unwrap();
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+        expected
+    );
+    assert_stream(&mut matcher, haystack.as_bytes(), expected, pattern);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::one_is_a_character("a?c", "aäc", Options::new(), true)]
+#[case::star_over_characters("*🦀", "ä🦞🦀", Options::new(), true)]
+#[case::class_range("[ä-ü]*", "öx", Options::new().enable_classes(true), true)]
+#[case::case_insensitive("A*ö", "aüö", Options::new().case_insensitive(true), true)]
+#[case::separator("ä/?", "ä/ö", Options::new().path_separator_with('/'), true)]
+// spell-checker: enable
+fn stream_matcher_of_chars_in_chunks(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] options: Options<char>,
+    #[case] expected: bool,
+) {
+    let chars = pattern.chars().collect::<Vec<char>>();
+    let mut matcher = StreamMatcher::new(&chars, options).unwrap();
+
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+    assert_stream(
+        &mut matcher,
+        &haystack.chars().collect::<Vec<char>>(),
+        expected,
+        pattern,
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::trailing_any("abc*", Options::new(), &["ab", "c"], Some(true))]
+#[case::mismatch("abc*", Options::new(), &["ab", "x"], Some(false))]
+#[case::too_long("ab", Options::new(), &["ab", "c"], Some(false))]
+#[case::suffix("*.rs", Options::new(), &["lib", ".rs"], None)]
+#[case::separator("src/*", Options::new().path_separator_with(b'/'), &["src/", "lib"], None)]
+#[case::globstar(
+    "src/**",
+    Options::new().path_separator_with(b'/').enable_globstar(true),
+    &["src/", "a/b"],
+    Some(true)
+)]
+#[case::leading_dot_at_start(
+    "*",
+    Options::new().protect_leading_dot(true),
+    &["", "a"],
+    Some(true)
+)]
+#[case::leading_dot_first("*", Options::new().protect_leading_dot(true), &[".", "a"], Some(false))]
+#[case::any_segment_leading_dot(
+    "*",
+    Options::new()
+        .path_separator_with(b'/')
+        .wildcard_any_segment_with(b'%')
+        .protect_leading_dot(true),
+    &["a/", "b"],
+    None
+)]
+// spell-checker: enable
+fn stream_matcher_verdict(
+    #[case] pattern: &str,
+    #[case] options: Options<u8>,
+    #[case] chunks: &[&str],
+    #[case] expected: Option<bool>,
+) {
+    let mut matcher = StreamMatcher::new(pattern.as_bytes(), options).unwrap();
+
+    for chunk in chunks {
+        matcher.feed(chunk.as_bytes());
+    }
+    assert_eq!(matcher.verdict(), expected);
+}
+
+#[test]
+fn stream_matcher_verdict_of_empty_haystack() {
+    assert_eq!(
+        StreamMatcher::new(b"*", Options::new()).unwrap().verdict(),
+        Some(true)
+    );
+    assert_eq!(
+        StreamMatcher::new(b"a", Options::new()).unwrap().verdict(),
+        None
+    );
+}
+
+#[test]
+fn stream_matcher_finish_resets() {
+    let mut matcher = StreamMatcher::new(b"a*", Options::new()).unwrap();
+
+    assert_eq!(matcher.feed(b"b"), Some(false));
+    assert_eq!(matcher.finish(), false);
+    assert_eq!(matcher.verdict(), None);
+    assert_eq!(matcher.feed(b"a"), Some(true));
+    assert_eq!(matcher.finish(), true);
+}
+
+#[rstest]
+#[case::not("!(a)*", Options::new().enable_extglob(true), SimpleMatchError::NotStreamable)]
+#[case::not_nested(
+    "@(a|!(b))",
+    Options::new().enable_extglob(true),
+    SimpleMatchError::NotStreamable
+)]
+#[case::duplicate(
+    "a",
+    Options::new().wildcard_one_with(b'*'),
+    SimpleMatchError::DuplicateCharacterAssignment
+)]
+fn stream_matcher_errors(
+    #[case] pattern: &str,
+    #[case] options: Options<u8>,
+    #[case] expected: SimpleMatchError,
+) {
+    assert_eq!(
+        StreamMatcher::new(pattern.as_bytes(), options).unwrap_err(),
+        expected
+    );
+}
+
+#[test]
+fn stream_matcher_error_when_strict() {
+    let options = Options::new().enable_classes(true).strict(true);
+
+    assert!(matches!(
+        StreamMatcher::new(b"[a-z", options),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+}
+
+#[cfg(feature = "normalization")]
+#[test]
+fn stream_matcher_error_when_normalized() {
+    let options = Options::new().normalize_with(simplematch::Normalization::Canonical);
+
+    assert_eq!(
+        StreamMatcher::new(&['é'], options).unwrap_err(),
+        SimpleMatchError::NotStreamable
+    );
+}
+
+#[rstest]
+#[case::match_early("*ERROR*", "INFO\nERROR\nINFO\n", Some(true), true)]
+#[case::mismatch_early("INFO*", "ERROR\nINFO\n", Some(false), false)]
+#[case::till_end("*.log", "debug.log", None, true)]
+#[case::empty("", "", None, true)]
+fn stream_matcher_feed_reader(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] verdict: Option<bool>,
+    #[case] expected: bool,
+) {
+    let mut matcher = StreamMatcher::new(pattern.as_bytes(), Options::new()).unwrap();
+    let reader = Trickle {
+        bytes: haystack.as_bytes(),
+        is_interrupted: false,
+    };
+
+    assert_eq!(matcher.feed_reader(reader).unwrap(), verdict);
+    assert_eq!(matcher.finish(), expected);
+}
+
+#[test]
+fn stream_matcher_feed_reader_error() {
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    let mut matcher = StreamMatcher::new(b"*", Options::new().protect_leading_dot(true)).unwrap();
+
+    assert_eq!(
+        matcher.feed_reader(Failing).unwrap_err().kind(),
+        ErrorKind::BrokenPipe
+    );
+}
@@ -0,0 +1,268 @@
+use rstest::rstest;
+use simplematch::{dowild_with, to_regex_string, utf8, Options, SimpleMatchError};
+
+// spell-checker: disable
+#[rstest]
+#[case::metacharacters(".+(|)^$*", Options::new(), &[".+(|)^$", ".+(|)^$x"], &["a+(|)^$"])]
+#[case::newline("a?c", Options::new(), &["a\nc"], &["ac"])]
+#[case::class_metacharacters(
+    "[]^-]",
+    Options::new().enable_classes(true),
+    &["]", "^", "-"],
+    &["a", "[]^-]"]
+)]
+#[case::case_insensitive_escape(
+    "A\\*",
+    Options::new().case_insensitive(true).enable_escape(true),
+    &["a*"],
+    &["ax", "a\\*"]
+)]
+#[case::empty_alternative("a{,b}c", Options::new().enable_braces(true), &["ac", "abc"], &["abbc"])]
+#[case::extglob_repetition(
+    "*(a|/)b",
+    Options::new().enable_extglob(true),
+    &["b", "a/ab"],
+    &["ac", "a/a"]
+)]
+#[case::separator("*/?", Options::new().path_separator_with(b'/'), &["a/b", "/b"], &["a/bc", "a/b/c"])]
+#[case::leading_dot(
+    "*/.?",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    &["a/.b", "/.b"],
+    &[".a/.b"]
+)]
+#[case::leading_dot_without_separator(
+    "*",
+    Options::new().protect_leading_dot(true),
+    &["a", "a/.b"],
+    &[".a"]
+)]
+#[case::globstar(
+    "**/*.rs",
+    Options::new().path_separator_with(b'/').enable_globstar(true),
+    &["lib.rs", "a/b/lib.rs"],
+    &["a/lib.c"]
+)]
+#[case::globstar_hidden(
+    "**/*.rs",
+    Options::new().path_separator_with(b'/').enable_globstar(true).protect_leading_dot(true),
+    &["a/lib.rs"],
+    &[".git/lib.rs", "a/.x/lib.rs"]
+)]
+#[case::any_segment(
+    "%/*",
+    Options::new().path_separator_with(b'/').wildcard_any_segment_with(b'%'),
+    &["a/b/c", "/b"],
+    &["a", "ab"]
+)]
+// spell-checker: enable
+fn to_regex_string_matches_like_dowild_with(
+    #[case] pattern: &str,
+    #[case] options: Options<u8>,
+    #[case] matching: &[&str],
+    #[case] not_matching: &[&str],
+) {
+    let regex = to_regex_string(pattern.as_bytes(), options).unwrap();
+    let compiled = regex::bytes::Regex::new(&regex).unwrap();
+
+    for haystack in matching {
+        assert!(
+            compiled.is_match(haystack.as_bytes()),
+            "{haystack:?}, {regex}"
+        );
+        assert!(
+            dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+            "{haystack:?}"
+        );
+    }
+    for haystack in not_matching {
+        assert!(
+            !compiled.is_match(haystack.as_bytes()),
+            "{haystack:?}, {regex}"
+        );
+        assert!(
+            !dowild_with(pattern.as_bytes(), haystack.as_bytes(), options),
+            "{haystack:?}"
+        );
+    }
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::one_is_a_character("?", Options::new(), &["ä", "🦀"], &["", "ab"])]
+#[case::range("[ä-ü]", Options::new().enable_classes(true), &["ö"], &["a", "ÿ"])]
+#[case::separator("?/*", Options::new().path_separator_with('/'), &["ä/b"], &["/b", "äb/c"])]
+// spell-checker: enable
+fn to_regex_string_chars_matches_like_dowild_with(
+    #[case] pattern: &str,
+    #[case] options: Options<char>,
+    #[case] matching: &[&str],
+    #[case] not_matching: &[&str],
+) {
+    let regex = utf8::to_regex_string(pattern, options).unwrap();
+    let compiled = regex::Regex::new(&regex).unwrap();
+
+    for haystack in matching {
+        assert!(compiled.is_match(haystack), "{haystack:?}, {regex}");
+        assert!(
+            utf8::dowild_with(pattern, haystack, options),
+            "{haystack:?}"
+        );
+    }
+    for haystack in not_matching {
+        assert!(!compiled.is_match(haystack), "{haystack:?}, {regex}");
+        assert!(
+            !utf8::dowild_with(pattern, haystack, options),
+            "{haystack:?}"
+        );
+    }
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("abc", Options::new(), "(?s-u)^abc$")]
+#[case::wildcards("a*?", Options::new(), "(?s-u)^a.*.$")]
+#[case::metacharacters(".+(|)^$", Options::new(), "(?s-u)^\\.\\+\\(\\|\\)\\^\\$$")]
+#[case::non_ascii("ä", Options::new(), "(?s-u)^\\xC3\\xA4$")]
+#[case::case_insensitive(
+    "a\\*",
+    Options::new().case_insensitive(true).enable_escape(true),
+    "(?is-u)^a(?-i:\\*)$"
+)]
+#[case::class("[!a-c]", Options::new().enable_classes(true), "(?s-u)^[^a-c]$")]
+#[case::class_escape(
+    "[\\]^a]",
+    Options::new().enable_classes(true).enable_escape(true).enable_class_escape(true),
+    "(?s-u)^[\\]\\^a]$"
+)]
+#[case::named_class("[[:alpha:]_]", Options::new().enable_classes(true), "(?s-u)^[[:alpha:]_]$")]
+#[case::named_class_case_insensitive(
+    "[![:upper:]b]",
+    Options::new().enable_classes(true).case_insensitive(true),
+    "(?is-u)^(?-i:[^[:upper:]bB])$"
+)]
+#[case::braces("{a,b}", Options::new().enable_braces(true), "(?s-u)^(?:a|b)$")]
+#[case::extglob("+(a|b)", Options::new().enable_extglob(true), "(?s-u)^(?:a|b)+$")]
+#[case::separator("*/?", Options::new().path_separator_with(b'/'), "(?s-u)^[^/]*/[^/]$")]
+#[case::leading_dot(
+    "*/.?",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    "(?s-u)^(?:[^\\./][^/]*)?/\\.[^/]$"
+)]
+#[case::globstar(
+    "**/*",
+    Options::new().path_separator_with(b'/').enable_globstar(true),
+    "(?s-u)^(?:[^/]*/)*[^/]*$"
+)]
+// spell-checker: enable
+fn to_regex_string_bytes(
+    #[case] pattern: &str,
+    #[case] options: Options<u8>,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        to_regex_string(pattern.as_bytes(), options).unwrap(),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::literal("äb", Options::new(), "(?s)^äb$")]
+#[case::named_class("[[:alpha:]]", Options::new().enable_classes(true), "(?s)^[\\p{Alphabetic}]$")]
+// spell-checker: enable
+fn to_regex_string_chars(
+    #[case] pattern: &str,
+    #[case] options: Options<char>,
+    #[case] expected: &str,
+) {
+    assert_eq!(utf8::to_regex_string(pattern, options).unwrap(), expected);
+}
+
+#[cfg(not(feature = "unicode"))]
+#[test]
+fn to_regex_string_chars_case_insensitive_without_unicode() {
+    let options = Options::new().case_insensitive(true).enable_classes(true);
+
+    assert_eq!(
+        utf8::to_regex_string("ä[x-z]", options).unwrap(),
+        "(?s)^ä[x-zX-Z]$"
+    );
+    assert_eq!(utf8::to_regex_string("a", options).unwrap(), "(?s)^[aA]$");
+}
+
+#[rstest]
+#[case::not(
+    "!(a)",
+    Options::new().enable_extglob(true),
+    SimpleMatchError::NoRegexEquivalent
+)]
+#[case::not_nested(
+    "@(a|!(b))",
+    Options::new().enable_extglob(true),
+    SimpleMatchError::NoRegexEquivalent
+)]
+#[case::not_in_braces(
+    "{a,!(b)}",
+    Options::new().enable_braces(true).enable_extglob(true),
+    SimpleMatchError::NoRegexEquivalent
+)]
+#[case::unknown_leading_dot(
+    "*?",
+    Options::new().path_separator_with(b'/').protect_leading_dot(true),
+    SimpleMatchError::NoRegexEquivalent
+)]
+#[case::duplicate(
+    "a",
+    Options::new().wildcard_one_with(b'*'),
+    SimpleMatchError::DuplicateCharacterAssignment
+)]
+fn to_regex_string_errors(
+    #[case] pattern: &str,
+    #[case] options: Options<u8>,
+    #[case] expected: SimpleMatchError,
+) {
+    assert_eq!(to_regex_string(pattern.as_bytes(), options), Err(expected));
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn to_regex_string_named_class_case_insensitive() {
+    let options = Options::new().case_insensitive(true).enable_classes(true);
+
+    assert_eq!(
+        utf8::to_regex_string("[[:upper:]]", options).unwrap(),
+        "(?is)^(?-i:[\\p{Uppercase}])$"
+    );
+    assert_eq!(
+        utf8::to_regex_string("[[:upper:]b]", options).unwrap(),
+        "(?is)^(?:(?-i:[\\p{Uppercase}])|[b])$"
+    );
+    assert_eq!(
+        utf8::to_regex_string("[![:upper:]b]", options),
+        Err(SimpleMatchError::NoRegexEquivalent)
+    );
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn to_regex_string_full_case_folding() {
+    let options = Options::new().case_insensitive(true);
+
+    assert_eq!(
+        utf8::to_regex_string("straße", options),
+        Err(SimpleMatchError::NoRegexEquivalent)
+    );
+    assert_eq!(utf8::to_regex_string("ä", options).unwrap(), "(?is)^ä$");
+}
+
+#[cfg(feature = "normalization")]
+#[test]
+fn to_regex_string_normalization() {
+    let options = Options::new().normalize_with(simplematch::Normalization::Canonical);
+
+    assert_eq!(
+        utf8::to_regex_string("é", options),
+        Err(SimpleMatchError::NoRegexEquivalent)
+    );
+}
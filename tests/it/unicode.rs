@@ -0,0 +1,154 @@
+//! Tests for the Unicode case folding of the `unicode` feature
+
+use rstest::rstest;
+use simplematch::{dowild_with, utf8, Options, Pattern, WildSet};
+
+fn chars(string: &str) -> Vec<char> {
+    string.chars().collect()
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::ascii("foo*", "FOOBAR", true)]
+#[case::greek_sigma("σ", "Σ", true)]
+#[case::greek_final_sigma("ς", "Σ", true)]
+#[case::greek_word("ΟΔΥΣΣΕΥΣ", "οδυσσευς", true)]
+#[case::cyrillic("привет*", "ПРИВЕТ МИР", true)]
+#[case::kelvin_sign("k", "\u{212a}", true)]
+#[case::long_s("S", "ſ", true)]
+#[case::german_umlaut("über*", "ÜBERALL", true)]
+#[case::different_letters("σ", "Τ", false)]
+#[case::sharp_s_to_double_s("straße*", "STRASSE", true)]
+#[case::double_s_to_sharp_s("STRASSE", "straße", true)]
+#[case::sharp_s_to_capital_sharp_s("straße", "STRAẞE", true)]
+#[case::capital_sharp_s_to_double_s("STRAẞE", "strasse", true)]
+#[case::sharp_s_mixed_case("ß", "sS", true)]
+#[case::sharp_s_with_any("stra*e", "STRAßE", true)]
+#[case::sharp_s_single_s("ß", "s", false)]
+#[case::sharp_s_triple_s("ß", "sss", false)]
+#[case::sharp_s_split_by_any("s*", "ß", false)]
+#[case::ligature("ﬁle", "FILE", true)]
+#[case::ligature_in_haystack("file", "ﬁle", true)]
+#[case::ligature_across_characters("ﬀi", "fﬁ", true)]
+#[case::dotted_capital_i("İstanbul", "i\u{307}stanbul", true)]
+#[case::dotless_i_is_not_folded("ı", "I", false)]
+#[case::one_matches_sharp_s("stra?e", "STRAßE", true)]
+#[case::one_does_not_match_double_s("stra?e", "STRASSE", false)]
+#[case::escaped_is_case_sensitive("\\Σ", "σ", false)]
+// spell-checker: enable
+fn dowild_with_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let pattern = chars(pattern);
+    let haystack = chars(haystack);
+    let options = Options::new().case_insensitive(true).enable_escape(true);
+
+    assert_eq!(dowild_with(&pattern, &haystack, options), expected);
+    assert_eq!(
+        Pattern::new(&pattern, options).unwrap().is_match(&haystack),
+        expected
+    );
+    assert_eq!(
+        Pattern::new(&pattern, options.enable_braces(true))
+            .unwrap()
+            .is_match(&haystack),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::sharp_s("ß", "ss")]
+#[case::sigma("σ", "Σ")]
+#[case::ligature("ﬁ", "fi")]
+// spell-checker: enable
+fn dowild_with_case_sensitive(#[case] pattern: &str, #[case] haystack: &str) {
+    assert_eq!(
+        dowild_with(&chars(pattern), &chars(haystack), Options::new()),
+        false
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::greek_range("[α-ω]", 'Σ', true)]
+#[case::greek_range_upper("[Α-Ω]", 'ς', true)]
+#[case::kelvin_sign_in_ascii_range("[a-z]", '\u{212a}', true)]
+#[case::ascii_letter_in_kelvin_range("[\u{2100}-\u{2200}]", 'k', true)]
+#[case::long_s_in_ascii_range("[r-t]", 'ſ', true)]
+#[case::cyrillic_range("[а-я]", 'Ж', true)]
+#[case::cyrillic_range_mismatch("[а-я]", 'Σ', false)]
+#[case::sharp_s_class("[ß]", 'ẞ', true)]
+#[case::negated_class("[!σ]", 'Σ', false)]
+// spell-checker: enable
+fn dowild_with_class_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: char,
+    #[case] expected: bool,
+) {
+    let pattern = chars(pattern);
+    let options = Options::new().case_insensitive(true).enable_classes(true);
+
+    assert_eq!(dowild_with(&pattern, &[haystack], options), expected);
+    assert_eq!(
+        Pattern::new(&pattern, options)
+            .unwrap()
+            .is_match(&[haystack]),
+        expected
+    );
+}
+
+#[test]
+fn class_matches_single_character_only() {
+    let options = Options::new().case_insensitive(true).enable_classes(true);
+
+    assert_eq!(dowild_with(&chars("[ß]"), &chars("ss"), options), false);
+    assert_eq!(dowild_with(&chars("[s][s]"), &chars("ß"), options), false);
+}
+
+#[test]
+fn pattern_find_and_captures() {
+    let options = Options::new().case_insensitive(true);
+
+    let pattern = Pattern::new(&chars("ss"), options).unwrap();
+    assert_eq!(pattern.find(&chars("Straße")), Some(4..5));
+
+    let pattern = Pattern::new(&chars("*ss*"), options).unwrap();
+    assert_eq!(pattern.captures(&chars("Straße")), Some(vec![0..4, 5..6]));
+    assert_eq!(pattern.captures(&chars("STRASSE")), Some(vec![0..4, 6..7]));
+}
+
+#[test]
+fn braces_with_full_case_folding() {
+    let options = Options::new().case_insensitive(true).enable_braces(true);
+    let pattern = Pattern::new(&chars("{straße,gasse}*"), options).unwrap();
+
+    assert_eq!(pattern.is_match(&chars("STRASSENBAHN")), true);
+    assert_eq!(pattern.is_match(&chars("GAẞE")), true);
+    assert_eq!(pattern.is_match(&chars("weg")), false);
+}
+
+#[test]
+fn wild_set_with_full_case_folding() {
+    let patterns = ["straße.*", "*.txt", "STRASSE", "*ss*"].map(chars);
+    let set = WildSet::new(patterns, Options::new().case_insensitive(true)).unwrap();
+
+    assert_eq!(set.matches(chars("STRASSE.TXT")), vec![0, 1, 3]);
+    assert_eq!(set.matches(chars("Straße")), vec![2, 3]);
+    assert_eq!(set.matches(chars("STRAẞE")), vec![2, 3]);
+}
+
+#[test]
+fn utf8_with_full_case_folding() {
+    let options = Options::new().case_insensitive(true);
+
+    assert_eq!(utf8::dowild_with("straße*", "STRASSENBAHN", options), true);
+    assert_eq!(utf8::dowild_with("STRASSE", "straße", options), true);
+    assert_eq!(utf8::find("ss", "Straße", options), Some(4..6));
+    assert_eq!(
+        utf8::captures("*ss*", "Straße", options),
+        Some(vec![0..4, 6..7])
+    );
+}
@@ -0,0 +1,168 @@
+use rstest::rstest;
+use simplematch::{
+    dowild, dowild_with, to_regex_string, utf16, utf8, DoWild, Options, Pattern, SimpleMatchError,
+};
+
+fn encode(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::class("[a-c]*.rs", "b.rs", Options::new().enable_classes(true), true)]
+#[case::braces("{a,b}x*", "bxy", Options::new().enable_braces(true), true)]
+#[case::extglob("@(ab|c)?", "cd", Options::new().enable_extglob(true), true)]
+#[case::escape("\\*a", "*a", Options::new().enable_escape(true), true)]
+#[case::separator("a?b", "a/b", Options::new().path_separator_with(u16::from(b'/')), false)]
+#[case::globstar(
+    "src/**/*.rs",
+    "src/a/b/lib.rs",
+    Options::new().path_separator_with(u16::from(b'/')).enable_globstar(true),
+    true
+)]
+#[case::leading_dot(".*", ".git", Options::new().protect_leading_dot(true), true)]
+#[case::hidden("*", ".git", Options::new().protect_leading_dot(true), false)]
+// spell-checker: enable
+fn code_units_with_options(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] options: Options<u16>,
+    #[case] expected: bool,
+) {
+    let pattern = encode(pattern);
+    let haystack = encode(haystack);
+
+    assert_eq!(dowild_with(&pattern, &haystack, options), expected);
+    assert_eq!(
+        Pattern::new(&pattern, options).unwrap().is_match(&haystack),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::one_is_a_pair("?", "🦀", Options::new(), true)]
+#[case::one_before_a_pair("?", "a🦀", Options::new(), false)]
+#[case::two_ones_around_a_pair("??", "a🦀", Options::new(), true)]
+#[case::star_over_pairs("a*b", "a🦀🦞b", Options::new(), true)]
+#[case::literal_pair_at_end("*🦀", "a🦀", Options::new(), true)]
+#[case::other_pair("*🦀", "a🦞", Options::new(), false)]
+#[case::class_of_pairs("[🦀-🦅]", "🦂", Options::new().enable_classes(true), true)]
+#[case::negated_class_of_pair("[!🦀]", "🦞", Options::new().enable_classes(true), true)]
+#[case::braces_of_pairs("{🦀,🦞}x", "🦞x", Options::new().enable_braces(true), true)]
+#[case::separator_after_pair("🦀/?", "🦀/a", Options::new().path_separator_with('/'), true)]
+#[case::case_insensitive_around_pair("a*B", "A🦀b", Options::new().case_insensitive(true), true)]
+// spell-checker: enable
+fn code_points_with_surrogate_pairs(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] options: Options<char>,
+    #[case] expected: bool,
+) {
+    let compiled = Pattern::new(&pattern.chars().collect::<Vec<char>>(), options).unwrap();
+    let units = encode(haystack);
+
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+    assert_eq!(
+        utf16::dowild_with(&encode(pattern), &units, options),
+        expected
+    );
+    assert_eq!(compiled.is_match_utf16(&units), expected);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::one_is_one_unit("?", "🦀", false)]
+#[case::two_ones_are_a_pair("??", "🦀", true)]
+#[case::literal_pair("a🦀", "a🦀", true)]
+#[case::ascii_folding("ABC*", "abcdef", true)]
+#[case::no_unicode_folding("Ä", "ä", false)]
+#[case::ascii_range("[A-C]x", "bX", true)]
+#[case::wide_range("[a-ퟻ]", "B", true)]
+#[case::non_ascii_named_class("[[:alpha:]]", "ä", false)]
+// spell-checker: enable
+fn code_units_case_insensitive(
+    #[case] pattern: &str,
+    #[case] haystack: &str,
+    #[case] expected: bool,
+) {
+    let options = Options::new().case_insensitive(true).enable_classes(true);
+
+    assert_eq!(
+        dowild_with(&encode(pattern), &encode(haystack), options),
+        expected
+    );
+}
+
+#[test]
+fn code_units_do_wild() {
+    let pattern = encode("*.r?");
+    let haystack = encode("lib.rs");
+
+    assert_eq!(dowild(&pattern, &haystack), true);
+    assert_eq!(pattern.as_slice().dowild(&haystack), true);
+    assert_eq!(pattern.dowild(haystack.clone()), true);
+    assert_eq!(
+        encode("*.RS").dowild_with(haystack, Options::new().case_insensitive(true)),
+        true
+    );
+}
+
+#[test]
+fn code_units_to_regex_string() {
+    let options = Options::new().enable_classes(true);
+
+    assert_eq!(
+        to_regex_string(&encode("[a-c]*.rs"), options).unwrap(),
+        to_regex_string(b"[a-c]*.rs", Options::new().enable_classes(true)).unwrap()
+    );
+    assert_eq!(
+        to_regex_string(&encode("ä"), options).unwrap(),
+        "(?s-u)^\\x{00E4}$"
+    );
+}
+
+#[test]
+fn code_units_invalid_pattern() {
+    let options = Options::new().enable_classes(true).strict(true);
+
+    let error = match simplematch::validate(&encode("ä[b"), options) {
+        Err(SimpleMatchError::InvalidPattern(error)) => error,
+        other => panic!("unexpected result: {other:?}"),
+    };
+    assert_eq!(error.span(), 1..3);
+    assert_eq!(
+        error.to_string(),
+        "Invalid pattern: The character class is not closed.\n    ä[b\n     ^^\nHelp: Close the \
+         class with `]`."
+    );
+}
+
+#[rstest]
+#[case::lone_high(&[0xd83e], true)]
+#[case::lone_low(&[0xdd80], true)]
+#[case::reversed_pair(&[0xdd80, 0xd83e], false)]
+#[case::pair(&[0xd83e, 0xdd80], true)]
+fn code_points_with_lone_surrogates(#[case] haystack: &[u16], #[case] expected: bool) {
+    assert_eq!(utf16::dowild(&[u16::from(b'?')], haystack), expected);
+}
+
+#[test]
+fn code_points_spans() {
+    let haystack = encode("a🦀b🦞");
+    let options = Options::new();
+
+    assert_eq!(
+        utf16::captures(&encode("?*?"), &haystack, options),
+        Some(vec![0..1, 1..4, 4..6])
+    );
+    assert_eq!(utf16::find(&encode("?b"), &haystack, options), Some(1..4));
+    assert_eq!(
+        utf16::find_longest(&encode("🦀*"), &haystack, options),
+        Some(1..6)
+    );
+    assert_eq!(
+        utf16::find_longest(&[0xd83e, u16::from(b'*')], &[0xd83e, 0xd83e], options),
+        Some(0..2)
+    );
+}
@@ -0,0 +1,148 @@
+//! Tests for the UTF-8 aware matching on `str`
+
+use core::ops::Range;
+
+use rstest::rstest;
+use simplematch::{captures, dowild_with, find, find_longest, utf8, DoWild, Options, Pattern};
+
+fn chars(string: &str) -> Vec<char> {
+    string.chars().collect()
+}
+
+/// Convert the character indices of the `span` into byte offsets of the `string`
+fn to_bytes(string: &str, span: Range<usize>) -> Range<usize> {
+    let offset = |index: usize| {
+        string
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .nth(index)
+            .unwrap_or(string.len())
+    };
+    offset(span.start)..offset(span.end)
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::one_two_bytes("?", "é", true)]
+#[case::one_three_bytes("?", "€", true)]
+#[case::one_four_bytes("?", "🦀", true)]
+#[case::one_two_chars("?", "éé", false)]
+#[case::ones("caf?", "café", true)]
+#[case::too_many_ones("caf??", "café", false)]
+#[case::star("*é", "résumé", true)]
+#[case::star_ones("*??", "ü", false)]
+#[case::star_then_ones("r*??", "résumé", true)]
+#[case::literal("grün", "grün", true)]
+#[case::literal_mismatch("grün", "grun", false)]
+#[case::empty_pattern("", "ä", false)]
+#[case::empty_haystack("*", "", true)]
+// spell-checker: enable
+fn dowild(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    assert_eq!(utf8::dowild(pattern, haystack), expected);
+    assert_eq!(
+        Pattern::new(&chars(pattern), Options::new())
+            .unwrap()
+            .is_match_str(haystack),
+        expected
+    );
+}
+
+#[test]
+fn dowild_on_str_matches_bytes() {
+    assert_eq!("?".dowild("é"), false);
+    assert_eq!("??".dowild("é"), true);
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::class("gr[äöü]n", "grün", true)]
+#[case::class_mismatch("gr[äö]n", "grün", false)]
+#[case::negated_class("gr[!äö]n", "grün", true)]
+#[case::range("[α-ω]", "λ", true)]
+#[case::range_mismatch("[α-ω]", "a", false)]
+#[case::range_across_widths("[a-€]", "é", true)]
+#[case::case_insensitive("CAFé*", "café au lait", true)]
+#[case::escaped("\\??", "?é", true)]
+#[case::braces("{café,thé}?", "thé!", true)]
+#[case::braces_mismatch("{café,thé}?", "thé", false)]
+#[case::path("*/?", "ä/ö", true)]
+#[case::path_one_does_not_match_separator("ä?ö", "ä/ö", false)]
+#[case::leading_dot("ü/*", "ü/.ä", false)]
+// spell-checker: enable
+fn dowild_with_options(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let options = Options::new()
+        .enable_classes(true)
+        .enable_escape(true)
+        .case_insensitive(true);
+    let options = if pattern.contains('{') {
+        options.enable_braces(true)
+    } else if haystack.contains('/') {
+        options.path_separator_with('/').protect_leading_dot(true)
+    } else {
+        options
+    };
+
+    assert_eq!(utf8::dowild_with(pattern, haystack, options), expected);
+    assert_eq!(
+        dowild_with(&chars(pattern), &chars(haystack), options),
+        expected
+    );
+    assert_eq!(
+        Pattern::new(&chars(pattern), options)
+            .unwrap()
+            .is_match_str(haystack),
+        expected
+    );
+}
+
+// spell-checker: disable
+#[rstest]
+#[case::one("?", "äöü")]
+#[case::literal("ö", "äöü")]
+#[case::star("*", "äöü")]
+#[case::leading_star("*ü", "äüöü")]
+#[case::trailing_star("ö*", "äöüö")]
+#[case::inner_star("ä*ö", "xäyöäzö")]
+#[case::star_then_ones("ä*??", "äöüß€")]
+#[case::longest_tail("ä*ö€", "äö€ö€x")]
+#[case::longest_tail_with_one("ä*ö?", "äöxöyz")]
+#[case::class("[αβ]?", "xxβ🦀")]
+#[case::braces("{ä,ö}ü", "äöü")]
+#[case::no_match("ß", "äöü")]
+// spell-checker: enable
+fn find_and_captures(#[case] pattern: &str, #[case] haystack: &str) {
+    let options = Options::new().enable_classes(true);
+    let options = if pattern.contains('{') {
+        options.enable_braces(true)
+    } else {
+        options
+    };
+    let p = chars(pattern);
+    let h = chars(haystack);
+    let to_bytes = |span| to_bytes(haystack, span);
+
+    let shortest = find(&p, &h, options).map(to_bytes);
+    let longest = find_longest(&p, &h, options).map(to_bytes);
+    let spans = captures(&p, &h, options).map(|spans| spans.into_iter().map(to_bytes).collect());
+
+    assert_eq!(utf8::find(pattern, haystack, options), shortest);
+    assert_eq!(utf8::find_longest(pattern, haystack, options), longest);
+    assert_eq!(utf8::captures(pattern, haystack, options), spans);
+
+    let compiled = Pattern::new(&p, options).unwrap();
+    assert_eq!(compiled.find_str(haystack), shortest);
+    assert_eq!(compiled.find_longest_str(haystack), longest);
+    assert_eq!(compiled.captures_str(haystack), spans);
+}
+
+#[test]
+fn offsets_are_char_boundaries() {
+    let haystack = "🦀ä€x";
+    let spans = utf8::captures("?*?", haystack, Options::new()).unwrap();
+
+    assert_eq!(spans, vec![0..4, 4..9, 9..10]);
+    for span in spans {
+        assert!(haystack.is_char_boundary(span.start));
+        assert!(haystack.is_char_boundary(span.end));
+    }
+}
@@ -0,0 +1,267 @@
+use rstest::rstest;
+use simplematch::{
+    dowild_with, to_regex_string, try_dowild_with, utf8, validate, Options, Pattern,
+    PatternErrorKind, SimpleMatchError, WildSet,
+};
+
+const fn options() -> Options<u8> {
+    Options::new()
+        .enable_braces(true)
+        .enable_classes(true)
+        .enable_escape(true)
+        .enable_extglob(true)
+}
+
+#[rstest]
+#[case::empty("")]
+#[case::wildcards("*?")]
+#[case::class("[a-z]")]
+#[case::class_with_closing_bracket("[]a]")]
+#[case::escaped_class("\\[a")]
+#[case::escaped_escape("a\\\\")]
+#[case::escape_before_literal("\\a")]
+#[case::braces("{a,{b,c}}")]
+#[case::escaped_brace("\\{a")]
+#[case::separator_outside_of_braces("a,b")]
+#[case::extglob("@(a|+(b))")]
+#[case::escaped_extglob_close("a\\)")]
+#[case::separator_outside_of_extglob("a|b")]
+fn validate_when_valid(#[case] pattern: &str) {
+    assert_eq!(validate(pattern.as_bytes(), options()), Ok(()));
+}
+
+#[rstest]
+#[case::trailing_escape("a\\", PatternErrorKind::TrailingEscape, 1..2)]
+#[case::only_escape("\\", PatternErrorKind::TrailingEscape, 0..1)]
+#[case::unclosed_class("a[bc", PatternErrorKind::UnclosedClass, 1..4)]
+#[case::unclosed_class_at_end("a[", PatternErrorKind::UnclosedClass, 1..2)]
+#[case::unclosed_empty_class("[]", PatternErrorKind::UnclosedClass, 0..2)]
+#[case::unclosed_brace("a{b,c", PatternErrorKind::UnclosedBrace, 1..2)]
+#[case::unclosed_nested_brace("{a,{b}", PatternErrorKind::UnclosedBrace, 0..1)]
+#[case::unmatched_brace_close("a}b", PatternErrorKind::UnmatchedBraceClose, 1..2)]
+#[case::unclosed_extglob("a+(b|c", PatternErrorKind::UnclosedExtglob, 1..3)]
+#[case::unmatched_extglob_close("a)", PatternErrorKind::UnmatchedExtglobClose, 1..2)]
+#[case::leftmost("}[", PatternErrorKind::UnmatchedBraceClose, 0..1)]
+fn validate_when_invalid(
+    #[case] pattern: &str,
+    #[case] kind: PatternErrorKind,
+    #[case] span: core::ops::Range<usize>,
+) {
+    match validate(pattern.as_bytes(), options()) {
+        Err(SimpleMatchError::InvalidPattern(error)) => {
+            assert_eq!(error.kind(), kind);
+            assert_eq!(error.span(), span);
+        }
+        result => panic!("Expected an invalid pattern but got {result:?}"),
+    }
+}
+
+#[rstest]
+#[case::escape("a\\", Options::new())]
+#[case::class("[a", Options::new().enable_escape(true))]
+#[case::brace("{a", Options::new().enable_classes(true))]
+#[case::extglob("@(a", Options::new().enable_braces(true))]
+fn validate_when_disabled(#[case] pattern: &str, #[case] options: Options<u8>) {
+    assert_eq!(validate(pattern.as_bytes(), options), Ok(()));
+}
+
+#[test]
+fn validate_when_options_are_invalid() {
+    assert_eq!(
+        validate(b"[a", options().wildcard_one_with(b'*')),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
+
+#[rstest]
+#[case::trailing_escape(
+    "*.rs\\",
+    options(),
+    "Invalid pattern: The pattern ends with the escape character.\n    *.rs\\\n        ^\nHelp: \
+     Remove the `\\` or escape it as `\\\\` to match it literally."
+)]
+#[case::unclosed_class(
+    "*.[ch",
+    options(),
+    "Invalid pattern: The character class is not closed.\n    *.[ch\n      ^^^\nHelp: Close the \
+     class with `]` or escape it as `\\[`."
+)]
+#[case::unclosed_class_without_escape(
+    "*.[ch",
+    Options::new().enable_classes(true),
+    "Invalid pattern: The character class is not closed.\n    *.[ch\n      ^^^\nHelp: Close the \
+     class with `]`."
+)]
+#[case::unmatched_brace_close(
+    "a}",
+    options(),
+    "Invalid pattern: The closing brace has no opening brace.\n    a}\n     ^\nHelp: Open an \
+     alternation with `{` before it or escape it as `\\}`."
+)]
+#[case::unclosed_extglob(
+    "!(a|b",
+    options(),
+    "Invalid pattern: The extended glob is not closed.\n    !(a|b\n    ^^\nHelp: Close the group \
+     with `)` or escape it as `\\(`."
+)]
+#[case::unmatched_extglob_close(
+    "a)",
+    options(),
+    "Invalid pattern: The closing parenthesis has no extended glob to close.\n    a)\n     \
+     ^\nHelp: Open a group like `@(` before it or escape it as `\\)`."
+)]
+#[case::custom_escape(
+    "a%",
+    Options::new().enable_escape_with(b'%'),
+    "Invalid pattern: The pattern ends with the escape character.\n    a%\n     ^\nHelp: Remove \
+     the `%` or escape it as `%%` to match it literally."
+)]
+#[case::custom_class_tokens(
+    "<a",
+    options().class_open_with(b'<').class_close_with(b'>'),
+    "Invalid pattern: The character class is not closed.\n    <a\n    ^^\nHelp: Close the class \
+     with `>` or escape it as `\\<`."
+)]
+#[case::control_character(
+    "\t[",
+    options(),
+    "Invalid pattern: The character class is not closed.\n    \u{fffd}[\n     ^\nHelp: Close the \
+     class with `]` or escape it as `\\[`."
+)]
+fn validate_display(#[case] pattern: &str, #[case] options: Options<u8>, #[case] expected: &str) {
+    assert_eq!(
+        validate(pattern.as_bytes(), options)
+            .unwrap_err()
+            .to_string(),
+        expected
+    );
+}
+
+#[test]
+fn validate_display_non_ascii_bytes() {
+    assert_eq!(
+        validate("ä[".as_bytes(), options())
+            .unwrap_err()
+            .to_string(),
+        "Invalid pattern: The character class is not closed.\n    \u{fffd}\u{fffd}[\n      \
+         ^\nHelp: Close the class with `]` or escape it as `\\[`."
+    );
+}
+
+#[rstest]
+#[case::ascii("a[b", 1..3, "    a[b\n     ^^\n")]
+#[case::non_ascii("äö[ü", 4..7, "    äö[ü\n      ^^\n")]
+#[case::non_ascii_after("{äö", 0..1, "    {äö\n    ^\n")]
+fn utf8_validate(
+    #[case] pattern: &str,
+    #[case] span: core::ops::Range<usize>,
+    #[case] rendered: &str,
+) {
+    let options = Options::new().enable_braces(true).enable_classes(true);
+    match utf8::validate(pattern, options) {
+        Err(SimpleMatchError::InvalidPattern(error)) => {
+            assert_eq!(error.span(), span);
+            assert!(error.to_string().contains(rendered), "{error}");
+        }
+        result => panic!("Expected an invalid pattern but got {result:?}"),
+    }
+}
+
+#[rstest]
+#[case::unclosed_negated_class("[!a-z", "[!a-z")]
+#[case::unclosed_class_with_wildcard("[!a-z*", "[!a-z.txt")]
+#[case::trailing_escape("a\\", "a\\")]
+#[case::unclosed_brace("{a,b", "{a,b")]
+#[case::unclosed_extglob("@(a", "@(a")]
+fn strict_when_invalid(#[case] pattern: &str, #[case] haystack: &str) {
+    let lenient = options();
+    let strict = options().strict(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), lenient),
+        true
+    );
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), strict),
+        false
+    );
+    assert_eq!(
+        try_dowild_with(pattern.as_bytes(), haystack.as_bytes(), lenient),
+        Ok(true)
+    );
+    assert!(matches!(
+        try_dowild_with(pattern.as_bytes(), haystack.as_bytes(), strict),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+    assert!(matches!(
+        Pattern::new(pattern.as_bytes(), strict),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+    assert!(matches!(
+        WildSet::new(["*", pattern], strict),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+    assert!(matches!(
+        to_regex_string(pattern.as_bytes(), strict),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+    assert_eq!(utf8::dowild_with(pattern, haystack, strict_chars()), false);
+    assert!(matches!(
+        utf8::try_dowild_with(pattern, haystack, strict_chars()),
+        Err(SimpleMatchError::InvalidPattern(_))
+    ));
+}
+
+#[rstest]
+#[case::class("[!a-z]*", "1.txt", true)]
+#[case::class_no_match("[!a-z]*", "a.txt", false)]
+#[case::escaped_class("\\[!a-z", "[!a-z", true)]
+#[case::escape_before_literal("\\a", "\\a", true)]
+#[case::braces("{a,b}", "b", true)]
+fn strict_when_valid(#[case] pattern: &str, #[case] haystack: &str, #[case] expected: bool) {
+    let strict = options().strict(true);
+
+    assert_eq!(
+        dowild_with(pattern.as_bytes(), haystack.as_bytes(), strict),
+        expected
+    );
+    assert_eq!(
+        try_dowild_with(pattern.as_bytes(), haystack.as_bytes(), strict),
+        Ok(expected)
+    );
+    assert_eq!(
+        Pattern::new(pattern.as_bytes(), strict)
+            .unwrap()
+            .is_match(haystack.as_bytes()),
+        expected
+    );
+    assert_eq!(
+        utf8::try_dowild_with(pattern, haystack, strict_chars()),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn try_dowild_with_when_options_are_invalid() {
+    assert_eq!(
+        try_dowild_with(b"a", b"a", Options::new().wildcard_one_with(b'*')),
+        Err(SimpleMatchError::DuplicateCharacterAssignment)
+    );
+}
+
+#[test]
+fn utf8_try_dowild_with_span_is_byte_offset() {
+    match utf8::try_dowild_with("ä[b", "ä[b", strict_chars()) {
+        Err(SimpleMatchError::InvalidPattern(error)) => assert_eq!(error.span(), 2..4),
+        result => panic!("Expected an invalid pattern but got {result:?}"),
+    }
+}
+
+const fn strict_chars() -> Options<char> {
+    Options::new()
+        .enable_braces(true)
+        .enable_classes(true)
+        .enable_escape(true)
+        .enable_extglob(true)
+        .strict(true)
+}
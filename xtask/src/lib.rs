@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 use common::{pattern_to_regex, PatternOptions};
 use rand::distr::uniform::{UniformSampler, UniformUsize};
@@ -7,9 +9,16 @@ use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
 pub mod gungraun;
+pub mod unicode;
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Generate the case folding tables of the `unicode` feature from the `CaseFolding.txt` of
+    /// the Unicode Character Database. Output is the rust source of `src/unicode/tables.rs`
+    GenerateCaseFolding {
+        /// The path to the `CaseFolding.txt`
+        path: PathBuf,
+    },
     /// Generate the random data for the benchmarks. Output is one benchmark as json per line
     GenerateRandomBenchmark {
         #[arg(short = 'k', long)]
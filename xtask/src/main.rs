@@ -11,6 +11,9 @@ fn main() {
     let args = xtask::Args::parse();
     if let Some(commands) = args.command {
         match commands {
+            Commands::GenerateCaseFolding { path } => {
+                print!("{}", xtask::unicode::generate_case_folding(&path));
+            }
             Commands::GenerateRandomBenchmark {
                 pattern_length,
                 haystack_length,
@@ -0,0 +1,128 @@
+//! Generate the unicode tables of simplematch from the Unicode Character Database
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Generate the case folding tables from the `CaseFolding.txt` of the Unicode Character
+/// Database as rust source
+///
+/// The turkic mappings (status `T`) are ignored.
+pub fn generate_case_folding(path: &Path) -> String {
+    let data = fs::read_to_string(path).expect("The CaseFolding.txt should be readable");
+
+    let mut version = None;
+    let mut simple = BTreeMap::new();
+    let mut full = BTreeMap::new();
+    for line in data.lines() {
+        if let Some(comment) = line.strip_prefix('#') {
+            if version.is_none() {
+                version = comment
+                    .trim()
+                    .strip_prefix("CaseFolding-")
+                    .and_then(|rest| rest.strip_suffix(".txt"))
+                    .map(ToOwned::to_owned);
+            }
+            continue;
+        }
+        let fields = line.split(';').map(str::trim).collect::<Vec<_>>();
+        if fields.len() < 3 {
+            continue;
+        }
+
+        let code = parse_char(fields[0]);
+        let mapping = fields[2]
+            .split_whitespace()
+            .map(parse_char)
+            .collect::<Vec<_>>();
+        match fields[1] {
+            "C" | "S" => {
+                simple.insert(code, mapping[0]);
+            }
+            "F" => {
+                full.insert(code, mapping);
+            }
+            "T" => {}
+            status => panic!("Unknown status '{status}' of the case folding of {code:?}"),
+        }
+    }
+
+    // All characters with the same simple case folding form an orbit. Each character points
+    // to the next one in ascending order and the last one back to the first.
+    let mut orbits: BTreeMap<char, Vec<char>> = BTreeMap::new();
+    for (from, to) in &simple {
+        orbits.entry(*to).or_insert_with(|| vec![*to]).push(*from);
+    }
+    let mut next = BTreeMap::new();
+    for orbit in orbits.values_mut() {
+        orbit.sort_unstable();
+        for (index, c) in orbit.iter().enumerate() {
+            next.insert(*c, orbit[(index + 1) % orbit.len()]);
+        }
+    }
+
+    let version = version.expect("The CaseFolding.txt should have a version in the header");
+    let mut out = String::new();
+    writeln!(
+        out,
+        "//! The case folding tables generated from the `CaseFolding.txt` of the Unicode \
+         Character\n//! Database version {version}\n//!\n//! DO NOT EDIT. This file is generated \
+         with `cargo xtask generate-case-folding\n//! CaseFolding.txt`."
+    )
+    .unwrap();
+
+    writeln!(
+        out,
+        "\n/// The next character with the same simple case folding for all characters whose \
+         simple\n/// case folding is shared with other characters, sorted by the character"
+    )
+    .unwrap();
+    writeln!(out, "pub const CASE_ORBITS: &[(char, char)] = &[").unwrap();
+    for (c, next) in &next {
+        writeln!(out, "    ({}, {}),", escape(*c), escape(*next)).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    writeln!(
+        out,
+        "\n/// The full case folding of all characters which fold to multiple characters, sorted \
+         by the\n/// character"
+    )
+    .unwrap();
+    writeln!(out, "pub const FULL_CASE_FOLDING: &[(char, &[char])] = &[").unwrap();
+    for (c, mapping) in &full {
+        let mapping = mapping
+            .iter()
+            .map(|c| escape(*c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(out, "    ({}, &[{mapping}]),", escape(*c)).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    writeln!(
+        out,
+        "\n/// The simple case folding of all characters which don't fold to themselves, sorted by \
+         the\n/// character"
+    )
+    .unwrap();
+    writeln!(out, "pub const SIMPLE_CASE_FOLDING: &[(char, char)] = &[").unwrap();
+    for (c, folded) in &simple {
+        writeln!(out, "    ({}, {}),", escape(*c), escape(*folded)).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    out
+}
+
+fn escape(c: char) -> String {
+    format!("'\\u{{{:x}}}'", c as u32)
+}
+
+fn parse_char(code: &str) -> char {
+    u32::from_str_radix(code, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .expect("The code point should be a valid character")
+}